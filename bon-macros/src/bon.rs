@@ -1,19 +1,77 @@
 use crate::builder;
+use crate::normalization::{ExpandCfg, ExpandCfgOutput};
 use crate::util::prelude::*;
 use darling::FromMeta;
+use quote::quote;
 
 #[derive(Debug, FromMeta)]
 pub(crate) struct BonParams {
-    // There may be config options for the proc macro in the future here
+    /// Path to the `bon` crate that the generated code references. It's the
+    /// default for the `#[builder(crate = ...)]` of the methods.
+    #[darling(rename = "crate", default, with = builder::parse_crate_path)]
+    bon: Option<syn::Path>,
 }
 
-pub(crate) fn generate(_: BonParams, item: syn::Item) -> Result<TokenStream2> {
+pub(crate) fn generate(params: TokenStream2, item: syn::Item) -> Result<TokenStream2> {
+    let expand_cfg = ExpandCfg {
+        macro_ident: syn::parse_quote!(bon),
+        params,
+        item,
+    };
+
+    let (params, mut item) = match expand_cfg.expand_cfg()? {
+        ExpandCfgOutput::Expanded { params, item } => (params, *item),
+        ExpandCfgOutput::Recurse(output) => return Ok(output),
+    };
+
+    let meta = darling::ast::NestedMeta::parse_meta_list(params.clone())?;
+    let BonParams { bon } = BonParams::from_list(&meta)?;
+
+    if let Some(output) = expand_async_trait_first(bon.as_ref(), &params, &mut item) {
+        return Ok(output);
+    }
+
     match item {
-        syn::Item::Impl(item_impl) => builder::item_impl::generate(item_impl),
+        syn::Item::Impl(item_impl) => builder::item_impl::generate(item_impl, bon),
+        syn::Item::Trait(item_trait) => builder::item_trait::generate(item_trait, bon),
         _ => bail!(
             &item,
             "`#[bon]` attribute is expected to be placed on an `impl` block \
-             but it was placed on other syntax instead"
+             or a `trait` definition but it was placed on other syntax instead"
         ),
     }
 }
+
+/// Macros like `#[async_trait]` desugar the `async fn` methods, so the builders
+/// must be generated for the desugared signatures. If such a macro is placed
+/// below `#[bon]`, then it's moved above it, and `#[bon]` is invoked once again
+/// via the path from `#[bon(crate = ...)]`, which is `::bon` by default.
+/// Returns `None` if there is no such macro below `#[bon]`.
+fn expand_async_trait_first(
+    bon: Option<&syn::Path>,
+    params: &TokenStream2,
+    item: &mut syn::Item,
+) -> Option<TokenStream2> {
+    let attrs = match item {
+        syn::Item::Impl(item_impl) => &mut item_impl.attrs,
+        syn::Item::Trait(item_trait) => &mut item_trait.attrs,
+        _ => return None,
+    };
+
+    let is_async_trait = |attr: &syn::Attribute| {
+        attr.path()
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "async_trait")
+    };
+
+    let index = attrs.iter().position(is_async_trait)?;
+    let async_trait = attrs.remove(index);
+    let bon = bon.cloned().unwrap_or_else(|| syn::parse_quote!(::bon));
+
+    Some(quote! {
+        #async_trait
+        #[#bon::bon(#params)]
+        #item
+    })
+}
@@ -0,0 +1,100 @@
+use super::BuilderGenCtx;
+use crate::util::prelude::*;
+use quote::quote;
+
+impl BuilderGenCtx {
+    /// Generates the method that overwrites the fields of an existing instance
+    /// of the struct with the values of the members that were set in the builder.
+    pub(crate) fn apply_to_method_impl(&self) -> Option<TokenStream2> {
+        let struct_ty = self.apply_to.as_ref()?;
+
+        let bon = &self.bon;
+        let state_param = self.state_param();
+        let state_arg = self.state_arg();
+        let vis = self.finish_func_vis();
+        let builder_ident = &self.builder_ident;
+        let generics_decl = &self.generics.params;
+        let generic_args = self.generic_args();
+        let where_clause_predicates = self
+            .generics
+            .where_clause
+            .as_ref()
+            .into_iter()
+            .flat_map(|where_clause| &where_clause.predicates);
+
+        let assignments: Vec<_> = self
+            .stored_members()
+            .map(|member| {
+                let ident = &member.ident;
+
+                // The members that aren't tracked in the type state are always
+                // present in the builder
+                if !member.is_named() {
+                    return quote! {
+                        target.#ident = self.__private_impl.#ident;
+                    };
+                }
+
+                if !member.is_stored_as_is() {
+                    return quote! {
+                        if let ::core::option::Option::Some(value) = self.__private_impl.#ident {
+                            target.#ident = value;
+                        }
+                    };
+                }
+
+                // An `Option` member that was explicitly set to `None` overwrites
+                // the field. Only the type state can tell that, so the builders
+                // without it leave the field untouched for `None`.
+                let explicitly_set = state_arg.filter(|_| !self.has_mut_setters(member)).map(
+                    |state_var| {
+                        let assoc_type_ident = &member.state_assoc_type_ident;
+                        quote!(<#state_var::#assoc_type_ident as #bon::state::MemberState>::IS_SET)
+                    },
+                );
+
+                if let Some(explicitly_set) = explicitly_set {
+                    return quote! {
+                        if #explicitly_set {
+                            target.#ident = self.__private_impl.#ident;
+                        }
+                    };
+                }
+
+                quote! {
+                    if let ::core::option::Option::Some(value) = self.__private_impl.#ident {
+                        target.#ident = ::core::option::Option::Some(value);
+                    }
+                }
+            })
+            .collect();
+
+        let maybe_unused_target = assignments.is_empty().then(|| quote!(let _ = target;));
+
+        Some(quote! {
+            // The state bounds don't mention the member types, so the lifetimes
+            // of the builder may be used only once in this header
+            #[allow(single_use_lifetimes)]
+            impl<
+                #(#generics_decl,)*
+                #state_param
+            >
+            #builder_ident<
+                #(#generic_args,)*
+                #state_arg
+            >
+            where
+                #( #where_clause_predicates, )*
+            {
+                /// Overwrites the fields of the `target` with the values of the members
+                /// that were set in this builder. The other fields are left untouched.
+                /// It's available in any state of the builder, because the values of
+                /// the members that weren't set are taken from the `target`.
+                #vis fn apply_to(self, target: &mut #struct_ty) {
+                    #maybe_unused_target
+                    #( #assignments )*
+                }
+            }
+        })
+    }
+}
@@ -0,0 +1,794 @@
+use super::{AssocMethodCtx, BuilderGenCtx, Member};
+use crate::util::prelude::*;
+use proc_macro2::Span;
+use quote::{format_ident, quote};
+
+impl BuilderGenCtx {
+    pub(crate) fn builder_derives(&self) -> Result<TokenStream2> {
+        let clone = self.derives.clone.is_present().then(|| self.derive_clone());
+        let debug = self.derives.debug.is_present().then(|| self.derive_debug());
+        let partial_eq = self
+            .derives
+            .partial_eq
+            .is_present()
+            .then(|| self.derive_partial_eq());
+        let eq = self.derives.eq.is_present().then(|| self.derive_eq());
+        let deserialize = self
+            .derives
+            .deserialize
+            .is_present()
+            .then(|| self.derive_deserialize())
+            .transpose()?;
+        let args = self
+            .derives
+            .args
+            .is_present()
+            .then(|| self.derive_args())
+            .transpose()?;
+        let json_schema = self
+            .derives
+            .json_schema
+            .is_present()
+            .then(|| self.derive_json_schema());
+
+        Ok(quote! {
+            #clone
+            #debug
+            #partial_eq
+            #eq
+            #deserialize
+            #args
+            #json_schema
+        })
+    }
+
+    /// Generates the `impl` block header for a trait implemented for the
+    /// builder in any state with the given additional where predicates.
+    fn derive_impl_header(
+        &self,
+        trait_path: &TokenStream2,
+        predicates: impl IntoIterator<Item = TokenStream2>,
+    ) -> TokenStream2 {
+        let state_param = self.state_param();
+        let state_arg = self.state_arg();
+        let builder_ident = &self.builder_ident;
+        let generics_decl = &self.generics.params;
+        let generic_args = self.generic_args();
+        let where_clause_predicates = self
+            .generics
+            .where_clause
+            .as_ref()
+            .into_iter()
+            .flat_map(|where_clause| &where_clause.predicates);
+
+        let predicates = predicates.into_iter();
+
+        quote! {
+            impl<
+                #(#generics_decl,)*
+                #state_param
+            >
+            #trait_path for #builder_ident<
+                #(#generic_args,)*
+                #state_arg
+            >
+            where
+                #( #where_clause_predicates, )*
+                #( #predicates, )*
+        }
+    }
+
+    /// Types of the values of the members stored in the builder. Named members
+    /// are stored wrapped in an `Option`, but the bounds are placed on the
+    /// types of their values, because the compiler can't infer the bounds of
+    /// the values from the bounds of the `Option` when the values are accessed
+    /// directly.
+    fn stored_member_types(&self) -> impl Iterator<Item = &syn::Type> {
+        self.stored_members().map(|member| {
+            if member.is_named() {
+                member.inner_type()
+            } else {
+                &member.ty
+            }
+        })
+    }
+
+    fn derive_clone(&self) -> TokenStream2 {
+        let clone = quote!(::core::clone::Clone);
+        let impl_header = self.derive_impl_header(&clone, self.stored_value_predicates(&clone));
+        let builder_clone = self.builder_clone_expr();
+
+        quote! {
+            #impl_header
+            {
+                fn clone(&self) -> Self {
+                    #builder_clone
+                }
+            }
+        }
+    }
+
+    /// Bounds that require the receiver and the values of all members stored
+    /// in the builder to implement the given trait.
+    pub(crate) fn stored_value_predicates<'a>(
+        &'a self,
+        trait_path: &'a TokenStream2,
+    ) -> impl Iterator<Item = TokenStream2> + 'a {
+        let receiver_ty = self
+            .assoc_method_ctx
+            .as_ref()
+            .and_then(AssocMethodCtx::as_receiver)
+            .map(|receiver| &receiver.without_self_keyword);
+
+        receiver_ty
+            .map(Box::as_ref)
+            .into_iter()
+            .chain(self.stored_member_types())
+            .map(move |ty| quote!(#ty: #trait_path))
+    }
+
+    /// Expression that clones the builder referenced by `self` into a new
+    /// builder in the same state.
+    pub(crate) fn builder_clone_expr(&self) -> TokenStream2 {
+        let clone = quote!(::core::clone::Clone);
+        let builder_private_impl_ident = &self.builder_private_impl_ident;
+
+        let maybe_receiver_field = self
+            .assoc_method_ctx
+            .as_ref()
+            .and_then(AssocMethodCtx::as_receiver)
+            .map(|_| {
+                quote! {
+                    receiver: #clone::clone(&self.__private_impl.receiver),
+                }
+            });
+
+        let member_idents = self.member_idents();
+
+        self.builder_expr(quote! {
+            #builder_private_impl_ident {
+                _phantom: ::core::marker::PhantomData,
+                #maybe_receiver_field
+                #( #member_idents: #clone::clone(&self.__private_impl.#member_idents), )*
+            }
+        })
+    }
+
+    /// Compares the builders by the values of the receiver and the members
+    /// stored in them. The builders may be in different states, because the
+    /// order of the setter calls is reflected in the type of the state, while
+    /// the builders with the same members set to equal values are equal.
+    fn derive_partial_eq(&self) -> TokenStream2 {
+        let partial_eq = quote!(::core::cmp::PartialEq);
+        let state_var = &self.state_var;
+        let other_state_var = format_ident!("{}Other", state_var.raw_name());
+        let builder_ident = &self.builder_ident;
+        let builder_state_trait = self.builder_state_trait();
+
+        // The builder without a type state is compared only with itself
+        let (state_params, state_arg, other_state_arg) = if self.runtime_checks.is_some() {
+            (None, None, None)
+        } else {
+            (
+                Some(quote! {
+                    #state_var: #builder_state_trait,
+                    #other_state_var: #builder_state_trait
+                }),
+                Some(state_var),
+                Some(&other_state_var),
+            )
+        };
+        let generics_decl = &self.generics.params;
+        let generic_args = self.generic_args().collect::<Vec<_>>();
+        let where_clause_predicates = self
+            .generics
+            .where_clause
+            .as_ref()
+            .into_iter()
+            .flat_map(|where_clause| &where_clause.predicates);
+        let predicates = self.stored_value_predicates(&partial_eq);
+
+        let maybe_receiver_cmp = self
+            .assoc_method_ctx
+            .as_ref()
+            .and_then(AssocMethodCtx::as_receiver)
+            .map(|_| {
+                quote! {
+                    && #partial_eq::eq(&self.__private_impl.receiver, &other.__private_impl.receiver)
+                }
+            });
+
+        let member_idents = self.member_idents();
+
+        quote! {
+            impl<
+                #(#generics_decl,)*
+                #state_params
+            >
+            #partial_eq<#builder_ident<#(#generic_args,)* #other_state_arg>>
+            for #builder_ident<#(#generic_args,)* #state_arg>
+            where
+                #( #where_clause_predicates, )*
+                #( #predicates, )*
+            {
+                fn eq(&self, other: &#builder_ident<#(#generic_args,)* #other_state_arg>) -> bool {
+                    true
+                    #maybe_receiver_cmp
+                    #( && #partial_eq::eq(&self.__private_impl.#member_idents, &other.__private_impl.#member_idents) )*
+                }
+            }
+        }
+    }
+
+    fn derive_eq(&self) -> TokenStream2 {
+        let eq = quote!(::core::cmp::Eq);
+        let impl_header = self.derive_impl_header(&eq, self.stored_value_predicates(&eq));
+
+        quote! {
+            #impl_header {}
+        }
+    }
+
+    /// Outputs the values of the members that are set, and marks the members
+    /// that aren't set yet as `<unset>`.
+    fn derive_debug(&self) -> TokenStream2 {
+        let state_var = &self.state_var;
+        let bon = &self.bon;
+        let debug = quote!(::core::fmt::Debug);
+        let builder_name = self.builder_ident.raw_name();
+
+        let receiver_ty = self
+            .assoc_method_ctx
+            .as_ref()
+            .and_then(AssocMethodCtx::as_receiver)
+            .map(|receiver| &receiver.without_self_keyword);
+
+        let predicates = receiver_ty
+            .map(Box::as_ref)
+            .into_iter()
+            .chain(self.stored_member_types())
+            .map(|ty| quote!(#ty: #debug));
+
+        let impl_header = self.derive_impl_header(&debug, predicates);
+
+        let maybe_receiver_field = receiver_ty.map(|_| {
+            quote! {
+                output.field("self", &self.__private_impl.receiver);
+            }
+        });
+
+        let member_fields = self.stored_members().map(|member| {
+            let ident = &member.ident;
+            let name = member.public_ident().raw_name();
+
+            if !member.is_named() {
+                return quote! {
+                    output.field(#name, &self.__private_impl.#ident);
+                };
+            }
+
+            let unset = quote!(output.field(#name, &::core::format_args!("<unset>")));
+
+            if member.is_required() {
+                return quote! {
+                    match &self.__private_impl.#ident {
+                        ::core::option::Option::Some(value) => output.field(#name, value),
+                        ::core::option::Option::None => #unset,
+                    };
+                };
+            }
+
+            // Optional members may be explicitly set to `None`, so the type
+            // state is the only way to tell if they were set. The state of the
+            // members with `&mut self` setters is always unset, and the builder
+            // may have no type state at all, so only the values tell if they
+            // were set in these cases.
+            let assoc_type_ident = &member.state_assoc_type_ident;
+            let is_set = if self.has_mut_setters(member) || self.runtime_checks.is_some() {
+                quote!(self.__private_impl.#ident.is_some())
+            } else {
+                quote!(<#state_var::#assoc_type_ident as #bon::state::MemberState>::IS_SET)
+            };
+
+            quote! {
+                if #is_set {
+                    output.field(#name, &self.__private_impl.#ident);
+                } else {
+                    #unset;
+                }
+            }
+        });
+
+        quote! {
+            #impl_header
+            {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    let mut output = f.debug_struct(#builder_name);
+                    #maybe_receiver_field
+                    #( #member_fields )*
+                    output.finish()
+                }
+            }
+        }
+    }
+
+    /// Deserializes the builder from a map of the members' values. Missing keys
+    /// map to unset members and present keys map to set ones. The values must
+    /// agree with the builder's type state, which is checked at runtime.
+    fn derive_deserialize(&self) -> Result<TokenStream2> {
+        let state_var = &self.state_var;
+        self.validate_derive_from_named_members("Deserialize", self.derives.deserialize.span())?;
+
+        let bon = &self.bon;
+        let serde = quote!(#bon::private::serde);
+        let serde_crate = serde.to_string();
+        let builder_ident = &self.builder_ident;
+        let builder_private_impl_ident = &self.builder_private_impl_ident;
+        let builder_state_trait = self.builder_state_trait();
+        let generics_decl = &self.generics.params;
+        let generic_args = self.generic_args().collect::<Vec<_>>();
+        let where_clause_predicates = self
+            .generics
+            .where_clause
+            .as_ref()
+            .into_iter()
+            .flat_map(|where_clause| &where_clause.predicates)
+            .collect::<Vec<_>>();
+
+        let members = self.named_members().collect::<Vec<_>>();
+        let member_idents = members
+            .iter()
+            .map(|member| &member.ident)
+            .collect::<Vec<_>>();
+        let member_types = members
+            .iter()
+            .map(|member| member.storage_type())
+            .collect::<Vec<_>>();
+
+        let member_names = members
+            .iter()
+            .map(|member| member.public_ident().raw_name());
+
+        // The types of all members are referenced in the phantom data to make
+        // sure every generic parameter of the builder is used
+        let phantom_types = self
+            .assoc_method_ctx
+            .as_ref()
+            .map(AssocMethodCtx::ty_without_self_keyword)
+            .into_iter()
+            .chain(self.members.iter().map(|member| member.ty.as_ref()))
+            .chain(&self.extra_phantom_types);
+
+        // Serde's inferred bounds would require the generic parameters to
+        // implement `Deserialize` and `Default`, which is too strict
+        let deserialize_bounds = member_types
+            .iter()
+            .map(|ty| quote!(#ty: #serde::Deserialize<'de>).to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        // Only the required members that are set in the state must be present.
+        // The values of the unset members are accepted too, and the setters
+        // called later override them. The optional members may be set to `None`,
+        // so their keys may be missing in any state. The state of the members
+        // with `&mut self` setters is always unset.
+        let state_checks = members
+            .iter()
+            .filter(|member| member.is_required() && !self.has_mut_setters(member))
+            .map(|member| {
+                let ident = &member.ident;
+                let name = member.public_ident().raw_name();
+                let assoc_type_ident = &member.state_assoc_type_ident;
+
+                quote! {
+                    let is_set = <
+                        #state_var::#assoc_type_ident as #bon::state::MemberState
+                    >::IS_SET;
+
+                    if is_set && members.#ident.is_none() {
+                        return ::core::result::Result::Err(
+                            <__D::Error as #serde::de::Error>::missing_field(#name)
+                        );
+                    }
+                }
+            });
+
+        let builder = self.builder_expr(quote! {
+            #builder_private_impl_ident {
+                _phantom: ::core::marker::PhantomData,
+                #( #member_idents: members.#member_idents, )*
+            }
+        });
+
+        Ok(quote! {
+            impl<
+                'de,
+                #(#generics_decl,)*
+                #state_var: #builder_state_trait
+            >
+            #serde::Deserialize<'de> for #builder_ident<
+                #(#generic_args,)*
+                #state_var
+            >
+            where
+                #( #where_clause_predicates, )*
+                #( #member_types: #serde::Deserialize<'de>, )*
+            {
+                fn deserialize<__D: #serde::Deserializer<'de>>(
+                    deserializer: __D
+                ) -> ::core::result::Result<Self, __D::Error> {
+                    #[derive(#serde::Deserialize)]
+                    #[serde(
+                        crate = #serde_crate,
+                        bound(deserialize = #deserialize_bounds),
+                        deny_unknown_fields,
+                    )]
+                    struct __Members<#(#generics_decl,)*>
+                    where
+                        #( #where_clause_predicates, )*
+                    {
+                        #(
+                            #[serde(default, rename = #member_names)]
+                            #member_idents: #member_types,
+                        )*
+
+                        #[serde(skip)]
+                        __phantom: ::core::marker::PhantomData<(#(#phantom_types,)*)>,
+                    }
+
+                    let members = <
+                        __Members<#(#generic_args,)*> as #serde::Deserialize<'de>
+                    >::deserialize(deserializer)?;
+
+                    #( #state_checks )*
+
+                    ::core::result::Result::Ok(#builder)
+                }
+            }
+        })
+    }
+
+    /// Checks that the builder can be created from the values of its named
+    /// members only, which is required by the traits that construct it.
+    fn validate_derive_from_named_members(&self, trait_name: &str, span: Span) -> Result {
+        if self
+            .assoc_method_ctx
+            .as_ref()
+            .and_then(AssocMethodCtx::as_receiver)
+            .is_some()
+        {
+            bail!(
+                &span,
+                "`{trait_name}` can't be derived for builders of methods \
+                with a `self` receiver, because the receiver can't be constructed \
+                from the members' values"
+            );
+        }
+
+        if self
+            .members
+            .iter()
+            .any(|member| member.is_start_fn() || member.is_field())
+        {
+            bail!(
+                &span,
+                "`{trait_name}` can't be derived for builders with \
+                `#[builder(start_fn)]` or `#[builder(field)]` members, \
+                because their values are provided by the starting function"
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Generates `clap::Args` for the builder. The members that are set in the
+    /// builder's type state become the command line arguments. The required
+    /// members map to required arguments, and the optional ones to optional
+    /// arguments. The docs of the members are used as the help text.
+    fn derive_args(&self) -> Result<TokenStream2> {
+        let state_var = &self.state_var;
+        let bon = &self.bon;
+        self.validate_derive_from_named_members("Args", self.derives.args.span())?;
+
+        let clap = quote!(#bon::private::clap);
+        let builder_private_impl_ident = &self.builder_private_impl_ident;
+
+        let members = self.named_members().collect::<Vec<_>>();
+
+        let value_predicates = members.iter().map(|member| {
+            let ty = member.inner_type();
+            quote! {
+                #ty: ::core::clone::Clone
+                    + ::core::marker::Send
+                    + ::core::marker::Sync
+                    + 'static
+            }
+        });
+
+        let impl_header_args =
+            self.derive_impl_header(&quote!(#clap::Args), [quote!(Self: #clap::FromArgMatches)]);
+        let impl_header_from_arg_matches =
+            self.derive_impl_header(&quote!(#clap::FromArgMatches), value_predicates);
+
+        let is_set = |member: &Member| {
+            let assoc_type_ident = &member.state_assoc_type_ident;
+            quote!(<#state_var::#assoc_type_ident as #bon::state::MemberState>::IS_SET)
+        };
+
+        let args = members
+            .iter()
+            .map(|member| {
+                let id = member.public_ident().raw_name();
+                let long = id.replace('_', "-");
+                let ty = member.inner_type();
+                let is_set = is_set(member);
+                let help = member.doc_text().map(|help| quote!(.help(#help)));
+
+                let action = if member.params.flag.is_present() {
+                    quote!(#clap::ArgAction::SetTrue)
+                } else {
+                    quote!(#clap::ArgAction::Set)
+                };
+
+                // The arguments are never required when updating an existing
+                // value of the builder
+                let required = member.is_required().then(|| quote!(.required(!for_update)));
+
+                let arg = quote! {
+                    #clap::Arg::new(#id)
+                        .long(#long)
+                        .action(#action)
+                        .value_parser(#clap::value_parser!(#ty))
+                        #help
+                };
+
+                quote! {
+                    let command = if #is_set {
+                        command.arg(#arg #required)
+                    } else {
+                        command
+                    };
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let member_idents = members.iter().map(|member| &member.ident);
+        let member_values = members.iter().map(|member| {
+            let id = member.public_ident().raw_name();
+            let ty = member.inner_type();
+            let is_set = is_set(member);
+
+            quote! {
+                if #is_set {
+                    matches.remove_one::<#ty>(#id)
+                } else {
+                    ::core::option::Option::None
+                }
+            }
+        });
+
+        let builder = self.builder_expr(quote! {
+            #builder_private_impl_ident {
+                _phantom: ::core::marker::PhantomData,
+                #( #member_idents: #member_values, )*
+            }
+        });
+
+        let member_updates = members.iter().map(|member| {
+            let ident = &member.ident;
+            let id = member.public_ident().raw_name();
+            let ty = member.inner_type();
+            let is_set = is_set(member);
+
+            quote! {
+                if #is_set {
+                    if let ::core::option::Option::Some(value) = matches.remove_one::<#ty>(#id) {
+                        self.__private_impl.#ident = ::core::option::Option::Some(value);
+                    }
+                }
+            }
+        });
+
+        Ok(quote! {
+            #impl_header_from_arg_matches
+            {
+                fn from_arg_matches(
+                    matches: &#clap::ArgMatches,
+                ) -> ::core::result::Result<Self, #clap::Error> {
+                    Self::from_arg_matches_mut(&mut ::core::clone::Clone::clone(matches))
+                }
+
+                fn from_arg_matches_mut(
+                    matches: &mut #clap::ArgMatches,
+                ) -> ::core::result::Result<Self, #clap::Error> {
+                    ::core::result::Result::Ok(#builder)
+                }
+
+                fn update_from_arg_matches(
+                    &mut self,
+                    matches: &#clap::ArgMatches,
+                ) -> ::core::result::Result<(), #clap::Error> {
+                    self.update_from_arg_matches_mut(&mut ::core::clone::Clone::clone(matches))
+                }
+
+                fn update_from_arg_matches_mut(
+                    &mut self,
+                    matches: &mut #clap::ArgMatches,
+                ) -> ::core::result::Result<(), #clap::Error> {
+                    #( #member_updates )*
+                    ::core::result::Result::Ok(())
+                }
+            }
+
+            #impl_header_args
+            {
+                fn augment_args(command: #clap::Command) -> #clap::Command {
+                    let for_update = false;
+                    #( #args )*
+                    command
+                }
+
+                fn augment_args_for_update(command: #clap::Command) -> #clap::Command {
+                    let for_update = true;
+                    #( #args )*
+                    command
+                }
+            }
+        })
+    }
+
+    /// Generates `schemars::JsonSchema` for the builder. The schema describes
+    /// an object with a property for every member that can be set via setters,
+    /// regardless of the builder's type state.
+    fn derive_json_schema(&self) -> TokenStream2 {
+        let bon = &self.bon;
+        let schemars = quote!(#bon::private::schemars);
+        let serde_json = quote!(#bon::private::serde_json);
+        let builder_name = self.builder_ident.raw_name();
+
+        let members = self.named_members().collect::<Vec<_>>();
+
+        let predicates = members.iter().map(|member| {
+            let ty = member.inner_type();
+            quote!(#ty: #schemars::JsonSchema)
+        });
+
+        let impl_header = self.derive_impl_header(&quote!(#schemars::JsonSchema), predicates);
+
+        let properties = members.iter().map(|member| {
+            let name = member.public_ident().raw_name();
+            let ty = member.inner_type();
+
+            let description = member.doc_text().map(|help| {
+                quote! {
+                    schema.insert(
+                        ::std::string::String::from("description"),
+                        #serde_json::Value::from(#help),
+                    );
+                }
+            });
+
+            let default = member_default_json(member, &serde_json).map(|default| {
+                quote! {
+                    schema.insert(::std::string::String::from("default"), #default);
+                }
+            });
+
+            quote! {
+                let mut schema = generator.subschema_for::<#ty>();
+                #description
+                #default
+                properties.insert(
+                    ::std::string::String::from(#name),
+                    #serde_json::Value::from(schema),
+                );
+            }
+        });
+
+        let required = members
+            .iter()
+            .filter(|member| member.is_required())
+            .map(|member| member.public_ident().raw_name());
+
+        // The id is built from the generic arguments that the user specifies,
+        // because the type state parameter would make the id different in
+        // every state of the builder
+        let generic_args = self
+            .generics
+            .params
+            .iter()
+            .filter_map(|param| match param {
+                syn::GenericParam::Lifetime(_) => None,
+                syn::GenericParam::Type(param) => {
+                    let ident = &param.ident;
+                    Some(quote!(::core::any::type_name::<#ident>()))
+                }
+                syn::GenericParam::Const(param) => {
+                    let ident = &param.ident;
+                    Some(quote!(&::std::string::ToString::to_string(&#ident)))
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let schema_id = if generic_args.is_empty() {
+            quote! {
+                ::std::borrow::Cow::Borrowed(::core::concat!(
+                    ::core::module_path!(), "::", #builder_name
+                ))
+            }
+        } else {
+            quote! {
+                ::std::borrow::Cow::Owned(::std::format!(
+                    ::core::concat!(::core::module_path!(), "::", #builder_name, "<{}>"),
+                    [ #( #generic_args, )* ].join(", ")
+                ))
+            }
+        };
+
+        quote! {
+            #impl_header
+            {
+                fn schema_name() -> ::std::borrow::Cow<'static, str> {
+                    ::std::borrow::Cow::Borrowed(#builder_name)
+                }
+
+                // The schema doesn't depend on the type state, but the generic
+                // parameters of the builder may change it
+                fn schema_id() -> ::std::borrow::Cow<'static, str> {
+                    #schema_id
+                }
+
+                fn json_schema(
+                    generator: &mut #schemars::SchemaGenerator,
+                ) -> #schemars::Schema {
+                    let mut properties = #serde_json::Map::new();
+
+                    #( #properties )*
+
+                    let mut schema = #serde_json::Map::new();
+
+                    schema.insert(
+                        ::std::string::String::from("type"),
+                        #serde_json::Value::from("object"),
+                    );
+                    schema.insert(
+                        ::std::string::String::from("properties"),
+                        #serde_json::Value::Object(properties),
+                    );
+                    schema.insert(
+                        ::std::string::String::from("required"),
+                        #serde_json::Value::from([#( #required ),*]),
+                    );
+                    schema.insert(
+                        ::std::string::String::from("additionalProperties"),
+                        #serde_json::Value::Bool(false),
+                    );
+
+                    #schemars::Schema::from(schema)
+                }
+            }
+        }
+    }
+}
+
+/// The default value of the member in the JSON schema. It's known only if the
+/// default is a literal, because other expressions are evaluated at runtime
+/// and may reference other members.
+fn member_default_json(member: &Member, serde_json: &TokenStream2) -> Option<TokenStream2> {
+    if member.params.flag.is_present() {
+        return Some(quote!(#serde_json::Value::Bool(false)));
+    }
+
+    let default = member.params.default.as_ref()?.as_ref().as_ref()?;
+
+    let syn::Expr::Lit(syn::ExprLit { lit, .. }) = default else {
+        return None;
+    };
+
+    match lit {
+        syn::Lit::Str(_) | syn::Lit::Int(_) | syn::Lit::Float(_) | syn::Lit::Bool(_) => {
+            Some(quote!(#serde_json::Value::from(#lit)))
+        }
+        _ => None,
+    }
+}
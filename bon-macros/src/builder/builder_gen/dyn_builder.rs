@@ -0,0 +1,173 @@
+use super::BuilderGenCtx;
+use crate::util::prelude::*;
+use quote::{format_ident, quote};
+
+impl BuilderGenCtx {
+    /// Name of the dyn-compatible trait of the builder. It's the name of the
+    /// builder with `Dyn` inserted before the `Builder` suffix, e.g.
+    /// `ConfigDynBuilder` for `ConfigBuilder`.
+    fn dyn_builder_trait_ident(&self) -> syn::Ident {
+        let builder_name = self.builder_ident.raw_name();
+        let prefix = builder_name
+            .strip_suffix("Builder")
+            .unwrap_or(&builder_name);
+
+        format_ident!("{prefix}DynBuilder")
+    }
+
+    /// Generates the dyn-compatible trait with a `set_` method for every member
+    /// and a fallible finishing function, and implements it for the builder.
+    /// This is possible only if the builder doesn't have a type state.
+    pub(crate) fn dyn_builder_impl(&self) -> Result<Option<TokenStream2>> {
+        if !self.dyn_builder.is_present() {
+            return Ok(None);
+        }
+
+        let span = self.dyn_builder.span();
+
+        if self.runtime_checks.is_none() {
+            bail!(
+                &span,
+                "#[builder(dyn_builder)] requires #[builder(check = \"runtime\")], \
+                because the type of the builder with the type state changes with \
+                every setter call, so it can't be used as a trait object",
+            );
+        }
+
+        if self.finish_fn_members().next().is_some() {
+            bail!(
+                &span,
+                "#[builder(dyn_builder)] can't be used together with #[builder(finish_fn)] \
+                members, because the finishing function of the trait doesn't accept \
+                any parameters",
+            );
+        }
+
+        if self.finish_func.asyncness.is_some() || self.finish_func.unsafety.is_some() {
+            bail!(
+                &span,
+                "#[builder(dyn_builder)] can't be used if the underlying function is \
+                async or unsafe, because the finishing function of the trait is neither",
+            );
+        }
+
+        let bon = &self.bon;
+        let vis = self.builder_type_vis();
+        let builder_ident = &self.builder_ident;
+        let trait_ident = self.dyn_builder_trait_ident();
+        let generics_decl = &self.generics.params;
+        let where_clause = &self.generics.where_clause;
+        let generic_args = self.generic_args().collect::<Vec<_>>();
+        let finish_func_ident = &self.finish_func.ident;
+        let try_finish_func_ident = format_ident!("try_{}", finish_func_ident.raw_name());
+        let error = quote!(#bon::dyn_builder::Error);
+
+        let members = self.named_members().collect::<Vec<_>>();
+
+        let setter_idents = members
+            .iter()
+            .map(|member| format_ident!("set_{}", member.public_ident().raw_name()))
+            .collect::<Vec<_>>();
+
+        let setter_value_types = members.iter().map(|member| member.inner_type());
+
+        let setter_docs = members.iter().map(|member| {
+            format!(
+                "Sets the value of the `{}` member. It replaces the previous value if \
+                the member was already set.",
+                member.public_ident().raw_name(),
+            )
+        });
+
+        let setter_impls = members.iter().zip(&setter_idents).map(|(member, setter)| {
+            let ident = &member.ident;
+            let ty = member.inner_type();
+            quote! {
+                fn #setter(&mut self, value: #ty) {
+                    self.__private_impl.#ident = ::core::option::Option::Some(value);
+                }
+            }
+        });
+
+        let required_checks = members
+            .iter()
+            .filter(|member| member.is_required())
+            .map(|member| {
+                let ident = &member.ident;
+                let name = member.public_ident().raw_name();
+
+                quote! {
+                    if self.__private_impl.#ident.is_none() {
+                        return ::core::result::Result::Err(#error::MissingMember { name: #name });
+                    }
+                }
+            });
+
+        // The output of the trait's function doesn't include the error of the
+        // validation, which is returned as the `Validation` error instead
+        let output = self
+            .finish_func
+            .infallible_output
+            .as_ref()
+            .unwrap_or(&self.finish_func.output);
+
+        let output = match output {
+            syn::ReturnType::Default => quote!(()),
+            syn::ReturnType::Type(_, ty) => quote!(#ty),
+        };
+
+        let finish = if self.finish_func.infallible_output.is_some() {
+            quote! {
+                #builder_ident::#finish_func_ident(*self).map_err(|error| #error::Validation {
+                    source: ::core::convert::Into::into(error),
+                })
+            }
+        } else {
+            quote! {
+                ::core::result::Result::Ok(#builder_ident::#finish_func_ident(*self))
+            }
+        };
+
+        let trait_docs = format!(
+            "Dyn-compatible trait implemented by [`{builder_ident}`], which lets it be \
+            stored and passed around as a trait object, e.g. `Box<dyn {trait_ident}>`.",
+        );
+
+        let try_finish_docs = format!(
+            "Finishes building like [`{builder_ident}::{finish_func_ident}()`], but \
+            returns an error instead of panicking if any of the required members \
+            wasn't set.",
+        );
+
+        Ok(Some(quote! {
+            #[doc = #trait_docs]
+            #vis trait #trait_ident<#(#generics_decl,)*>
+            #where_clause
+            {
+                #(
+                    #[doc = #setter_docs]
+                    fn #setter_idents(&mut self, value: #setter_value_types);
+                )*
+
+                #[doc = #try_finish_docs]
+                fn #try_finish_func_ident(
+                    self: ::std::boxed::Box<Self>,
+                ) -> ::core::result::Result<#output, #error>;
+            }
+
+            impl<#(#generics_decl,)*> #trait_ident<#(#generic_args,)*>
+            for #builder_ident<#(#generic_args,)*>
+            #where_clause
+            {
+                #( #setter_impls )*
+
+                fn #try_finish_func_ident(
+                    self: ::std::boxed::Box<Self>,
+                ) -> ::core::result::Result<#output, #error> {
+                    #( #required_checks )*
+                    #finish
+                }
+            }
+        }))
+    }
+}
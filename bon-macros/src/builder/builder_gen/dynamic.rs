@@ -0,0 +1,180 @@
+use super::BuilderGenCtx;
+use crate::util::prelude::*;
+use quote::{format_ident, quote, ToTokens};
+
+impl BuilderGenCtx {
+    /// Generates the methods of the runtime mode of the builder, where the
+    /// members are set by their names from dynamic values, and the presence
+    /// of the required members is checked when finishing building.
+    pub(crate) fn dynamic_methods_impl(&self) -> Option<TokenStream2> {
+        let state_var = &self.state_var;
+        let bon = &self.bon;
+        if !self.dynamic.is_present() {
+            return None;
+        }
+
+        let vis = self.builder_type_vis();
+        let finish_func_vis = self.finish_func_vis();
+        let builder_ident = &self.builder_ident;
+        let builder_state_trait = self.builder_state_trait();
+        let generics_decl = &self.generics.params;
+        let generic_args = self.generic_args().collect::<Vec<_>>();
+        let where_clause_predicates = self
+            .generics
+            .where_clause
+            .as_ref()
+            .into_iter()
+            .flat_map(|where_clause| &where_clause.predicates);
+
+        let error = quote!(#bon::dynamic::Error);
+
+        let members = self.named_members().collect::<Vec<_>>();
+
+        let deserialize_predicates = members.iter().map(|member| {
+            let storage_type = member.storage_type();
+            quote!(#storage_type: #bon::private::serde::de::DeserializeOwned)
+        });
+
+        let set_by_name_arms = members.iter().map(|member| {
+            let ident = &member.ident;
+            let name = member.public_ident().raw_name();
+            let assoc_type_ident = &member.state_assoc_type_ident;
+
+            // The members with `&mut self` setters may be set repeatedly,
+            // and their state is always unset
+            let already_set_check = (!self.has_mut_setters(member)).then(|| {
+                quote! {
+                    if <#state_var::#assoc_type_ident as #bon::state::MemberState>::IS_SET {
+                        return ::core::result::Result::Err(#error::AlreadySet { name: #name });
+                    }
+                }
+            });
+
+            quote! {
+                #name => {
+                    #already_set_check
+
+                    self.__private_impl.#ident = #bon::private::serde_json::from_value(value)
+                        .map_err(|source| #error::InvalidValue { name: #name, source })?;
+                }
+            }
+        });
+
+        let required_checks = members
+            .iter()
+            .filter(|member| member.is_required())
+            .map(|member| {
+                let ident = &member.ident;
+                let name = member.public_ident().raw_name();
+
+                quote! {
+                    if self.__private_impl.#ident.is_none() {
+                        return ::core::result::Result::Err(#error::MissingMember { name: #name });
+                    }
+                }
+            });
+
+        // The state where all required members are set, which makes the
+        // finishing function available
+        let finish_state = members.iter().filter(|member| member.is_required()).fold(
+            self.empty_state().into_token_stream(),
+            |state, member| {
+                let set_state = self.set_state(member);
+                quote!(#set_state<#state>)
+            },
+        );
+
+        // The values of the members don't depend on the type state, so
+        // they're moved into the builder in the finishing state as is
+        let builder = self.builder_expr(quote!(self.__private_impl));
+
+        let asyncness = &self.finish_func.asyncness;
+        let unsafety = &self.finish_func.unsafety;
+        let safety_docs = &self.finish_func.safety_docs;
+        let finish_func_ident = &self.finish_func.ident;
+        let try_finish_func_ident = format_ident!("try_{}", finish_func_ident.raw_name());
+        let maybe_await = asyncness.is_some().then(|| quote!(.await));
+        let track_caller = self.track_caller_attr();
+        let output = match self.finish_func_output(&[]) {
+            syn::ReturnType::Default => quote!(()),
+            syn::ReturnType::Type(_, ty) => quote!(#ty),
+        };
+
+        // The parameters are forwarded to the finishing function as is,
+        // so the `Into` conversions are applied by it
+        let finish_fn_params = self.finish_fn_members().map(|member| {
+            let ident = &member.ident;
+            let ty = &member.ty;
+            quote!(#ident: #ty)
+        });
+        let finish_fn_args = self.finish_fn_members().map(|member| &member.ident);
+
+        let finish_call = quote! {
+            builder.#finish_func_ident(#( #finish_fn_args, )*) #maybe_await
+        };
+
+        let finish_call = if unsafety.is_some() {
+            quote!(unsafe { #finish_call })
+        } else {
+            finish_call
+        };
+
+        Some(quote! {
+            // The state bounds don't mention the member types, so the lifetimes
+            // of the builder may be used only once in this header
+            #[allow(single_use_lifetimes)]
+            impl<
+                #(#generics_decl,)*
+                #state_var: #builder_state_trait
+            >
+            #builder_ident<
+                #(#generic_args,)*
+                #state_var
+            >
+            where
+                #( #where_clause_predicates, )*
+            {
+                /// Sets the member with the given name from a dynamic value. The
+                /// `null` value leaves the member unset.
+                ///
+                /// Returns an error if there is no member with this name, if the
+                /// member was already set via its setter, or if the value can't
+                /// be deserialized into the member's type.
+                #vis fn set_by_name(
+                    &mut self,
+                    name: &str,
+                    value: #bon::private::serde_json::Value,
+                ) -> ::core::result::Result<(), #error>
+                where
+                    #( #deserialize_predicates, )*
+                {
+                    match name {
+                        #( #set_by_name_arms )*
+                        _ => {
+                            return ::core::result::Result::Err(#error::UnknownMember {
+                                name: ::std::borrow::ToOwned::to_owned(name),
+                            });
+                        }
+                    }
+
+                    ::core::result::Result::Ok(())
+                }
+
+                /// Finishes building if all required members are set, which is
+                /// checked at runtime. Returns an error otherwise.
+                #( #safety_docs )*
+                #track_caller
+                #finish_func_vis #asyncness #unsafety fn #try_finish_func_ident(
+                    self,
+                    #( #finish_fn_params, )*
+                ) -> ::core::result::Result<#output, #error> {
+                    #( #required_checks )*
+
+                    let builder: #builder_ident<#(#generic_args,)* #finish_state> = #builder;
+
+                    ::core::result::Result::Ok(#finish_call)
+                }
+            }
+        })
+    }
+}
@@ -0,0 +1,67 @@
+use super::BuilderGenCtx;
+use crate::util::prelude::*;
+use quote::{format_ident, quote};
+
+impl BuilderGenCtx {
+    /// Generates the finishing function that takes the builder by reference,
+    /// clones it and finishes building from the clone. It's named after the
+    /// regular finishing function with the `_clone` suffix.
+    pub(crate) fn finish_clone_method_impl(&self) -> Result<Option<TokenStream2>> {
+        if !self.finish_fn_clone.is_present() {
+            return Ok(None);
+        }
+
+        let asyncness = &self.finish_func.asyncness;
+        let unsafety = &self.finish_func.unsafety;
+        let safety_docs = &self.finish_func.safety_docs;
+        let attrs = &self.finish_func.attrs;
+        let track_caller = self.track_caller_attr();
+        let vis = self.finish_func_vis();
+        let finish_func_ident = &self.finish_func.ident;
+        let finish_clone_func_ident = format_ident!("{}_clone", finish_func_ident.raw_name());
+        let output = self.finish_func_output(&[]);
+        let impl_header = self.finish_impl_header(None);
+        let state_where_predicates = self.finish_state_where_predicates();
+        let clone = quote!(::core::clone::Clone);
+        let clone_predicates = self.stored_value_predicates(&clone);
+        let builder_clone = self.builder_clone_expr();
+        let (finish_fn_params, _) = self.finish_fn_params()?;
+        let finish_fn_args = self.finish_fn_members().map(|member| &member.ident);
+        let maybe_await = asyncness.is_some().then(|| quote!(.await));
+
+        let mut finish = quote! {
+            __builder.#finish_func_ident(#( #finish_fn_args, )*) #maybe_await
+        };
+
+        if unsafety.is_some() {
+            finish = quote!(unsafe { #finish });
+        }
+
+        let docs = format!(
+            "Finishes building from the clones of the values of the members, so the \
+            builder stays intact and can be finished again. It's the same as calling \
+            [`{finish_func_ident}`](Self::{finish_func_ident}) on a clone of the builder.",
+        );
+
+        Ok(Some(quote! {
+            #impl_header
+            {
+                #[doc = #docs]
+                #( #safety_docs )*
+                #( #attrs )*
+                #track_caller
+                #vis #asyncness #unsafety fn #finish_clone_func_ident(
+                    &self,
+                    #( #finish_fn_params, )*
+                ) #output
+                where
+                    #( #state_where_predicates, )*
+                    #( #clone_predicates, )*
+                {
+                    let __builder: Self = #builder_clone;
+                    #finish
+                }
+            }
+        }))
+    }
+}
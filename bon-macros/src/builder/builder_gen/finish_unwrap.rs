@@ -0,0 +1,86 @@
+use super::BuilderGenCtx;
+use crate::util::prelude::*;
+use quote::quote;
+
+impl BuilderGenCtx {
+    /// Generates the additional finishing function that calls the regular one
+    /// and panics with the error if the validation fails. It's useful in tests
+    /// and in the code where the values are known to be valid.
+    pub(crate) fn finish_unwrap_method_impl(&self) -> Result<Option<TokenStream2>> {
+        let Some(unwrap_func_ident) = &self.finish_func.unwrap else {
+            return Ok(None);
+        };
+
+        let Some(infallible_output) = &self.finish_func.infallible_output else {
+            bail!(
+                unwrap_func_ident,
+                "the finishing function that panics on errors can be generated only \
+                if the finishing function is fallible, i.e. there is a validation \
+                function configured with #[builder(validate)] on the builder or \
+                on any of its members, or a member with #[builder(env)]",
+            );
+        };
+
+        let asyncness = &self.finish_func.asyncness;
+        let unsafety = &self.finish_func.unsafety;
+        let safety_docs = &self.finish_func.safety_docs;
+        let attrs = &self.finish_func.attrs;
+        let vis = self.finish_func_vis();
+        let finish_func_ident = &self.finish_func.ident;
+        let output = self.precise_output(infallible_output.clone(), &[]);
+        let impl_header = self.finish_impl_header(None);
+        let state_where_predicates = self.finish_state_where_predicates();
+        let (finish_fn_params, _) = self.finish_fn_params()?;
+        let finish_fn_args = self.finish_fn_members().map(|member| &member.ident);
+        let maybe_await = asyncness.is_some().then(|| quote!(.await));
+
+        // The panic points to the caller unless the function is async,
+        // where `#[track_caller]` has no effect
+        let track_caller = asyncness.is_none().then(|| quote!(#[track_caller]));
+
+        let mut finish = quote! {
+            self.#finish_func_ident(#( #finish_fn_args, )*) #maybe_await
+        };
+
+        if unsafety.is_some() {
+            finish = quote!(unsafe { #finish });
+        }
+
+        let panic_message = format!(
+            "`{}::{finish_func_ident}()` failed: {{}}",
+            self.builder_ident.raw_name()
+        );
+
+        let docs = format!(
+            "Same as [`{finish_func_ident}`](Self::{finish_func_ident}), but panics \
+            if the validation fails instead of returning the error.\n\n\
+            # Panics\n\n\
+            Panics with the message of the error returned by \
+            [`{finish_func_ident}`](Self::{finish_func_ident}).",
+        );
+
+        Ok(Some(quote! {
+            #impl_header
+            {
+                #[doc = #docs]
+                #( #safety_docs )*
+                #( #attrs )*
+                #track_caller
+                #vis #asyncness #unsafety fn #unwrap_func_ident(
+                    self,
+                    #( #finish_fn_params, )*
+                ) #output
+                where
+                    #( #state_where_predicates, )*
+                {
+                    match #finish {
+                        ::core::result::Result::Ok(value) => value,
+                        ::core::result::Result::Err(error) => {
+                            ::core::panic!(#panic_message, error)
+                        }
+                    }
+                }
+            }
+        }))
+    }
+}
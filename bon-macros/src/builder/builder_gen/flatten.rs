@@ -0,0 +1,214 @@
+use super::{BuilderGenCtx, FlattenedFrom, Member};
+use crate::builder::params::{FlattenParams, FlattenedStruct};
+use crate::util::prelude::*;
+use darling::util::SpannedValue;
+use quote::quote;
+use syn::spanned::Spanned;
+
+impl Member {
+    /// Marks the field that stores the base struct of `#[builder(extends = ...)]`
+    /// as `#[builder(flatten)]`, so that the builder inherits its members
+    pub(crate) fn extend(members: &mut [Self], extends: &syn::Type) -> Result {
+        let mut extended = members
+            .iter_mut()
+            .filter(|member| crate::normalization::is_extended_type(&member.ty, extends));
+
+        let Some(member) = extended.next() else {
+            bail!(
+                extends,
+                "#[builder(extends = ...)] requires a field of this type, which \
+                stores the inherited part of the struct",
+            );
+        };
+
+        if let Some(other) = extended.next() {
+            bail!(
+                &other.ty,
+                "#[builder(extends = ...)] requires exactly one field of this type, \
+                because otherwise it's ambiguous which of them stores the \
+                inherited part of the struct; use #[builder(flatten(prefix = ...))] \
+                on the fields to lift the members of all of them",
+            );
+        }
+
+        if let Some(flatten) = &member.params.flatten {
+            bail!(
+                &flatten.span(),
+                "this field is already flattened by #[builder(extends = ...)], \
+                so explicit #[builder(flatten)] is redundant",
+            );
+        }
+
+        member.params.flatten = Some(SpannedValue::new(FlattenParams::default(), extends.span()));
+
+        member.validate()
+    }
+
+    /// Replaces the fields of the structs of the `#[builder(flatten)]` members
+    /// with the members of the builder. The lifted members are placed before
+    /// the member of their struct, so that their local variables are already
+    /// initialized in the finishing function when the struct is created.
+    pub(crate) fn flatten(
+        members: Vec<Self>,
+        mut structs: Vec<FlattenedStruct>,
+    ) -> Result<Vec<Self>> {
+        // Every callback puts the declaration of its struct before the ones
+        // passed by the previous callbacks
+        structs.reverse();
+
+        let mut structs = structs.into_iter();
+        let mut output = Vec::with_capacity(members.len());
+
+        for member in members {
+            let Some(params) = &member.params.flatten else {
+                output.push(member);
+                continue;
+            };
+
+            let Some(FlattenedStruct(decl)) = structs.next() else {
+                bail!(
+                    &params.span(),
+                    "#[builder(flatten)] is supported only for the fields of the \
+                    structs and the arguments of the free functions with the \
+                    #[builder] attribute on top of them, because the fields of \
+                    the nested struct are requested by invoking the attribute \
+                    once again"
+                );
+            };
+
+            if !decl.generics.params.is_empty() {
+                bail!(
+                    &member.ty,
+                    "the struct of a #[builder(flatten)] member can't have generic \
+                    parameters, because the types of its fields would have to be \
+                    specialized with the generic arguments of the member's type"
+                );
+            }
+
+            let syn::Fields::Named(fields) = &decl.fields else {
+                bail!(
+                    &member.ty,
+                    "only the structs with named fields can be flattened"
+                );
+            };
+
+            let prefix = params.prefix.as_ref();
+
+            for field in &fields.named {
+                let mut lifted = Self::from_syn_field(field)?;
+
+                if lifted.is_start_fn() || lifted.is_finish_fn() || lifted.is_flatten() {
+                    bail!(
+                        &params.span(),
+                        "the member `{}` of `{}` can't be lifted into this builder, \
+                        because #[builder(start_fn)], #[builder(finish_fn)] and \
+                        #[builder(flatten)] members of the nested struct aren't \
+                        supported",
+                        lifted.ident.raw_name(),
+                        decl.ident.raw_name(),
+                    );
+                }
+
+                lifted.normalize_self_ty(&member.ty);
+
+                let field = lifted.ident.clone();
+
+                if let Some(prefix) = prefix {
+                    lifted.add_prefix(prefix);
+                }
+
+                lifted.flattened_from = Some(FlattenedFrom {
+                    member: member.ident.clone(),
+                    field,
+                });
+
+                output.push(lifted);
+            }
+
+            output.push(member);
+        }
+
+        if let Some(FlattenedStruct(decl)) = structs.next() {
+            bail!(
+                &decl.ident,
+                "BUG: the declaration of the flattened struct wasn't used"
+            );
+        }
+
+        Ok(output)
+    }
+
+    /// Prepends the prefix to the names of the member and to the names of the
+    /// other members that it references, which are lifted from the same struct.
+    /// This way the same struct can be flattened several times with different
+    /// prefixes.
+    fn add_prefix(&mut self, prefix: &syn::Ident) {
+        let prefixed = |ident: &syn::Ident| {
+            syn::Ident::new_maybe_raw(
+                &format!("{}_{}", prefix.raw_name(), ident.raw_name()),
+                ident.span(),
+            )
+        };
+
+        let public_ident = self.public_ident();
+        let params = &mut self.params;
+
+        params.name = Some(prefixed(&public_ident));
+
+        let idents = params
+            .alias
+            .iter_mut()
+            .chain(params.group.iter_mut())
+            .chain(params.section.iter_mut())
+            .chain(
+                params
+                    .requires
+                    .iter_mut()
+                    .flat_map(|idents| idents.iter_mut()),
+            )
+            .chain(
+                params
+                    .conflicts_with
+                    .iter_mut()
+                    .flat_map(|idents| idents.iter_mut()),
+            )
+            .chain(
+                [&mut params.push, &mut params.insert, &mut params.extend]
+                    .into_iter()
+                    .filter_map(|ident| ident.as_deref_mut()?.as_mut()),
+            );
+
+        for ident in idents {
+            *ident = prefixed(ident);
+        }
+
+        self.ident = prefixed(&self.ident);
+        self.state_assoc_type_ident = self.enum_variant_ident();
+    }
+}
+
+impl BuilderGenCtx {
+    /// Expression that creates the struct of the `#[builder(flatten)]` member
+    /// from the local variables of the members lifted from it
+    pub(crate) fn flattened_struct_expr(&self, member: &Member) -> TokenStream2 {
+        let fields = self.members.iter().filter_map(|lifted| {
+            let from = lifted
+                .flattened_from
+                .as_ref()
+                .filter(|from| from.member == member.ident)?;
+
+            let field = &from.field;
+            let var = &lifted.ident;
+
+            Some(quote!(#field: #var))
+        });
+
+        let ty = &member.ty;
+
+        quote! {
+            #ty {
+                #( #fields, )*
+            }
+        }
+    }
+}
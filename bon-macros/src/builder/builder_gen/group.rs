@@ -0,0 +1,169 @@
+use super::{BuilderGenCtx, Member};
+use crate::util::prelude::*;
+use quote::quote;
+
+/// Members that share the same `#[builder(group = ...)]`. At least one of
+/// them must be set before the builder can be finished.
+pub(crate) struct MemberGroup<'a> {
+    pub(crate) ident: &'a syn::Ident,
+    pub(crate) members: Vec<&'a Member>,
+}
+
+impl MemberGroup<'_> {
+    fn member_names(&self) -> String {
+        self.members
+            .iter()
+            .map(|member| format!("`{}`", member.public_ident().raw_name()))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+impl BuilderGenCtx {
+    /// Groups of members in the order of the first member of each group
+    pub(crate) fn member_groups(&self) -> Vec<MemberGroup<'_>> {
+        let mut groups: Vec<MemberGroup<'_>> = vec![];
+
+        for member in self.named_members() {
+            let Some(ident) = &member.params.group else {
+                continue;
+            };
+
+            match groups.iter_mut().find(|group| group.ident == ident) {
+                Some(group) => group.members.push(member),
+                None => groups.push(MemberGroup {
+                    ident,
+                    members: vec![member],
+                }),
+            }
+        }
+
+        groups
+    }
+
+    fn validate_member_groups(&self) -> Result {
+        for group in self.member_groups() {
+            let group_name = group.ident.raw_name();
+
+            if let [member] = group.members.as_slice() {
+                bail!(
+                    group.ident,
+                    "the group `{group_name}` has a single member `{}`; make the \
+                    member required instead, or add other members to the group",
+                    member.ident.raw_name(),
+                );
+            }
+
+            for member in &group.members {
+                let member_name = member.ident.raw_name();
+
+                if member.is_required() {
+                    bail!(
+                        &member.ident,
+                        "the member `{member_name}` is required, so it can't be in \
+                        the group `{group_name}`, because the members of a group \
+                        must be optional",
+                    );
+                }
+
+                if self.has_mut_setters(member) {
+                    bail!(
+                        &member.ident,
+                        "the member `{member_name}` has setters that take `&mut self`, \
+                        so whether it was set isn't tracked in the type state, which \
+                        is required for the members of a group",
+                    );
+                }
+            }
+
+            if self.dynamic.is_present() {
+                bail!(
+                    &self.dynamic.span(),
+                    "#[builder(dynamic)] can't be used together with member groups, \
+                    because the state in which the builder is finished at runtime \
+                    doesn't tell which member of the group `{group_name}` was set",
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Generates the marker types of the groups. They're the arguments of the
+    /// `bon::state::IsSetGroup` trait, that names them in the compile errors if
+    /// none of the members of a group are set when finishing building.
+    pub(crate) fn group_markers_decl(&self, vis: &syn::Visibility) -> Result<TokenStream2> {
+        self.validate_member_groups()?;
+
+        let groups = self.member_groups();
+
+        if groups.is_empty() {
+            return Ok(quote!());
+        }
+
+        let marker_vis = vis.clone().into_equivalent_in_child_module()?;
+
+        let markers = groups.iter().map(|group| {
+            let group_ident = group.ident;
+            let docs = format!(
+                "Marker of the group `{}` with the members {} in the errors \
+                about none of them being set.",
+                group_ident.raw_name(),
+                group.member_names(),
+            );
+
+            quote! {
+                #[doc = #docs]
+                #marker_vis enum #group_ident {}
+            }
+        });
+
+        Ok(quote! {
+            #[doc = "Marker types of the groups in the errors about none of their members being set."]
+            #vis mod groups {
+                #( #markers )*
+            }
+        })
+    }
+
+    /// Bounds on the `__State` type parameter that require at least one member
+    /// of every group to be set. The states of the members are combined with
+    /// `MergeState`, which selects the set state if either of them is set.
+    pub(crate) fn group_state_where_predicates(&self) -> Vec<TokenStream2> {
+        let bon = &self.bon;
+        let state_var = &self.state_var;
+        let builder_mod_ident = &self.builder_mod_ident;
+        let mut predicates = vec![];
+
+        for group in self.member_groups() {
+            let mut members = group
+                .members
+                .iter()
+                .rev()
+                .map(|member| &member.state_assoc_type_ident);
+
+            let Some(last) = members.next() else {
+                continue;
+            };
+
+            let mut combined = quote!(#state_var::#last);
+
+            for assoc_type_ident in members {
+                let member_state = quote!(#state_var::#assoc_type_ident);
+                predicates.push(quote! {
+                    #member_state: #bon::private::MergeState<#combined>
+                });
+                combined = quote! {
+                    <#member_state as #bon::private::MergeState<#combined>>::Output
+                };
+            }
+
+            let group_ident = group.ident;
+            predicates.push(quote! {
+                #combined: #bon::state::IsSetGroup<#builder_mod_ident::groups::#group_ident>
+            });
+        }
+
+        predicates
+    }
+}
@@ -1,30 +1,39 @@
 use super::{
-    generic_param_to_arg, AssocFreeMethodCtx, AssocMethodCtx, AssocMethodReceiverCtx,
-    BuilderGenCtx, FinishFunc, FinishFuncBody, Generics, Member, MemberExpr, MemberOrigin,
-    StartFunc,
+    builder_mod_ident, generic_param_idents, generic_param_to_arg, AssocFreeMethodCtx,
+    AssocMethodCtx, AssocMethodReceiverCtx, BuilderGenCtx, ClosureFunc, FinishFunc, FinishFuncBody,
+    Generics, Member, MemberExpr, MemberOrigin, StartFunc, Validator,
+};
+use crate::builder::params::{
+    BuilderCheck, BuilderParams, ClosureFnParams, FinishFnParams, ItemParams,
 };
-use crate::builder::params::BuilderParams;
 use crate::normalization::NormalizeSelfTy;
 use crate::util::prelude::*;
-use darling::util::SpannedValue;
 use darling::FromMeta;
 use itertools::Itertools;
 use proc_macro2::Span;
 use quote::quote;
+use std::collections::BTreeSet;
 use std::rc::Rc;
 use syn::punctuated::Punctuated;
 use syn::visit::Visit;
 use syn::visit_mut::VisitMut;
 
-#[derive(Debug, FromMeta)]
+#[derive(Debug, Clone, FromMeta)]
 pub(crate) struct FuncInputParams {
-    expose_positional_fn: Option<SpannedValue<ExposePositionalFnParams>>,
+    expose_positional_fn: Option<ExposePositionalFnParams>,
+
+    /// Enables the generation of an additional builder that stores owned
+    /// values for the members of borrowed types like `&str` or `&[T]`.
+    owned: darling::util::Flag,
+
+    /// Overrides the name and visibility of the function that returns the builder
+    start_fn: Option<ItemParams>,
 
     #[darling(flatten)]
     base: BuilderParams,
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
 struct ExposePositionalFnParams {
     name: Option<syn::Ident>,
     vis: Option<syn::Visibility>,
@@ -32,36 +41,17 @@ struct ExposePositionalFnParams {
 
 impl FromMeta for ExposePositionalFnParams {
     fn from_meta(meta: &syn::Meta) -> Result<Self> {
-        match meta {
-            syn::Meta::Path(_) => {
-                return Ok(Self::default());
-            }
-            syn::Meta::NameValue(meta) => {
-                let val = &meta.value;
-                let name = syn::parse2(quote!(#val))?;
-
-                return Ok(Self { name, vis: None });
-            }
-            syn::Meta::List(_) => {}
+        if let syn::Meta::Path(_) = meta {
+            return Ok(Self::default());
         }
 
-        #[derive(Debug, FromMeta)]
-        struct Full {
-            name: Option<syn::Ident>,
-            vis: Option<syn::Visibility>,
-        }
-
-        let full = Full::from_meta(meta)?;
+        let ItemParams { name, vis } = ItemParams::from_meta(meta)?;
 
-        let me = Self {
-            name: full.name,
-            vis: full.vis,
-        };
-
-        Ok(me)
+        Ok(Self { name, vis })
     }
 }
 
+#[derive(Clone)]
 pub(crate) struct FuncInputCtx {
     pub(crate) orig_func: syn::ItemFn,
     pub(crate) norm_func: syn::ItemFn,
@@ -72,13 +62,25 @@ pub(crate) struct FuncInputCtx {
 pub(crate) struct ImplCtx {
     pub(crate) self_ty: Box<syn::Type>,
     pub(crate) generics: syn::Generics,
+
+    /// The trait that defines the methods if the builders are generated for
+    /// the methods of a trait definition
+    pub(crate) trait_ident: Option<syn::Ident>,
+
+    /// Path to the `bon` crate from `#[bon(crate = ...)]`. It's used by the
+    /// builders of the methods that don't override it themselves.
+    pub(crate) bon: Option<syn::Path>,
 }
 
 impl FuncInputCtx {
     fn self_ty_prefix(&self) -> Option<String> {
-        let prefix = self
-            .impl_ctx
-            .as_deref()?
+        let impl_ctx = self.impl_ctx.as_deref()?;
+
+        if let Some(trait_ident) = &impl_ctx.trait_ident {
+            return Some(trait_ident.raw_name());
+        }
+
+        let prefix = impl_ctx
             .self_ty
             .as_path()?
             .path
@@ -103,12 +105,69 @@ impl FuncInputCtx {
 
         NormalizeSelfTy { self_ty }.visit_type_mut(&mut without_self_ty);
 
+        // The `mut` binding of the receiver matters only for the body of the
+        // method. The start function just moves the receiver into the builder.
+        let mut with_self_keyword = receiver.clone();
+        if with_self_keyword.reference.is_none() {
+            with_self_keyword.mutability = None;
+        }
+
         Some(AssocMethodCtx::Receiver(AssocMethodReceiverCtx {
-            with_self_keyword: receiver.clone(),
+            with_self_keyword,
             without_self_keyword: without_self_ty,
         }))
     }
 
+    /// Generic parameters that the members and the receiver may not reference,
+    /// but that are still the generic parameters of the builder:
+    ///
+    /// - the generic parameters of a trait, which are needed in the bound of
+    ///   the `Self` type of the builders of its methods;
+    /// - the lifetimes of the function referenced only in its return type or
+    ///   in its where clause, e.g. the `'async_trait` lifetime of the methods
+    ///   desugared by `#[async_trait]`.
+    fn extra_phantom_types(&self) -> Vec<syn::Type> {
+        let trait_generics = self
+            .impl_ctx
+            .as_deref()
+            .filter(|impl_ctx| impl_ctx.trait_ident.is_some())
+            .into_iter()
+            .flat_map(|impl_ctx| &impl_ctx.generics.params);
+
+        let mut used_lifetimes = CollectLifetimes::default();
+        for arg in &self.norm_func.sig.inputs {
+            used_lifetimes.visit_fn_arg(arg);
+        }
+
+        let unused_lifetimes =
+            self.norm_func
+                .sig
+                .generics
+                .params
+                .iter()
+                .filter(|param| match param {
+                    syn::GenericParam::Lifetime(param) => {
+                        !used_lifetimes.lifetimes.contains(&param.lifetime.ident)
+                    }
+                    _ => false,
+                });
+
+        trait_generics
+            .chain(unused_lifetimes)
+            .filter_map(|param| match param {
+                syn::GenericParam::Lifetime(param) => {
+                    let lifetime = &param.lifetime;
+                    Some(syn::parse_quote!(fn() -> &#lifetime ()))
+                }
+                syn::GenericParam::Type(param) => {
+                    let ident = &param.ident;
+                    Some(syn::parse_quote!(fn() -> ::core::marker::PhantomData<#ident>))
+                }
+                syn::GenericParam::Const(_) => None,
+            })
+            .collect()
+    }
+
     fn generics(&self) -> Generics {
         let impl_ctx = self.impl_ctx.as_ref();
         let norm_func_params = &self.norm_func.sig.generics.params;
@@ -136,8 +195,14 @@ impl FuncInputCtx {
     }
 
     fn builder_ident(&self) -> syn::Ident {
-        if let Some(builder_type) = &self.params.base.builder_type {
-            return builder_type.clone();
+        if let Some(name) = self
+            .params
+            .base
+            .builder_type
+            .as_ref()
+            .and_then(|params| params.name.clone())
+        {
+            return name;
         }
 
         if self.is_method_new() {
@@ -152,7 +217,38 @@ impl FuncInputCtx {
         )
     }
 
-    pub(crate) fn adapted_func(&self) -> Result<syn::ItemFn> {
+    fn start_func_ident(&self) -> syn::Ident {
+        if let Some(name) = self
+            .params
+            .start_fn
+            .as_ref()
+            .and_then(|params| params.name.clone())
+        {
+            return name;
+        }
+
+        // Special case for `new` methods. We rename them to `builder`
+        // since this is the name that is used in the builder pattern
+        if self.is_method_new() {
+            return syn::Ident::new("builder", self.norm_func.sig.ident.span());
+        }
+
+        // The positional function keeps the original name if it's exposed
+        // without an explicit name, so the start function gets a suffix
+        let keeps_positional_fn_name = self
+            .params
+            .expose_positional_fn
+            .as_ref()
+            .is_some_and(|params| params.name.is_none());
+
+        if keeps_positional_fn_name {
+            return quote::format_ident!("{}_builder", self.norm_func.sig.ident.raw_name());
+        }
+
+        self.norm_func.sig.ident.clone()
+    }
+
+    pub(crate) fn adapted_func(&self) -> syn::ItemFn {
         let mut orig = self.orig_func.clone();
 
         let params = self.params.expose_positional_fn.as_ref();
@@ -176,32 +272,14 @@ impl FuncInputCtx {
 
         let orig_ident = orig.sig.ident.clone();
 
-        if let Some(params) = params {
-            let has_no_value = matches!(
-                params.as_ref(),
-                ExposePositionalFnParams {
-                    name: None,
-                    vis: None,
-                }
-            );
-
-            if has_no_value && !self.is_method_new() {
-                bail!(
-                    &params.span(),
-                    "Positional function identifier is required. It must be \
-                    specified with `#[builder(expose_positional_fn = function_name_here)]`"
-                )
-            }
-        }
-
         orig.sig.ident = params
-            .and_then(|params| {
+            .map(|params| {
                 params
                     .name
                     .clone()
-                    // We treat `new` method specially. In this case we already know the best
-                    // default name for the positional function, which is `new` itself.
-                    .or_else(|| self.is_method_new().then_some(orig.sig.ident))
+                    // The positional function keeps its original name by default,
+                    // and the start function is renamed to avoid the conflict.
+                    .unwrap_or(orig.sig.ident)
             })
             // By default we don't want to expose the positional function, so we
             // hide it under a generated name to avoid name conflicts.
@@ -222,7 +300,7 @@ impl FuncInputCtx {
             .map(|self_ty_prefix| format!("{self_ty_prefix}::"))
             .unwrap_or_default();
 
-        let builder_entry_fn_link = format!("{prefix}{orig_ident}",);
+        let builder_entry_fn_link = format!("{prefix}{}", self.start_func_ident().raw_name());
 
         let doc = format!(
             "Positional function equivalent of [`{builder_entry_fn_link}()`].\n\
@@ -243,7 +321,91 @@ impl FuncInputCtx {
         orig.attrs
             .push(syn::parse_quote!(#[allow(clippy::too_many_arguments)]));
 
-        Ok(orig)
+        orig
+    }
+
+    /// The finishing function of an `unsafe` function is `unsafe` as well, so
+    /// the `# Safety` section of the function's docs is carried over to it.
+    fn safety_docs(&self) -> Vec<syn::Attribute> {
+        if self.norm_func.sig.unsafety.is_none() {
+            return vec![];
+        }
+
+        let doc_text = |attr: &syn::Attribute| match attr.as_doc()? {
+            syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(doc),
+                ..
+            }) => Some(doc.value()),
+            _ => None,
+        };
+
+        let mut safety_docs = vec![];
+        let mut in_section = false;
+
+        // The lines of code blocks that start with `# ` are hidden lines of
+        // doc tests, so they must not be confused with headings
+        let mut in_code_block = false;
+
+        for attr in self.norm_func.attrs.iter().filter(|attr| attr.is_doc()) {
+            let doc = doc_text(attr).unwrap_or_default();
+
+            if !in_code_block {
+                let heading = doc.trim().strip_prefix("# ").map(str::trim);
+
+                match heading {
+                    Some("Safety") if !in_section => in_section = true,
+                    Some(_) if in_section => break,
+                    _ => {}
+                }
+            }
+
+            if in_section {
+                safety_docs.push(attr.clone());
+            }
+
+            let fences = doc.lines().filter(|line| {
+                let line = line.trim_start();
+                line.starts_with("```") || line.starts_with("~~~")
+            });
+
+            for _ in fences {
+                in_code_block = !in_code_block;
+            }
+        }
+
+        if !safety_docs.is_empty() {
+            // Separates the section from the summary line of the docs
+            safety_docs.insert(0, syn::parse_quote!(#[doc = ""]));
+        }
+
+        safety_docs
+    }
+
+    /// Attributes of the function that apply to the value that it returns are
+    /// carried over to the finishing function.
+    fn finish_func_attrs(&self) -> Vec<syn::Attribute> {
+        let mut attrs: Vec<_> = self
+            .norm_func
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("must_use"))
+            .cloned()
+            .collect();
+
+        // The finishing function calls the deprecated positional function. The
+        // deprecation is reported on the start function of the builder instead.
+        if self.deprecated_attrs().next().is_some() {
+            attrs.push(syn::parse_quote!(#[allow(deprecated)]));
+        }
+
+        attrs
+    }
+
+    fn deprecated_attrs(&self) -> impl Iterator<Item = &syn::Attribute> {
+        self.norm_func
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("deprecated"))
     }
 
     fn is_method_new(&self) -> bool {
@@ -251,7 +413,59 @@ impl FuncInputCtx {
     }
 
     pub(crate) fn into_builder_gen_ctx(self) -> Result<BuilderGenCtx> {
+        self.into_builder_gen_ctx_impl(false)
+    }
+
+    /// Generates the context for the additional builder enabled with
+    /// `#[builder(owned)]` if it was requested. This builder stores owned
+    /// values for the members of borrowed types and borrows them only when
+    /// the finishing function is called. This way the builder itself doesn't
+    /// borrow anything from its caller and can be stored or sent freely.
+    pub(crate) fn into_owned_builder_gen_ctx(self) -> Result<Option<BuilderGenCtx>> {
+        if !self.params.owned.is_present() {
+            return Ok(None);
+        }
+
+        if let Some(receiver) = self.norm_func.sig.receiver() {
+            if matches!(receiver.ty.as_ref(), syn::Type::Reference(_)) {
+                bail!(
+                    &receiver,
+                    "#[builder(owned)] is not supported for methods with a borrowed \
+                    receiver, because the builder would borrow `self` in this case"
+                );
+            }
+        }
+
+        // It's impossible to tell which members the elided lifetimes in the
+        // return type refer to, so it's unknown which members must stay borrowed
+        let mut elided = FindElidedLifetime::default();
+        elided.visit_return_type(&self.orig_func.sig.output);
+        if let Some(span) = elided.span {
+            bail!(
+                &span,
+                "#[builder(owned)] is not supported for functions with elided or \
+                anonymous lifetimes in the return type, because the output may \
+                borrow from the members that the builder would own; give this \
+                lifetime a name to keep the members that use it borrowed"
+            );
+        }
+
+        self.into_builder_gen_ctx_impl(true).map(Some)
+    }
+
+    fn into_builder_gen_ctx_impl(mut self, owned: bool) -> Result<BuilderGenCtx> {
         let receiver = self.assoc_method_ctx();
+        let extra_phantom_types = self.extra_phantom_types();
+        let doc_cfg_attrs = self
+            .norm_func
+            .attrs
+            .iter()
+            .filter(|attr| attr.is_doc_cfg())
+            .cloned()
+            .collect();
+        let safety_docs = self.safety_docs();
+        let finish_func_attrs = self.finish_func_attrs();
+        let deprecated_attrs: Vec<_> = self.deprecated_attrs().cloned().collect();
 
         if self.impl_ctx.is_none() {
             let explanation = "\
@@ -278,10 +492,36 @@ impl FuncInputCtx {
             }
         }
 
-        let builder_ident = self.builder_ident();
+        let mut builder_ident = self.builder_ident();
+
+        if owned {
+            builder_ident = quote::format_ident!("Owned{}", builder_ident.raw_name());
+        }
+
         let builder_private_impl_ident =
             quote::format_ident!("__{}PrivateImpl", builder_ident.raw_name());
-        let builder_state_trait_ident = quote::format_ident!("__{}State", builder_ident.raw_name());
+        let builder_mod_ident = builder_mod_ident(&builder_ident);
+        let builder_member_enum_ident = self
+            .params
+            .base
+            .member_enum
+            .is_present()
+            .then(|| quote::format_ident!("{}Member", builder_ident.raw_name()));
+
+        let impl_ctx_tokens = self.impl_ctx.as_deref().map(|impl_ctx| {
+            let ImplCtx {
+                self_ty, generics, ..
+            } = impl_ctx;
+            quote!(#self_ty #generics)
+        });
+        let orig_func = &self.orig_func;
+        let (state_var, configure_var) = generic_param_idents(quote!(#orig_func #impl_ctx_tokens));
+        let keep_leading_underscore = self.params.base.keep_leading_underscore.is_present();
+
+        // The members that share a lifetime with the return type must stay
+        // borrowed, because the output may borrow from them
+        let mut output_lifetimes = LifetimesCollector::default();
+        output_lifetimes.visit_return_type(&self.norm_func.sig.output);
 
         let members: Vec<_> = self
             .norm_func
@@ -292,24 +532,106 @@ impl FuncInputCtx {
             .map(Member::from_typed_fn_arg)
             .try_collect()?;
 
-        let generics = self.generics();
+        // The declarations of the flattened structs are passed only to the
+        // free functions, so the members of the methods can't be flattened
+        let members: Vec<_> =
+            Member::flatten(members, std::mem::take(&mut self.params.base.flattened))?
+                .into_iter()
+                .map(|mut member| {
+                    if let Some(impl_ctx) = &self.impl_ctx {
+                        member.normalize_self_ty(&impl_ctx.self_ty);
+                    }
+                    if keep_leading_underscore {
+                        member.keep_leading_underscore();
+                    }
+                    if owned && !output_lifetimes.is_used_by(&member.ty) {
+                        member.into_owned()
+                    } else {
+                        member
+                    }
+                })
+                .collect();
+
+        let mut generics = self.generics();
+        let mut start_func_generics = Generics {
+            params: Vec::from_iter(self.norm_func.sig.generics.params.iter().cloned()),
+            where_clause: self.norm_func.sig.generics.where_clause.clone(),
+        };
+
+        if owned {
+            // Lifetimes that were used only by the borrowed members are no longer
+            // needed. We must remove them, otherwise they'd be reported as unused
+            // generic parameters of the builder struct.
+            let mut used = LifetimesCollector::default();
+
+            for member in &members {
+                used.visit_type(&member.ty);
+            }
+            if let Some(receiver) = &receiver {
+                used.visit_type(receiver.ty_without_self_keyword());
+            }
+            used.visit_return_type(&self.norm_func.sig.output);
+            if let Some(where_clause) = &generics.where_clause {
+                used.visit_where_clause(where_clause);
+            }
+            for param in &generics.params {
+                if !matches!(param, syn::GenericParam::Lifetime(_)) {
+                    used.visit_generic_param(param);
+                }
+            }
+
+            generics.params.retain(|param| used.retains(param));
+            start_func_generics
+                .params
+                .retain(|param| used.retains(param));
+        }
 
         let finish_func_body = FnCallBody {
-            func: self.adapted_func()?,
+            func: self.adapted_func(),
             impl_ctx: self.impl_ctx.clone(),
         };
 
         let is_method_new = self.is_method_new();
 
-        // Special case for `new` methods. We rename them to `builder`
-        // since this is the name that is used in the builder pattern
-        let start_func_ident = if is_method_new {
-            syn::Ident::new("builder", self.norm_func.sig.ident.span())
-        } else {
-            self.norm_func.sig.ident.clone()
-        };
+        let mut start_func_ident = self.start_func_ident();
+        let start_func_vis = self
+            .params
+            .start_fn
+            .as_ref()
+            .and_then(|params| params.vis.clone());
+
+        let mut start_func_attrs: Vec<_> = self
+            .norm_func
+            .attrs
+            .into_iter()
+            .filter(|attr| attr.is_doc())
+            .collect();
+
+        if owned {
+            let prefix = if self.impl_ctx.is_some() {
+                "Self::"
+            } else {
+                ""
+            };
+            let docs = format!(
+                "Same as [`{prefix}{start_func_ident}()`], but the builder stores owned \
+                values for the borrowed members, and borrows them only when finishing.",
+            );
+
+            start_func_attrs = vec![syn::parse_quote!(#[doc = #docs])];
+            start_func_ident = quote::format_ident!("{}_owned", start_func_ident.raw_name());
+        }
+
+        start_func_attrs.extend(deprecated_attrs);
+
+        let FinishFnParams {
+            name: finish_func_ident,
+            vis: finish_func_vis,
+            map: finish_func_map,
+            unwrap: finish_func_unwrap,
+        } = self.params.base.finish_fn.unwrap_or_default();
 
-        let finish_func_ident = self.params.base.finish_fn.unwrap_or_else(|| {
+        let finish_func_ident = finish_func_ident.unwrap_or_else(|| {
             // For `new` methods the `build` finisher is more conventional
             let name = if is_method_new { "build" } else { "call" };
 
@@ -322,40 +644,88 @@ impl FuncInputCtx {
             asyncness: self.norm_func.sig.asyncness,
             body: Box::new(finish_func_body),
             output: self.norm_func.sig.output,
+            safety_docs,
+            attrs: finish_func_attrs,
+            vis: finish_func_vis,
+            map: finish_func_map,
+            unwrap: finish_func_unwrap,
+            infallible_output: None,
         };
 
+        // The companion items are generated only for the main builder
+        let closure_func = self
+            .params
+            .base
+            .closure_fn
+            .filter(|_| !owned)
+            .map(|params| {
+                let ClosureFnParams { name, vis } = params;
+                ClosureFunc {
+                    ident: name.unwrap_or_else(|| {
+                        quote::format_ident!("{}_with", start_func_ident.raw_name())
+                    }),
+                    vis,
+                }
+            });
+
         let start_func = StartFunc {
             ident: start_func_ident,
 
-            // No override for visibility for the start fn is provided here.
-            // It's supposed to be the same as the original function's visibility.
-            vis: None,
+            // If there is no override for visibility, then the start fn
+            // has the same visibility as the original function.
+            vis: start_func_vis,
 
-            attrs: self
-                .norm_func
-                .attrs
-                .into_iter()
-                .filter(|attr| attr.is_doc())
-                .collect(),
+            attrs: start_func_attrs,
+            generics: Some(start_func_generics),
 
-            generics: Some(Generics {
-                params: Vec::from_iter(self.norm_func.sig.generics.params),
-                where_clause: self.norm_func.sig.generics.where_clause,
-            }),
+            is_assoc: self.impl_ctx.is_some(),
         };
 
         let ctx = BuilderGenCtx {
             members,
             builder_ident,
             builder_private_impl_ident,
-            builder_state_trait_ident,
+            builder_mod_ident,
+            state_var,
+            configure_var,
+            builder_member_enum_ident,
 
             assoc_method_ctx: receiver,
             generics,
-            vis: self.norm_func.vis,
+            vis: self.params.base.vis.unwrap_or(self.norm_func.vis),
+            builder_type_vis: self.params.base.builder_type.and_then(|params| params.vis),
 
             start_func,
             finish_func,
+
+            implements: self.params.base.implements.filter(|_| !owned),
+            closure_func,
+            default_from: None,
+            into_builder_funcs: None,
+            apply_to: None,
+            has_builder_impl: None,
+            preset_funcs: None,
+            setter_prefix: self.params.base.setter_prefix,
+            maybe_setters: self.params.base.maybe,
+            validator: self.params.base.validate.map(Validator::new),
+            pre_build: self.params.base.pre_build,
+            on_params: self.params.base.on,
+            derives: self.params.base.derive,
+            finish_fn_clone: self.params.base.finish_fn_clone,
+            dynamic: self.params.base.dynamic,
+            merge: self.params.base.merge,
+            mut_setters: self.params.base.mut_setters,
+            runtime_checks: BuilderCheck::runtime_span(self.params.base.check),
+            dyn_builder: self.params.base.dyn_builder,
+            track_caller: self.params.base.track_caller,
+            doc_cfg_attrs,
+            bon: self
+                .params
+                .base
+                .bon
+                .or_else(|| self.impl_ctx.as_deref()?.bon.clone())
+                .unwrap_or_else(|| syn::parse_quote!(::bon)),
+            extra_phantom_types,
         };
 
         Ok(ctx)
@@ -390,10 +760,10 @@ impl FinishFuncBody for FnCallBody {
             .func
             .sig
             .receiver()
-            .map(|receiver| {
-                let self_token = &receiver.self_token;
-                quote!(#self_token.__private_impl.receiver.)
-            })
+            // The `self` of the finishing function is generated by the macro, so
+            // the user's `self` token can't refer to it under hygiene of the
+            // `macro_rules!` that may invoke this macro
+            .map(|_| quote!(self.__private_impl.receiver.))
             .or_else(|| {
                 let self_ty = &self.impl_ctx.as_deref()?.self_ty;
                 Some(quote!(<#self_ty>::))
@@ -463,6 +833,77 @@ impl Member {
     }
 }
 
+#[derive(Default)]
+struct LifetimesCollector {
+    lifetimes: BTreeSet<syn::Ident>,
+}
+
+impl LifetimesCollector {
+    /// Returns `false` only for the lifetime parameters that weren't collected
+    fn retains(&self, param: &syn::GenericParam) -> bool {
+        match param {
+            syn::GenericParam::Lifetime(param) => self.lifetimes.contains(&param.lifetime.ident),
+            _ => true,
+        }
+    }
+
+    /// Returns `true` if the type references any of the collected lifetimes
+    fn is_used_by(&self, ty: &syn::Type) -> bool {
+        let mut used = Self::default();
+        used.visit_type(ty);
+        !self.lifetimes.is_disjoint(&used.lifetimes)
+    }
+}
+
+impl Visit<'_> for LifetimesCollector {
+    fn visit_lifetime(&mut self, lifetime: &syn::Lifetime) {
+        self.lifetimes.insert(lifetime.ident.clone());
+    }
+}
+
+#[derive(Default)]
+struct CollectLifetimes {
+    lifetimes: BTreeSet<syn::Ident>,
+}
+
+impl Visit<'_> for CollectLifetimes {
+    fn visit_lifetime(&mut self, lifetime: &syn::Lifetime) {
+        self.lifetimes.insert(lifetime.ident.clone());
+    }
+}
+
+#[derive(Default)]
+struct FindElidedLifetime {
+    span: Option<Span>,
+}
+
+impl Visit<'_> for FindElidedLifetime {
+    fn visit_item(&mut self, _: &syn::Item) {
+        // Don't recurse into nested items because lifetimes aren't available there
+    }
+
+    fn visit_type_bare_fn(&mut self, _: &syn::TypeBareFn) {
+        // Anonymous lifetimes in function pointers don't belong to the signature
+    }
+
+    fn visit_parenthesized_generic_arguments(&mut self, _: &syn::ParenthesizedGenericArguments) {
+        // Skip Fn traits for the same reason as function pointers
+    }
+
+    fn visit_lifetime(&mut self, lifetime: &syn::Lifetime) {
+        if lifetime.ident == "_" && self.span.is_none() {
+            self.span = Some(lifetime.span());
+        }
+    }
+
+    fn visit_type_reference(&mut self, reference: &syn::TypeReference) {
+        if reference.lifetime.is_none() && self.span.is_none() {
+            self.span = Some(reference.and_token.span);
+        }
+        syn::visit::visit_type_reference(self, reference);
+    }
+}
+
 #[derive(Default)]
 struct FindSelfReference {
     self_span: Option<Span>,
@@ -1,19 +1,50 @@
 use super::{
-    BuilderGenCtx, FinishFunc, FinishFuncBody, Generics, Member, MemberExpr, MemberOrigin,
-    StartFunc,
+    builder_mod_ident, generic_param_idents, BuilderGenCtx, ClosureFunc, DefaultFrom, FinishFunc,
+    FinishFuncBody, Generics, HasBuilderImpl, IntoBuilderFuncs, Member, MemberExpr, MemberOrigin,
+    PresetFuncs, StartFunc, Validator,
+};
+use crate::builder::params::{
+    parse_syntax, BuilderCheck, BuilderParams, ClosureFnParams, FinishFnParams, PresetParams,
+    StartFnParams,
 };
-use crate::builder::params::{BuilderParams, ItemParams};
 use crate::util::prelude::*;
 use darling::FromMeta;
 use itertools::Itertools;
-use quote::quote;
+use quote::{quote, ToTokens};
 use syn::visit_mut::VisitMut;
 
 #[derive(Debug, FromMeta)]
 pub(crate) struct StructInputParams {
     #[darling(flatten)]
     base: BuilderParams,
-    start_fn: Option<ItemParams>,
+    start_fn: Option<StartFnParams>,
+
+    /// An expression that creates an instance of the struct to take the values
+    /// of unset members from. The special `Default` value means the instance is
+    /// created with the `Default` trait implementation of the struct.
+    default_from: Option<syn::Expr>,
+
+    /// Type of the base struct whose members are inherited by the builder.
+    /// The field of this type is flattened as if it had `#[builder(flatten)]`.
+    #[darling(default, with = parse_syntax)]
+    extends: Option<syn::Type>,
+
+    /// Generates the `into_builder(self)` method on the struct that converts
+    /// the instance into the builder with all members set.
+    into_builder: darling::util::Flag,
+
+    /// Generates the `to_builder(&self)` method on the struct that creates
+    /// the builder with all members set to the clones of the fields' values.
+    to_builder: darling::util::Flag,
+
+    /// Generates the `apply_to(&mut instance)` method on the builder that
+    /// overwrites only the fields of the members that were set.
+    apply_to: darling::util::Flag,
+
+    /// Additional start functions that create the builder with some members
+    /// already set to the given values
+    #[darling(multiple)]
+    preset: Vec<PresetParams>,
 }
 
 pub(crate) struct StructInputCtx {
@@ -51,13 +82,63 @@ impl StructInputCtx {
     }
 
     fn builder_ident(&self) -> syn::Ident {
-        if let Some(builder_type) = &self.params.base.builder_type {
-            return builder_type.clone();
+        if let Some(name) = self
+            .params
+            .base
+            .builder_type
+            .as_ref()
+            .and_then(|params| params.name.clone())
+        {
+            return name;
         }
 
         quote::format_ident!("{}Builder", self.norm_struct.ident.raw_name())
     }
 
+    pub(crate) fn struct_ident(&self) -> &syn::Ident {
+        &self.norm_struct.ident
+    }
+
+    /// The type on which the start function should be defined if it was
+    /// overridden with `#[builder(start_fn(on = ...))]`.
+    pub(crate) fn start_func_on(&self) -> Option<&syn::Type> {
+        self.params.start_fn.as_ref()?.on.as_ref()
+    }
+
+    /// Declares the `macro_rules!` callback that passes the declaration of the
+    /// struct to the builders of the other structs that flatten it with
+    /// `#[builder(flatten)]`. It's imported with `use` next to the struct, so
+    /// it's available by the same path as the struct regardless of the order
+    /// of the items.
+    pub(crate) fn flatten_callback(&self) -> TokenStream2 {
+        let callback_ident = crate::normalization::flatten_callback_ident(&self.norm_struct.ident);
+
+        let decl = syn::ItemStruct {
+            attrs: vec![],
+            vis: syn::Visibility::Inherited,
+            ..self.norm_struct.clone()
+        };
+
+        quote! {
+            #[doc(hidden)]
+            #[allow(unused_macros)]
+            macro_rules! #callback_ident {
+                (
+                    ( $($macro_path:tt)* )
+                    ( $($params:tt)* )
+                    $($item:tt)*
+                ) => {
+                    #[$($macro_path)*(__flatten(#decl), $($params)*)]
+                    $($item)*
+                };
+            }
+
+            #[doc(hidden)]
+            #[allow(unused_imports)]
+            pub(crate) use #callback_ident;
+        }
+    }
+
     pub(crate) fn adapted_struct(&self) -> syn::ItemStruct {
         let mut orig = self.orig_struct.clone();
 
@@ -78,7 +159,14 @@ impl StructInputCtx {
         let builder_private_impl_ident =
             quote::format_ident!("__{}PrivateImpl", builder_ident.raw_name());
 
-        let builder_state_trait_ident = quote::format_ident!("__{}State", builder_ident.raw_name());
+        let builder_mod_ident = builder_mod_ident(&builder_ident);
+        let builder_member_enum_ident = self
+            .params
+            .base
+            .member_enum
+            .is_present()
+            .then(|| quote::format_ident!("{}Member", builder_ident.raw_name()));
+        let (state_var, configure_var) = generic_param_idents(self.orig_struct.to_token_stream());
 
         let fields = match self.norm_struct.fields {
             syn::Fields::Named(fields) => fields,
@@ -90,12 +178,127 @@ impl StructInputCtx {
             }
         };
 
-        let members: Vec<_> = fields
+        let default_from_instance = self.params.default_from.is_some();
+        let keep_leading_underscore = self.params.base.keep_leading_underscore.is_present();
+
+        let into_builder_funcs = IntoBuilderFuncs {
+            struct_ty: self.struct_ty.clone(),
+            into_builder: self.params.into_builder.is_present(),
+            to_builder: self.params.to_builder.is_present(),
+        };
+        let into_builder_funcs = (into_builder_funcs.into_builder || into_builder_funcs.to_builder)
+            .then_some(into_builder_funcs);
+
+        let mut members: Vec<_> = fields
             .named
             .iter()
             .map(Member::from_syn_field)
             .try_collect()?;
 
+        if let Some(extends) = &self.params.extends {
+            Member::extend(&mut members, extends)?;
+        }
+
+        let members = Member::flatten(members, self.params.base.flattened)?;
+
+        let flatten = members
+            .iter()
+            .find_map(|member| member.params.flatten.as_ref());
+
+        if let Some(flatten) = flatten {
+            let attr = if default_from_instance {
+                Some("default_from")
+            } else if self.params.into_builder.is_present() {
+                Some("into_builder")
+            } else if self.params.to_builder.is_present() {
+                Some("to_builder")
+            } else if self.params.apply_to.is_present() {
+                Some("apply_to")
+            } else {
+                None
+            };
+
+            if let Some(attr) = attr {
+                bail!(
+                    &flatten.span(),
+                    "#[builder(flatten)] can't be used together with #[builder({attr})], \
+                    because the fields of the struct don't correspond to the \
+                    members of the builder one to one",
+                );
+            }
+        }
+
+        let members: Vec<_> = members
+            .into_iter()
+            .map(|mut member| {
+                member.normalize_self_ty(&self.struct_ty);
+                member.default_from_instance = default_from_instance && member.is_named();
+                if keep_leading_underscore {
+                    member.keep_leading_underscore();
+                }
+                // All members are set in the builder created from the instance,
+                // so they must be overwritable to be changed before building
+                if into_builder_funcs.is_some() && member.is_named() {
+                    member.params.overwritable = darling::util::Flag::present();
+                }
+                member
+            })
+            .collect();
+
+        if self.params.apply_to.is_present() {
+            let hook = if self.params.base.validate.is_some() {
+                Some("#[builder(validate)]")
+            } else if self.params.base.pre_build.is_some() {
+                Some("#[builder(pre_build)]")
+            } else if members
+                .iter()
+                .any(|member| member.params.validate.is_some())
+            {
+                Some("#[builder(validate)] on a member")
+            } else {
+                None
+            };
+
+            if let Some(hook) = hook {
+                bail!(
+                    &self.params.apply_to.span(),
+                    "#[builder(apply_to)] can't be combined with {hook}, because \
+                    `apply_to` changes only some of the fields of an existing \
+                    instance instead of building a new one, so the hook can't \
+                    see the values of all members"
+                );
+            }
+        }
+
+        let struct_ty = &self.struct_ty;
+
+        let doc_cfg_attrs = self
+            .norm_struct
+            .attrs
+            .iter()
+            .filter(|attr| attr.is_doc_cfg())
+            .cloned()
+            .collect();
+
+        let default_from = self.params.default_from.map(|default_from| {
+            let is_default_trait = matches!(
+                &default_from,
+                syn::Expr::Path(path) if path.qself.is_none() && path.path.is_ident("Default")
+            );
+
+            let expr = if is_default_trait {
+                quote!(<#struct_ty as ::core::default::Default>::default())
+            } else {
+                quote!(#default_from)
+            };
+
+            DefaultFrom {
+                struct_ident: self.norm_struct.ident.clone(),
+                struct_ty: struct_ty.clone(),
+                expr,
+            }
+        });
+
         let generics = Generics {
             params: Vec::from_iter(self.norm_struct.generics.params.iter().cloned()),
             where_clause: self.norm_struct.generics.where_clause.clone(),
@@ -105,27 +308,45 @@ impl StructInputCtx {
             struct_ident: self.norm_struct.ident.clone(),
         };
 
-        let ItemParams {
+        let StartFnParams {
             name: start_func_ident,
             vis: start_func_vis,
+            on: start_func_on,
         } = self.params.start_fn.unwrap_or_default();
 
-        let start_func_ident = start_func_ident
-            .unwrap_or_else(|| syn::Ident::new("builder", self.norm_struct.ident.span()));
+        let start_func_ident = start_func_ident.unwrap_or_else(|| {
+            // If the start function is defined on some other type, then the
+            // `builder` name would be ambiguous there, so we use the name of
+            // the struct instead e.g. `Client::request()` for `Request`.
+            if start_func_on.is_some() {
+                return self.norm_struct.ident.to_snake_case();
+            }
 
-        let finish_func_ident = self
-            .params
-            .base
-            .finish_fn
-            .unwrap_or_else(|| syn::Ident::new("build", start_func_ident.span()));
+            syn::Ident::new("builder", self.norm_struct.ident.span())
+        });
+
+        let FinishFnParams {
+            name: finish_func_ident,
+            vis: finish_func_vis,
+            map: finish_func_map,
+            unwrap: finish_func_unwrap,
+        } = self.params.base.finish_fn.unwrap_or_default();
+
+        let finish_func_ident =
+            finish_func_ident.unwrap_or_else(|| syn::Ident::new("build", start_func_ident.span()));
 
-        let struct_ty = &self.struct_ty;
         let finish_func = FinishFunc {
             ident: finish_func_ident,
             unsafety: None,
             asyncness: None,
             body: Box::new(finish_func_body),
             output: syn::parse_quote!(-> #struct_ty),
+            safety_docs: vec![],
+            attrs: vec![],
+            vis: finish_func_vis,
+            map: finish_func_map,
+            unwrap: finish_func_unwrap,
+            infallible_output: None,
         };
 
         let start_func_docs = format!(
@@ -133,25 +354,92 @@ impl StructInputCtx {
             self.norm_struct.ident
         );
 
+        let closure_func = self.params.base.closure_fn.map(|params| {
+            let ClosureFnParams { name, vis } = params;
+            ClosureFunc {
+                ident: name.unwrap_or_else(|| {
+                    quote::format_ident!("{}_with", start_func_ident.raw_name())
+                }),
+                vis,
+            }
+        });
+
         let start_func = StartFunc {
             ident: start_func_ident,
             vis: start_func_vis,
             attrs: vec![syn::parse_quote!(#[doc = #start_func_docs])],
             generics: None,
+            is_assoc: true,
         };
 
+        let builder_type_vis = self.params.base.builder_type.and_then(|params| params.vis);
+
+        // The builder type is exposed in the trait implementation, so it's
+        // generated only if the builder has the same visibility as the struct
+        let has_builder_impl = (self.params.base.vis.is_none()
+            && builder_type_vis.is_none()
+            && !members.iter().any(Member::is_start_fn))
+        .then(|| HasBuilderImpl {
+            struct_ty: self.struct_ty.clone(),
+            start_func_on: start_func_on
+                .clone()
+                .unwrap_or_else(|| self.struct_ty.clone()),
+        });
+
+        let preset_funcs = (!self.params.preset.is_empty()).then(|| PresetFuncs {
+            struct_ty: self.struct_ty.clone(),
+            start_func_on: start_func_on.unwrap_or_else(|| self.struct_ty.clone()),
+            presets: self.params.preset,
+        });
+
         let ctx = BuilderGenCtx {
             members,
             builder_ident,
             builder_private_impl_ident,
-            builder_state_trait_ident,
+            builder_mod_ident,
+            state_var,
+            configure_var,
+            builder_member_enum_ident,
 
             assoc_method_ctx: None,
             generics,
-            vis: self.norm_struct.vis,
+            vis: self.params.base.vis.unwrap_or(self.norm_struct.vis),
+            builder_type_vis,
 
             start_func,
             finish_func,
+
+            implements: self.params.base.implements,
+            closure_func,
+            default_from,
+            into_builder_funcs,
+            apply_to: self
+                .params
+                .apply_to
+                .is_present()
+                .then(|| self.struct_ty.clone()),
+            has_builder_impl,
+            preset_funcs,
+            setter_prefix: self.params.base.setter_prefix,
+            maybe_setters: self.params.base.maybe,
+            validator: self.params.base.validate.map(Validator::new),
+            pre_build: self.params.base.pre_build,
+            on_params: self.params.base.on,
+            derives: self.params.base.derive,
+            finish_fn_clone: self.params.base.finish_fn_clone,
+            dynamic: self.params.base.dynamic,
+            merge: self.params.base.merge,
+            mut_setters: self.params.base.mut_setters,
+            runtime_checks: BuilderCheck::runtime_span(self.params.base.check),
+            dyn_builder: self.params.base.dyn_builder,
+            track_caller: self.params.base.track_caller,
+            doc_cfg_attrs,
+            bon: self
+                .params
+                .base
+                .bon
+                .unwrap_or_else(|| syn::parse_quote!(::bon)),
+            extra_phantom_types: vec![],
         };
 
         Ok(ctx)
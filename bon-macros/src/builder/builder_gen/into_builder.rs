@@ -0,0 +1,93 @@
+use super::BuilderGenCtx;
+use crate::util::prelude::*;
+use quote::quote;
+
+impl BuilderGenCtx {
+    /// Generates the methods of the struct that create the builder with all
+    /// members set from the values of the struct's fields.
+    pub(crate) fn instance_into_builder_impl(&self) -> Option<TokenStream2> {
+        let funcs = self.into_builder_funcs.as_ref()?;
+
+        let vis = self.builder_type_vis();
+        let struct_ty = &funcs.struct_ty;
+        let builder_ident = &self.builder_ident;
+        let builder_private_impl_ident = &self.builder_private_impl_ident;
+        let generics_decl = &self.generics.params;
+        let generic_args = self.generic_args().collect::<Vec<_>>();
+        let where_clause = &self.generics.where_clause;
+
+        // The state where all members that have setters are set
+        let full_state = self
+            .named_members()
+            .fold(self.empty_state(), |state, member| {
+                let set_state = self.set_state(member);
+                quote!(#set_state<#state>)
+            });
+
+        let return_type = quote! {
+            #builder_ident<#(#generic_args,)* #full_state>
+        };
+
+        let builder_literal = |value: &dyn Fn(&syn::Ident) -> TokenStream2| {
+            let members_init = self.stored_members().map(|member| {
+                let ident = &member.ident;
+                let value = value(ident);
+                let value = if member.is_stored_as_is() {
+                    value
+                } else {
+                    quote!(::core::option::Option::Some(#value))
+                };
+                quote!(#ident: #value)
+            });
+
+            self.builder_expr(quote! {
+                #builder_private_impl_ident {
+                    _phantom: ::core::marker::PhantomData,
+                    #( #members_init, )*
+                }
+            })
+        };
+
+        let into_builder = funcs.into_builder.then(|| {
+            let builder = builder_literal(&|ident| quote!(self.#ident));
+            quote! {
+                /// Converts the instance into the builder with all members set to
+                /// the values of its fields. The members can be changed with their
+                /// setters before building the instance again.
+                #vis fn into_builder(self) -> #return_type {
+                    #builder
+                }
+            }
+        });
+
+        let to_builder = funcs.to_builder.then(|| {
+            let clone = quote!(::core::clone::Clone);
+            let builder = builder_literal(&|ident| quote!(#clone::clone(&self.#ident)));
+            let clone_predicates = self.stored_members().map(|member| {
+                let ty = &member.ty;
+                quote!(#ty: #clone)
+            });
+
+            quote! {
+                /// Creates the builder with all members set to the clones of the
+                /// values of the instance's fields. The members can be changed with
+                /// their setters before building a new instance.
+                #vis fn to_builder(&self) -> #return_type
+                where
+                    #( #clone_predicates, )*
+                {
+                    #builder
+                }
+            }
+        });
+
+        Some(quote! {
+            impl<#(#generics_decl,)*> #struct_ty
+            #where_clause
+            {
+                #into_builder
+                #to_builder
+            }
+        })
+    }
+}
@@ -1,11 +1,16 @@
+use crate::builder::params::{FlattenParams, MaybeSetters, StrictBool};
+use crate::normalization::NormalizeSelfTy;
 use crate::util::prelude::*;
 use darling::util::SpannedValue;
-use darling::{FromAttributes, FromMeta};
+use darling::FromMeta;
+use itertools::Itertools;
 use quote::quote;
 use std::fmt;
+use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
+use syn::visit_mut::VisitMut;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(crate) enum MemberOrigin {
     FnArg,
     StructField,
@@ -20,7 +25,7 @@ impl fmt::Display for MemberOrigin {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(crate) struct Member {
     /// Specifies what syntax the member comes from.
     pub(crate) origin: MemberOrigin,
@@ -44,10 +49,34 @@ pub(crate) struct Member {
 
     /// Parameters configured by the user explicitly via attributes
     pub(crate) params: MemberParams,
+
+    /// If `true`, then the builder stores an owned value for this member,
+    /// and it must be borrowed when passed to the finishing function.
+    pub(crate) borrow_on_finish: bool,
+
+    /// If `true`, then the value for this member is taken from the instance
+    /// configured via `#[builder(default_from = ...)]` if it wasn't set.
+    pub(crate) default_from_instance: bool,
+
+    /// If `true`, then the leading underscore isn't stripped from the public
+    /// name of the member. Configured via `#[builder(keep_leading_underscore)]`.
+    pub(crate) keep_leading_underscore: bool,
+
+    /// Set for the members lifted from the struct of a `#[builder(flatten)]`
+    /// member. Their values are moved into that struct in the finishing function.
+    pub(crate) flattened_from: Option<FlattenedFrom>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct FlattenedFrom {
+    /// Name of the `#[builder(flatten)]` member
+    pub(crate) member: syn::Ident,
+
+    /// Name of the field in the struct of the `#[builder(flatten)]` member
+    pub(crate) field: syn::Ident,
 }
 
-#[derive(Debug, darling::FromAttributes)]
-#[darling(attributes(builder))]
+#[derive(Debug, Clone, darling::FromMeta)]
 pub(crate) struct MemberParams {
     /// Overrides the decision to use `Into` for the setter method.
     pub(crate) into: Option<SpannedValue<StrictBool>>,
@@ -55,35 +84,124 @@ pub(crate) struct MemberParams {
     #[darling(with = "parse_optional_expression", map = "Some")]
     pub(crate) default: Option<SpannedValue<Option<syn::Expr>>>,
 
+    /// Name of the environment variable that the value of the member is read
+    /// from if it wasn't set. The value is parsed with `FromStr`.
+    pub(crate) env: Option<SpannedValue<String>>,
+
     /// Rename the name exposed in the builder API.
     pub(crate) name: Option<syn::Ident>,
-}
 
-/// This primitive represents the syntax that accepts only two states:
-/// a word e.g. `#[attr(field)]` represents true, and an expression with
-/// `false` e.g. `#[attr(field = false)]` represents false. No other syntax
-/// is accepted. That's why it's called a "strict" bool.
-#[derive(Debug)]
-pub(crate) struct StrictBool {
-    pub(crate) value: bool,
-}
+    /// Generates additional setters under a different name, e.g. the old name
+    /// of a renamed member. They set the same member as the regular setters.
+    pub(crate) alias: Option<syn::Ident>,
 
-impl FromMeta for StrictBool {
-    fn from_word() -> Result<Self> {
-        Ok(Self { value: true })
-    }
+    /// Marks the setters generated for the `alias` as `#[deprecated]` with
+    /// the given note.
+    pub(crate) deprecated: Option<SpannedValue<String>>,
 
-    fn from_bool(value: bool) -> Result<Self> {
-        if !value {
-            return Ok(Self { value: false });
-        }
+    /// Overrides the configuration of the setter that accepts an `Option` for
+    /// an optional member. It can be disabled with `false` or renamed by
+    /// specifying a different prefix instead of `maybe_`.
+    pub(crate) maybe: Option<SpannedValue<MaybeSetters>>,
 
-        // Error span is set by default trait impl in the caller
-        Err(Error::custom(format_args!(
-            "No need to write `= true`. Just mentioning the attribute is enough \
-            to set it to `true`, so remove the `= true` part.",
-        )))
-    }
+    /// Generates a setter without parameters for a `bool` member that sets it
+    /// to `true`. The setter that accepts the `bool` value explicitly is named
+    /// `with_{member}`. The member defaults to `false` if it wasn't set.
+    pub(crate) flag: darling::util::Flag,
+
+    /// Makes the setters available even if the member was already set. Calling
+    /// them again replaces the previous value.
+    pub(crate) overwritable: darling::util::Flag,
+
+    /// Names of the members that must be set before the setters of this
+    /// member become available in the type state.
+    #[darling(default, with = "parse_ident_list", map = "Some")]
+    pub(crate) requires: Option<SpannedValue<Vec<syn::Ident>>>,
+
+    /// Names of the members that must not be set for the setters of this
+    /// member to be available. Setting this member also makes the setters
+    /// of the listed members unavailable.
+    #[darling(default, with = "parse_ident_list", map = "Some")]
+    pub(crate) conflicts_with: Option<SpannedValue<Vec<syn::Ident>>>,
+
+    /// Name of the group of optional members, at least one of which must be
+    /// set before the builder can be finished.
+    pub(crate) group: Option<syn::Ident>,
+
+    /// Name of the section of optional members that are configured together
+    /// via a nested builder passed to the closure of the section's setter.
+    pub(crate) section: Option<syn::Ident>,
+
+    /// Makes the setter accept a closure that configures the builder of the
+    /// member's type, which must implement `bon::nested::HasBuilder`. The value
+    /// of the member is built from the builder returned by the closure.
+    pub(crate) nested: darling::util::Flag,
+
+    /// Lifts the members of the struct of the member's type into the builder.
+    /// The struct is created from their values in the finishing function.
+    pub(crate) flatten: Option<SpannedValue<FlattenParams>>,
+
+    /// Function that checks the value of the member in the finishing function.
+    /// The errors of all members are collected into `bon::ValidationErrors`.
+    pub(crate) validate: Option<syn::Expr>,
+
+    /// Keeps a member of type `Option<T>` required in the type state. Its
+    /// setter accepts the `Option<T>` value as is.
+    pub(crate) required: darling::util::Flag,
+
+    /// Makes the setter accept `impl AsRef<T>` and convert the value into
+    /// the member's type with `ToOwned`. The target type `T` is inferred from
+    /// the member's type unless it's specified explicitly.
+    #[darling(with = "parse_optional_type", map = "Some")]
+    pub(crate) as_ref: Option<SpannedValue<Option<syn::Type>>>,
+
+    /// Generates an additional fallible setter that accepts `impl TryInto`
+    /// and returns the conversion error if it fails.
+    pub(crate) try_into: darling::util::Flag,
+
+    /// Closure that accepts the parameters of the setter and
+    /// returns the value that is stored in the builder.
+    #[darling(default, with = crate::builder::params::parse_syntax)]
+    pub(crate) with: Option<syn::ExprClosure>,
+
+    /// Makes the member a positional parameter of the start function
+    /// instead of generating setters for it.
+    pub(crate) start_fn: darling::util::Flag,
+
+    /// Makes the member a positional parameter of the finishing function
+    /// instead of generating setters for it.
+    pub(crate) finish_fn: darling::util::Flag,
+
+    /// Excludes the member from the builder entirely. Its value is initialized
+    /// with the given expression or with `Default::default()` if it's omitted.
+    #[darling(with = "parse_optional_expression", map = "Some")]
+    pub(crate) skip: Option<SpannedValue<Option<syn::Expr>>>,
+
+    /// Stores the member in a private field of the builder without generating
+    /// setters for it. The field is initialized with the given expression or with
+    /// `Default::default()` if it's omitted. Custom methods on the builder can
+    /// read and modify the field, and its final value is passed to the finishing
+    /// function.
+    #[darling(with = "parse_optional_expression", map = "Some")]
+    pub(crate) field: Option<SpannedValue<Option<syn::Expr>>>,
+
+    /// Generates an additional setter for a `Vec` member that appends a single
+    /// item to it. The setter is named `push_{member}` by default, or it uses
+    /// the given name.
+    #[darling(with = "parse_optional_ident", map = "Some")]
+    pub(crate) push: Option<SpannedValue<Option<syn::Ident>>>,
+
+    /// Generates an additional setter for a map member that inserts a single
+    /// entry into it. The setter is named `insert_{member}` by default, or it
+    /// uses the given name.
+    #[darling(with = "parse_optional_ident", map = "Some")]
+    pub(crate) insert: Option<SpannedValue<Option<syn::Ident>>>,
+
+    /// Generates an additional setter for a collection member that extends
+    /// it with the contents of an iterator. The setter is named `extend_{member}`
+    /// by default, or it uses the given name.
+    #[darling(with = "parse_optional_ident", map = "Some")]
+    pub(crate) extend: Option<SpannedValue<Option<syn::Ident>>>,
 }
 
 fn parse_optional_expression(meta: &syn::Meta) -> Result<SpannedValue<Option<syn::Expr>>> {
@@ -94,6 +212,43 @@ fn parse_optional_expression(meta: &syn::Meta) -> Result<SpannedValue<Option<syn
     }
 }
 
+fn parse_optional_ident(meta: &syn::Meta) -> Result<SpannedValue<Option<syn::Ident>>> {
+    let expr = parse_optional_expression(meta)?;
+    let span = expr.span();
+    let ident = expr
+        .as_ref()
+        .as_ref()
+        .map(|expr| syn::parse2(quote!(#expr)))
+        .transpose()?;
+
+    Ok(SpannedValue::new(ident, span))
+}
+
+/// Parses either a single name `attr = name` or a list of names `attr(a, b)`.
+/// The names may also be specified as string literals.
+fn parse_ident_list(meta: &syn::Meta) -> Result<SpannedValue<Vec<syn::Ident>>> {
+    let idents = match meta {
+        syn::Meta::Path(_) => return Err(Error::unsupported_format("word").with_span(meta)),
+        syn::Meta::NameValue(nv) => vec![syn::Ident::from_expr(&nv.value)?],
+        syn::Meta::List(list) => list
+            .parse_args_with(Punctuated::<syn::Ident, syn::Token![,]>::parse_terminated)?
+            .into_iter()
+            .collect(),
+    };
+
+    Ok(SpannedValue::new(idents, meta.span()))
+}
+
+fn parse_optional_type(meta: &syn::Meta) -> Result<SpannedValue<Option<syn::Type>>> {
+    if let syn::Meta::Path(_) = meta {
+        return Ok(SpannedValue::new(None, meta.span()));
+    }
+
+    let ty = crate::builder::params::parse_syntax(meta)?;
+
+    Ok(SpannedValue::new(ty, meta.span()))
+}
+
 impl Member {
     pub(crate) fn new(
         origin: MemberOrigin,
@@ -103,7 +258,14 @@ impl Member {
     ) -> Result<Self> {
         let docs = attrs.iter().filter(|attr| attr.is_doc()).cloned().collect();
 
-        let params = MemberParams::from_attributes(attrs)?;
+        let params: Vec<_> = attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("builder"))
+            .map(|attr| attr.parse_nested_metas())
+            .flatten_ok()
+            .try_collect()?;
+
+        let params = MemberParams::from_list(&params)?;
 
         let ident = ident.or_else(|| params.name.clone()).ok_or_else(|| {
             err!(
@@ -114,23 +276,72 @@ impl Member {
             )
         })?;
 
-        let me = Self {
+        let mut me = Self {
             origin,
-            state_assoc_type_ident: ident.to_pascal_case(),
+            state_assoc_type_ident: ident.clone(),
             ident,
             ty,
             params,
             docs,
+            borrow_on_finish: false,
+            default_from_instance: false,
+            keep_leading_underscore: false,
+            flattened_from: None,
         };
 
+        // The state is named after the public name of the member, so that the type
+        // of a partially built builder doesn't change if the original member is renamed
+        me.state_assoc_type_ident = me.enum_variant_ident();
+
         me.validate()?;
 
         Ok(me)
     }
 
-    fn validate(&self) -> Result {
+    pub(super) fn validate(&self) -> Result {
         super::reject_self_references_in_docs(&self.docs)?;
 
+        if self.params.flag.is_present() {
+            self.validate_flag()?;
+        }
+
+        if self.params.required.is_present() {
+            if !self.ty.is_option() {
+                bail!(
+                    &self.params.required.span(),
+                    "This attribute is redundant and can be removed. Members that \
+                    aren't of type `Option<T>` are already required.",
+                );
+            }
+
+            if let Some(default) = &self.params.default {
+                bail!(
+                    &default.span(),
+                    "#[builder(default)] can't be used together with #[builder(required)], \
+                    because required members must always be set explicitly",
+                );
+            }
+
+            if let Some(env) = &self.params.env {
+                bail!(
+                    &env.span(),
+                    "#[builder(env)] can't be used together with #[builder(required)], \
+                    because required members must always be set explicitly",
+                );
+            }
+        }
+
+        if let Some(env) = &self.params.env {
+            if !self.ty.is_option() && self.params.default.is_none() {
+                bail!(
+                    &env.span(),
+                    "#[builder(env)] requires the member to be of type `Option<T>` \
+                    or to have a #[builder(default)], which is used if the \
+                    environment variable isn't set",
+                );
+            }
+        }
+
         if let Some(default) = &self.params.default {
             if self.ty.is_option() {
                 bail!(
@@ -141,36 +352,673 @@ impl Member {
             }
         }
 
+        if let Some(deprecated) = &self.params.deprecated {
+            if self.params.alias.is_none() {
+                bail!(
+                    &deprecated.span(),
+                    "#[builder(deprecated)] applies to the setters generated for \
+                    the alias, so it requires #[builder(alias = ...)]",
+                );
+            }
+        }
+
+        if let Some(alias) = &self.params.alias {
+            if *alias == self.public_ident() {
+                bail!(
+                    alias,
+                    "the alias is the same as the name of the member, so the \
+                    setters would be generated twice under the same name",
+                );
+            }
+        }
+
+        if self.params.skip.is_some() {
+            self.validate_exclusive("skip")?;
+        }
+
+        if self.params.field.is_some() {
+            self.validate_exclusive("field")?;
+        }
+
+        if self.params.flatten.is_some() {
+            self.validate_exclusive("flatten")?;
+        }
+
+        if self.params.start_fn.is_present() && self.params.finish_fn.is_present() {
+            bail!(
+                &self.params.finish_fn.span(),
+                "#[builder(start_fn)] and #[builder(finish_fn)] are mutually exclusive",
+            );
+        }
+
+        let positional = [
+            ("start_fn", &self.params.start_fn),
+            ("finish_fn", &self.params.finish_fn),
+        ];
+
+        for (positional_attr, flag) in positional {
+            if !flag.is_present() {
+                continue;
+            }
+
+            let conflicting = [
+                (
+                    "default",
+                    self.params.default.as_ref().map(|default| default.span()),
+                ),
+                ("env", self.params.env.as_ref().map(|env| env.span())),
+                ("with", self.params.with.as_ref().map(|with| with.span())),
+                (
+                    "as_ref",
+                    self.params.as_ref.as_ref().map(|as_ref| as_ref.span()),
+                ),
+                (
+                    "maybe",
+                    self.params.maybe.as_ref().map(|maybe| maybe.span()),
+                ),
+                (
+                    "flag",
+                    self.params
+                        .flag
+                        .is_present()
+                        .then(|| self.params.flag.span()),
+                ),
+                (
+                    "overwritable",
+                    self.params
+                        .overwritable
+                        .is_present()
+                        .then(|| self.params.overwritable.span()),
+                ),
+                (
+                    "required",
+                    self.params
+                        .required
+                        .is_present()
+                        .then(|| self.params.required.span()),
+                ),
+                (
+                    "requires",
+                    self.params
+                        .requires
+                        .as_ref()
+                        .map(|requires| requires.span()),
+                ),
+                (
+                    "conflicts_with",
+                    self.params
+                        .conflicts_with
+                        .as_ref()
+                        .map(|conflicts_with| conflicts_with.span()),
+                ),
+                (
+                    "group",
+                    self.params.group.as_ref().map(|group| group.span()),
+                ),
+                (
+                    "section",
+                    self.params.section.as_ref().map(|section| section.span()),
+                ),
+                (
+                    "nested",
+                    self.params
+                        .nested
+                        .is_present()
+                        .then(|| self.params.nested.span()),
+                ),
+                (
+                    "try_into",
+                    self.params
+                        .try_into
+                        .is_present()
+                        .then(|| self.params.try_into.span()),
+                ),
+                ("push", self.params.push.as_ref().map(|push| push.span())),
+                (
+                    "insert",
+                    self.params.insert.as_ref().map(|insert| insert.span()),
+                ),
+                (
+                    "extend",
+                    self.params.extend.as_ref().map(|extend| extend.span()),
+                ),
+                (
+                    "alias",
+                    self.params.alias.as_ref().map(|alias| alias.span()),
+                ),
+            ];
+
+            for (attr, span) in conflicting {
+                if let Some(span) = span {
+                    bail!(
+                        &span,
+                        "#[builder({attr})] can't be used together with #[builder({positional_attr})], \
+                        because there are no setters for the positional members",
+                    );
+                }
+            }
+        }
+
+        if let Some(as_ref) = &self.params.as_ref {
+            if let Some(into) = &self.params.into {
+                bail!(
+                    &into.span(),
+                    "#[builder(into)] can't be used together with #[builder(as_ref)], \
+                    because the latter already defines the conversion of the value",
+                );
+            }
+
+            if self.params.with.is_some() {
+                bail!(
+                    &as_ref.span(),
+                    "#[builder(as_ref)] can't be used together with #[builder(with)], \
+                    because the setter's parameters are defined by the closure",
+                );
+            }
+        }
+
+        if let Some(with) = &self.params.with {
+            if let Some(into) = &self.params.into {
+                bail!(
+                    &into.span(),
+                    "#[builder(into)] can't be used together with #[builder(with)], \
+                    because the setter's parameters are defined by the closure",
+                );
+            }
+
+            if self.params.try_into.is_present() {
+                bail!(
+                    &self.params.try_into.span(),
+                    "#[builder(try_into)] can't be used together with #[builder(with)], \
+                    because the setter's parameters are defined by the closure",
+                );
+            }
+
+            for input in &with.inputs {
+                if !matches!(input, syn::Pat::Type(_)) {
+                    bail!(
+                        input,
+                        "the closure parameters must have explicit type annotations, \
+                        because they define the parameters of the setter",
+                    );
+                }
+            }
+        }
+
+        if let Some(section) = &self.params.section {
+            self.validate_section(section)?;
+        }
+
+        if self.params.nested.is_present() {
+            self.validate_nested()?;
+        }
+
         Ok(())
     }
 
-    pub(crate) fn as_optional(&self) -> Option<&syn::Type> {
-        self.ty
-            .option_type_param()
-            .or_else(|| (self.params.default.is_some()).then_some(&self.ty))
+    fn validate_flag(&self) -> Result {
+        let flag = &self.params.flag;
+
+        let is_bool = self
+            .ty
+            .as_path()
+            .is_some_and(|path| path.qself.is_none() && path.path.is_ident("bool"));
+
+        if !is_bool {
+            bail!(
+                &flag.span(),
+                "#[builder(flag)] is supported only for members of type `bool`",
+            );
+        }
+
+        let conflicting = [
+            ("into", self.params.into.as_ref().map(|into| into.span())),
+            (
+                "default",
+                self.params.default.as_ref().map(|default| default.span()),
+            ),
+            ("env", self.params.env.as_ref().map(|env| env.span())),
+            (
+                "try_into",
+                self.params
+                    .try_into
+                    .is_present()
+                    .then(|| self.params.try_into.span()),
+            ),
+            ("with", self.params.with.as_ref().map(|with| with.span())),
+            (
+                "as_ref",
+                self.params.as_ref.as_ref().map(|as_ref| as_ref.span()),
+            ),
+            (
+                "required",
+                self.params
+                    .required
+                    .is_present()
+                    .then(|| self.params.required.span()),
+            ),
+            (
+                "maybe",
+                self.params.maybe.as_ref().map(|maybe| maybe.span()),
+            ),
+        ];
+
+        for (attr, span) in conflicting {
+            if let Some(span) = span {
+                bail!(
+                    &span,
+                    "#[builder({attr})] can't be used together with #[builder(flag)], \
+                    because the setters of a flag member are fixed and it always \
+                    defaults to `false`",
+                );
+            }
+        }
+
+        Ok(())
     }
 
-    pub(crate) fn unset_state_type(&self) -> TokenStream2 {
-        let ty = &self.ty;
+    fn validate_section(&self, section: &syn::Ident) -> Result {
+        if self.is_required() {
+            bail!(
+                section,
+                "the members of a section must be optional, because the nested \
+                builder of the section doesn't track which members were set",
+            );
+        }
+
+        let conflicting = [
+            (
+                "requires",
+                self.params
+                    .requires
+                    .as_ref()
+                    .map(|requires| requires.span()),
+            ),
+            (
+                "conflicts_with",
+                self.params
+                    .conflicts_with
+                    .as_ref()
+                    .map(|conflicts_with| conflicts_with.span()),
+            ),
+            (
+                "group",
+                self.params.group.as_ref().map(|group| group.span()),
+            ),
+            (
+                "overwritable",
+                self.params
+                    .overwritable
+                    .is_present()
+                    .then(|| self.params.overwritable.span()),
+            ),
+        ];
 
-        if let Some(inner_type) = self.as_optional() {
-            quote!(::bon::private::Optional<#inner_type>)
+        for (attr, span) in conflicting {
+            if let Some(span) = span {
+                bail!(
+                    &span,
+                    "#[builder({attr})] can't be used together with #[builder(section)], \
+                    because all members of a section are set at once by the setter \
+                    of the section",
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    fn validate_nested(&self) -> Result {
+        let conflicting = [
+            ("into", self.params.into.as_ref().map(|into| into.span())),
+            (
+                "as_ref",
+                self.params.as_ref.as_ref().map(|as_ref| as_ref.span()),
+            ),
+            ("with", self.params.with.as_ref().map(|with| with.span())),
+            (
+                "try_into",
+                self.params
+                    .try_into
+                    .is_present()
+                    .then(|| self.params.try_into.span()),
+            ),
+            (
+                "flag",
+                self.params
+                    .flag
+                    .is_present()
+                    .then(|| self.params.flag.span()),
+            ),
+        ];
+
+        for (attr, span) in conflicting {
+            if let Some(span) = span {
+                bail!(
+                    &span,
+                    "#[builder({attr})] can't be used together with #[builder(nested)], \
+                    because the value of the member is built with the builder of its \
+                    type that is configured in the setter's closure",
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Validates that the attribute, which excludes the member from the setters
+    /// and positional parameters, isn't combined with any other attributes.
+    fn validate_exclusive(&self, exclusive_attr: &str) -> Result {
+        let MemberParams {
+            into,
+            default,
+            env,
+            name: _,
+            alias,
+            deprecated,
+            as_ref,
+            maybe,
+            flag,
+            overwritable,
+            required,
+            requires,
+            conflicts_with,
+            group,
+            section,
+            nested,
+            flatten,
+            validate,
+            try_into,
+            with,
+            start_fn,
+            finish_fn,
+            skip,
+            field,
+            push,
+            insert,
+            extend,
+        } = &self.params;
+
+        let attrs = [
+            ("into", into.as_ref().map(|into| into.span())),
+            ("default", default.as_ref().map(|default| default.span())),
+            ("env", env.as_ref().map(|env| env.span())),
+            ("maybe", maybe.as_ref().map(|maybe| maybe.span())),
+            ("flag", flag.is_present().then(|| flag.span())),
+            (
+                "overwritable",
+                overwritable.is_present().then(|| overwritable.span()),
+            ),
+            ("required", required.is_present().then(|| required.span())),
+            (
+                "requires",
+                requires.as_ref().map(|requires| requires.span()),
+            ),
+            (
+                "conflicts_with",
+                conflicts_with
+                    .as_ref()
+                    .map(|conflicts_with| conflicts_with.span()),
+            ),
+            ("group", group.as_ref().map(|group| group.span())),
+            ("section", section.as_ref().map(|section| section.span())),
+            ("nested", nested.is_present().then(|| nested.span())),
+            ("flatten", flatten.as_ref().map(|flatten| flatten.span())),
+            (
+                "validate",
+                validate.as_ref().map(|validate| validate.span()),
+            ),
+            ("try_into", try_into.is_present().then(|| try_into.span())),
+            ("as_ref", as_ref.as_ref().map(|as_ref| as_ref.span())),
+            ("with", with.as_ref().map(|with| with.span())),
+            ("start_fn", start_fn.is_present().then(|| start_fn.span())),
+            (
+                "finish_fn",
+                finish_fn.is_present().then(|| finish_fn.span()),
+            ),
+            ("skip", skip.as_ref().map(|skip| skip.span())),
+            ("field", field.as_ref().map(|field| field.span())),
+            ("push", push.as_ref().map(|push| push.span())),
+            ("insert", insert.as_ref().map(|insert| insert.span())),
+            ("extend", extend.as_ref().map(|extend| extend.span())),
+            ("alias", alias.as_ref().map(|alias| alias.span())),
+            (
+                "deprecated",
+                deprecated.as_ref().map(|deprecated| deprecated.span()),
+            ),
+        ];
+
+        for (attr, span) in attrs {
+            if attr == exclusive_attr {
+                continue;
+            }
+
+            if let Some(span) = span {
+                bail!(
+                    &span,
+                    "#[builder({attr})] can't be used together with #[builder({exclusive_attr})], \
+                    because the latter excludes the member from the setters and positional parameters",
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Replaces references to `Self` in the default, skip and field value expressions with the
+    /// given type. The default value is evaluated inside of the builder's impl
+    /// block, where `Self` would otherwise refer to the builder itself.
+    pub(crate) fn normalize_self_ty(&mut self, self_ty: &syn::Type) {
+        let exprs = [
+            &mut self.params.default,
+            &mut self.params.skip,
+            &mut self.params.field,
+        ]
+        .into_iter()
+        .filter_map(|expr| expr.as_deref_mut()?.as_mut());
+
+        for expr in exprs {
+            NormalizeSelfTy { self_ty }.visit_expr_mut(expr);
+        }
+    }
+
+    /// Replaces the borrowed `&str` and `&[T]` types (optionally wrapped in an
+    /// `Option`) with their owned counterparts `String` and `Vec<T>`. The `&mut`
+    /// references are left as is, because the function may mutate the value
+    /// that the caller owns.
+    pub(crate) fn into_owned(mut self) -> Self {
+        let ty = match self.ty.option_type_param() {
+            Some(inner) if self.ty.is_option() => inner,
+            _ => &self.ty,
+        };
+
+        let syn::Type::Reference(reference) = ty.peel() else {
+            return self;
+        };
+
+        if reference.mutability.is_some() {
+            return self;
+        }
+
+        let owned_ty: syn::Type = match reference.elem.peel() {
+            syn::Type::Path(path) if path.qself.is_none() && path.path.is_ident("str") => {
+                syn::parse_quote!(::std::string::String)
+            }
+            syn::Type::Slice(slice) => {
+                let elem = &slice.elem;
+                syn::parse_quote!(::std::vec::Vec<#elem>)
+            }
+            _ => return self,
+        };
+
+        self.ty = if self.ty.is_option() {
+            syn::parse_quote!(::core::option::Option<#owned_ty>)
         } else {
-            quote!(::bon::private::Required<#ty>)
+            Box::new(owned_ty)
+        };
+
+        self.borrow_on_finish = true;
+        self
+    }
+
+    /// Name of the member exposed in the builder API. It's the original name
+    /// with the leading underscore stripped, unless it was overridden with
+    /// `#[builder(name = ...)]`.
+    pub(crate) fn public_ident(&self) -> syn::Ident {
+        if let Some(name) = &self.params.name {
+            return name.clone();
+        }
+
+        if self.keep_leading_underscore {
+            return self.ident.clone();
+        }
+
+        let member_ident = &self.ident.to_string();
+        let norm_member_ident = member_ident
+            // Remove the leading underscore from the member name since it's used
+            // to denote unused symbols in Rust. That doesn't mean the builder
+            // API should expose that knowledge to the caller.
+            .strip_prefix('_')
+            .unwrap_or(member_ident);
+
+        // Keywords like `self` can't be raw identifiers, so the leading underscore
+        // is kept for them. The other keywords e.g. `_type` become raw identifiers.
+        if ["self", "Self", "super", "crate"].contains(&norm_member_ident) {
+            return self.ident.clone();
+        }
+
+        // Preserve the original identifier span to make IDE go to definition correctly
+        // and make error messages point to the correct place.
+        syn::Ident::new_maybe_raw(norm_member_ident, self.ident.span())
+    }
+
+    /// Returns `true` if the member is a positional parameter of the start
+    /// function. Such members are not part of the builder's type state.
+    pub(crate) fn is_start_fn(&self) -> bool {
+        self.params.start_fn.is_present()
+    }
+
+    /// Returns `true` if the member is excluded from the builder and initialized
+    /// with an expression in the finishing function.
+    pub(crate) fn is_skipped(&self) -> bool {
+        self.params.skip.is_some()
+    }
+
+    /// Returns `true` if the member is stored in a private field of the builder
+    /// that is initialized by the start function and has no setters.
+    pub(crate) fn is_field(&self) -> bool {
+        self.params.field.is_some()
+    }
+
+    /// Returns `true` if the member's value is created from the values of the
+    /// members lifted from its struct with `#[builder(flatten)]`.
+    pub(crate) fn is_flatten(&self) -> bool {
+        self.params.flatten.is_some()
+    }
+
+    /// Returns `true` if the member is set via setters and is tracked in the
+    /// builder's type state.
+    pub(crate) fn is_named(&self) -> bool {
+        !self.is_start_fn()
+            && !self.is_finish_fn()
+            && !self.is_skipped()
+            && !self.is_field()
+            && !self.is_flatten()
+    }
+
+    /// Returns `true` if the member is a positional parameter of the finishing
+    /// function. Such members are not stored in the builder at all.
+    pub(crate) fn is_finish_fn(&self) -> bool {
+        self.params.finish_fn.is_present()
+    }
+
+    /// Text of the member's doc comments with the lines trimmed. Returns `None`
+    /// if there are no docs.
+    pub(crate) fn doc_text(&self) -> Option<String> {
+        let text = self
+            .docs
+            .iter()
+            .filter_map(|attr| match attr.as_doc()? {
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(doc),
+                    ..
+                }) => Some(doc.value().trim().to_owned()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let text = text.trim();
+
+        (!text.is_empty()).then(|| text.to_owned())
+    }
+
+    /// Name of the variant in the generated enum of members.
+    pub(crate) fn enum_variant_ident(&self) -> syn::Ident {
+        self.to_pascal_case(&self.public_ident())
+    }
+
+    /// Keeps the leading underscore in the public name of the member and in the
+    /// names of the items generated for it, so that they don't collide with
+    /// the ones of the member that is named the same but without the underscore.
+    pub(crate) fn keep_leading_underscore(&mut self) {
+        self.keep_leading_underscore = true;
+        self.state_assoc_type_ident = self.enum_variant_ident();
+    }
+
+    /// Converts the ident to PascalCase. The leading underscore is kept if
+    /// it was requested with `#[builder(keep_leading_underscore)]`.
+    fn to_pascal_case(&self, ident: &syn::Ident) -> syn::Ident {
+        let pascal_case = ident.to_pascal_case();
+
+        if !self.keep_leading_underscore || !ident.raw_name().starts_with('_') {
+            return pascal_case;
         }
+
+        quote::format_ident!("_{}", pascal_case)
     }
 
-    pub(crate) fn set_state_type_param(&self) -> TokenStream2 {
-        let ty = &self.ty;
+    pub(crate) fn as_optional(&self) -> Option<&syn::Type> {
+        if self.params.required.is_present() {
+            return None;
+        }
+
+        self.ty.option_type_param().or_else(|| {
+            let has_default = self.params.default.is_some()
+                || self.params.flag.is_present()
+                || self.default_from_instance;
 
-        self.as_optional()
-            .map(|ty| quote!(Option<#ty>))
-            .unwrap_or_else(|| quote!(#ty))
+            has_default.then_some(&self.ty)
+        })
     }
 
-    pub(crate) fn set_state_type(&self) -> TokenStream2 {
-        let ty = self.set_state_type_param();
+    /// Type of the member's value without the `Option` for optional members.
+    /// The setters that accumulate the value of a collection member operate on it.
+    pub(crate) fn inner_type(&self) -> &syn::Type {
+        self.as_optional().unwrap_or(&self.ty)
+    }
+
+    /// Returns `true` if the member must be set before the finishing function
+    /// can be called.
+    pub(crate) fn is_required(&self) -> bool {
+        self.as_optional().is_none()
+    }
+
+    /// Type of the builder's field that stores the value of the member. It's
+    /// `None` until the member is set.
+    pub(crate) fn storage_type(&self) -> TokenStream2 {
+        let ty = self.inner_type();
+        quote!(::core::option::Option<#ty>)
+    }
+
+    /// Returns `true` if the builder stores the value of the member with the
+    /// same type as the member itself. Named members are stored wrapped in an
+    /// `Option` unless the member is an optional `Option<T>` already.
+    pub(crate) fn is_stored_as_is(&self) -> bool {
+        if !self.is_named() {
+            return true;
+        }
 
-        quote!(::bon::private::Set<#ty>)
+        self.ty.is_option() && self.as_optional().is_some()
     }
 }
@@ -0,0 +1,128 @@
+use super::BuilderGenCtx;
+use crate::util::prelude::*;
+use quote::{format_ident, quote};
+
+impl BuilderGenCtx {
+    /// Generates the state of the builder after two builders are merged. It's
+    /// placed in the module with the other states of the builder.
+    pub(crate) fn merged_state_decl(&self, vis: &syn::Visibility) -> Option<TokenStream2> {
+        if !self.merge.is_present() {
+            return None;
+        }
+
+        let bon = &self.bon;
+        let assoc_types_idents = self.member_assoc_type_idents().collect::<Vec<_>>();
+
+        // The docs are placed inside of the child module, so the links
+        // to the builder must go through the parent module
+        let docs = format!(
+            "State of [`{0}`](super::{0}) after the builder in the state `L` is merged \
+            with the builder in the state `R`. Each member is set if it's set in \
+            either of the states.",
+            self.builder_ident,
+        );
+
+        Some(quote! {
+            #[doc = #docs]
+            #vis struct Merged<L: State, R: State>(
+                ::core::marker::PhantomData<fn() -> (L, R)>,
+            );
+
+            impl<L: State, R: State> State for Merged<L, R>
+            where
+                #( L::#assoc_types_idents: #bon::private::MergeState<R::#assoc_types_idents>, )*
+            {
+                #(
+                    type #assoc_types_idents = <
+                        L::#assoc_types_idents as #bon::private::MergeState<R::#assoc_types_idents>
+                    >::Output;
+                )*
+            }
+        })
+    }
+
+    /// Generates the method that combines two partially built builders. The
+    /// members set in the other builder take precedence.
+    pub(crate) fn merge_method_impl(&self) -> Option<TokenStream2> {
+        if !self.merge.is_present() {
+            return None;
+        }
+
+        let bon = &self.bon;
+        let state_var = &self.state_var;
+        let other_state_var = format_ident!("{}Other", state_var.raw_name());
+        let vis = self.builder_type_vis();
+        let builder_ident = &self.builder_ident;
+        let builder_mod_ident = &self.builder_mod_ident;
+        let builder_state_trait = self.builder_state_trait();
+        let generics_decl = &self.generics.params;
+        let generic_args = self.generic_args().collect::<Vec<_>>();
+        let where_clause_predicates = self
+            .generics
+            .where_clause
+            .as_ref()
+            .into_iter()
+            .flat_map(|where_clause| &where_clause.predicates);
+
+        let merge_state_predicates = self.member_assoc_type_idents().map(|assoc_type_ident| {
+            quote! {
+                #state_var::#assoc_type_ident: #bon::private::MergeState<
+                    #other_state_var::#assoc_type_ident
+                >
+            }
+        });
+
+        // The members that aren't tracked in the type state are always
+        // present, so they and the receiver are kept from this builder
+        let members_merge = self.named_members().map(|member| {
+            let ident = &member.ident;
+
+            quote! {
+                self.__private_impl.#ident = ::core::option::Option::or(
+                    other.__private_impl.#ident,
+                    self.__private_impl.#ident,
+                );
+            }
+        });
+
+        let builder = self.builder_expr(quote!(self.__private_impl));
+
+        Some(quote! {
+            // The state bounds don't mention the member types, so the lifetimes
+            // of the builder may be used only once in this header
+            #[allow(single_use_lifetimes)]
+            impl<
+                #(#generics_decl,)*
+                #state_var: #builder_state_trait
+            >
+            #builder_ident<
+                #(#generic_args,)*
+                #state_var
+            >
+            where
+                #( #where_clause_predicates, )*
+            {
+                /// Merges this builder with the `other` one. The members that are set
+                /// in the `other` builder take precedence over the ones set in this
+                /// builder. The resulting builder has a member set if it's set in
+                /// either of them.
+                ///
+                /// The members that aren't set via setters and the receiver of the
+                /// method are taken from this builder.
+                #vis fn merge<#other_state_var: #builder_state_trait>(
+                    mut self,
+                    other: #builder_ident<#(#generic_args,)* #other_state_var>,
+                ) -> #builder_ident<
+                    #(#generic_args,)*
+                    #builder_mod_ident::Merged<#state_var, #other_state_var>
+                >
+                where
+                    #( #merge_state_predicates, )*
+                {
+                    #( #members_merge )*
+                    #builder
+                }
+            }
+        })
+    }
+}
@@ -1,4 +1,18 @@
+mod apply_to;
+mod builder_derives;
+mod dyn_builder;
+mod dynamic;
+mod finish_clone;
+mod finish_unwrap;
+mod flatten;
+mod group;
+mod into_builder;
 mod member;
+mod merge;
+mod nested;
+mod preset;
+mod runtime_checks;
+mod section;
 mod setter_methods;
 
 pub(crate) mod input_func;
@@ -6,9 +20,15 @@ pub(crate) mod input_struct;
 
 use member::*;
 
+use crate::builder::params::{
+    BuilderDerives, ImplementsParams, MaybeSetters, OnParams, PresetParams, ValidateParams,
+};
 use crate::util::prelude::*;
 use itertools::Itertools;
-use quote::quote;
+use quote::{quote, quote_spanned, ToTokens};
+use std::collections::BTreeSet;
+use syn::spanned::Spanned;
+use syn::visit_mut::VisitMut;
 
 pub(crate) struct AssocMethodReceiverCtx {
     pub(crate) with_self_keyword: syn::Receiver,
@@ -33,7 +53,7 @@ impl AssocMethodCtx {
         }
     }
 
-    fn ty_without_self_keyword(&self) -> &syn::Type {
+    pub(crate) fn ty_without_self_keyword(&self) -> &syn::Type {
         match self {
             AssocMethodCtx::Receiver(receiver) => &receiver.without_self_keyword,
             AssocMethodCtx::Free(parent) => &parent.self_ty,
@@ -45,15 +65,161 @@ pub(crate) struct BuilderGenCtx {
     pub(crate) members: Vec<Member>,
 
     pub(crate) generics: Generics,
+
+    /// Visibility of the generated items that don't override it
     pub(crate) vis: syn::Visibility,
+
+    /// If present overrides the visibility of the builder type, its
+    /// setters and the other items that describe it
+    pub(crate) builder_type_vis: Option<syn::Visibility>,
     pub(crate) assoc_method_ctx: Option<AssocMethodCtx>,
 
     pub(crate) start_func: StartFunc,
     pub(crate) finish_func: FinishFunc,
 
+    /// Trait that the builder implements by delegating to the setters and
+    /// the finishing function
+    pub(crate) implements: Option<ImplementsParams>,
+
+    pub(crate) closure_func: Option<ClosureFunc>,
+
+    /// Instance of the struct to take the values of unset members from
+    pub(crate) default_from: Option<DefaultFrom>,
+
+    /// Methods of the struct that convert its instance into the builder
+    pub(crate) into_builder_funcs: Option<IntoBuilderFuncs>,
+
+    /// Type of the struct that the values of the set members can be applied
+    /// to with the `apply_to` method of the builder
+    pub(crate) apply_to: Option<syn::Type>,
+
+    /// Implementation of `bon::nested::HasBuilder` for the struct, which lets
+    /// the `#[builder(nested)]` setters of other builders create this builder
+    pub(crate) has_builder_impl: Option<HasBuilderImpl>,
+
+    /// Start functions of the struct that create the builder with some
+    /// members already set
+    pub(crate) preset_funcs: Option<PresetFuncs>,
+
+    /// Prefix that is prepended to the names of all setters
+    pub(crate) setter_prefix: Option<syn::Ident>,
+
+    /// Configures the `maybe_` setters of all optional members
+    pub(crate) maybe_setters: Option<MaybeSetters>,
+
+    pub(crate) validator: Option<Validator>,
+
+    /// Function or closure that accepts mutable references to the members
+    /// and is called before the validator
+    pub(crate) pre_build: Option<syn::Expr>,
+
+    /// Settings applied to the members of the matching types
+    pub(crate) on_params: Vec<OnParams>,
+
+    /// Traits that are implemented for the builder
+    pub(crate) derives: BuilderDerives,
+
+    /// Generates the finishing function that clones the builder's values
+    pub(crate) finish_fn_clone: darling::util::Flag,
+
+    /// Enables the runtime mode of the builder
+    pub(crate) dynamic: darling::util::Flag,
+
+    /// Generates the method that merges two builders
+    pub(crate) merge: darling::util::Flag,
+
+    /// Makes the setters of the optional members take `&mut self`
+    pub(crate) mut_setters: darling::util::Flag,
+
+    /// Span of `#[builder(check = "runtime")]` if the builder has no type
+    /// state, and the required members are checked when finishing building
+    pub(crate) runtime_checks: Option<proc_macro2::Span>,
+
+    /// Generates the dyn-compatible trait implemented by the builder
+    pub(crate) dyn_builder: darling::util::Flag,
+
+    /// Adds `#[track_caller]` to the functions that finish building
+    pub(crate) track_caller: darling::util::Flag,
+
+    /// `#[doc(cfg(...))]` attributes of the underlying item that are copied
+    /// to the generated items
+    pub(crate) doc_cfg_attrs: Vec<syn::Attribute>,
+
+    /// Path to the `bon` crate, which is `::bon` unless it's overridden
+    /// with `#[builder(crate = ...)]`
+    pub(crate) bon: syn::Path,
+
+    /// Types that are stored in the phantom data of the builder in addition
+    /// to the types of the members to mark the generic parameters that the
+    /// members don't reference as used
+    pub(crate) extra_phantom_types: Vec<syn::Type>,
+
     pub(crate) builder_ident: syn::Ident,
     pub(crate) builder_private_impl_ident: syn::Ident,
-    pub(crate) builder_state_trait_ident: syn::Ident,
+
+    /// Module that contains the type state trait and the state types of the
+    /// builder, so that they don't clutter the surrounding scope
+    pub(crate) builder_mod_ident: syn::Ident,
+
+    /// Name of the enum that lists all members of the builder. It's generated
+    /// only if `#[builder(member_enum)]` is specified.
+    pub(crate) builder_member_enum_ident: Option<syn::Ident>,
+
+    /// Name of the generic parameter for the type state of the builder. It's
+    /// `__State` unless the underlying item already uses this identifier.
+    pub(crate) state_var: syn::Ident,
+
+    /// Name of the generic parameter for the closure of the closure function.
+    /// It's `__Configure` unless the underlying item already uses this identifier.
+    pub(crate) configure_var: syn::Ident,
+}
+
+/// Methods generated on the struct that create the builder with all members
+/// set from the values of the struct's fields
+pub(crate) struct DefaultFrom {
+    /// Name of the struct used in the pattern that moves the fields out of the instance
+    pub(crate) struct_ident: syn::Ident,
+
+    /// Type of the struct
+    pub(crate) struct_ty: syn::Type,
+
+    /// Expression that creates the instance. It's evaluated lazily, only if
+    /// some member wasn't set.
+    pub(crate) expr: TokenStream2,
+}
+
+pub(crate) struct IntoBuilderFuncs {
+    /// Type of the struct that the methods are defined on
+    pub(crate) struct_ty: syn::Type,
+
+    /// Generates `into_builder(self)`
+    pub(crate) into_builder: bool,
+
+    /// Generates `to_builder(&self)`, which clones the values of the fields
+    pub(crate) to_builder: bool,
+}
+
+/// Implementation of `bon::nested::HasBuilder` for the struct that creates
+/// the builder with the starting function
+pub(crate) struct HasBuilderImpl {
+    /// Type of the struct that the trait is implemented for
+    pub(crate) struct_ty: syn::Type,
+
+    /// Type that the starting function is defined on. It's the struct itself
+    /// unless it was overridden with `#[builder(start_fn(on = ...))]`.
+    pub(crate) start_func_on: syn::Type,
+}
+
+/// Start functions generated on the struct for `#[builder(preset(...))]`
+pub(crate) struct PresetFuncs {
+    /// Type of the struct that the functions are defined on
+    pub(crate) struct_ty: syn::Type,
+
+    /// Type that the regular starting function is defined on, which is
+    /// called by the preset functions to create the builder
+    pub(crate) start_func_on: syn::Type,
+
+    pub(crate) presets: Vec<PresetParams>,
 }
 
 pub(crate) struct FinishFunc {
@@ -62,6 +228,28 @@ pub(crate) struct FinishFunc {
     pub(crate) asyncness: Option<syn::Token![async]>,
     pub(crate) body: Box<dyn FinishFuncBody>,
     pub(crate) output: syn::ReturnType,
+
+    /// The `# Safety` section of the docs of the underlying `unsafe` function
+    pub(crate) safety_docs: Vec<syn::Attribute>,
+
+    /// Attributes of the underlying function that are carried over to the
+    /// finishing function, e.g. `#[must_use]`
+    pub(crate) attrs: Vec<syn::Attribute>,
+
+    /// If present overrides the automatic visibility
+    pub(crate) vis: Option<syn::Visibility>,
+
+    /// Function that transforms the value produced by the body, which
+    /// is configured with `#[builder(finish_fn(map = ...))]`
+    pub(crate) map: Option<syn::Expr>,
+
+    /// Name of the additional finishing function that panics if the validation
+    /// fails, which is configured with `#[builder(finish_fn(unwrap = ...))]`
+    pub(crate) unwrap: Option<syn::Ident>,
+
+    /// Output of the finishing function before it became fallible because
+    /// of the validators. It's `None` if there are no validators.
+    pub(crate) infallible_output: Option<syn::ReturnType>,
 }
 
 pub(crate) struct StartFunc {
@@ -73,6 +261,48 @@ pub(crate) struct StartFunc {
 
     /// If present overrides the automatic visibility
     pub(crate) vis: Option<syn::Visibility>,
+
+    /// Whether the start function is defined inside of an `impl` block
+    pub(crate) is_assoc: bool,
+}
+
+pub(crate) struct Validator {
+    /// Function or closure that accepts references to the members
+    /// and returns a `Result<(), E>`
+    pub(crate) func: syn::Expr,
+
+    /// Error type returned by the finishing function
+    pub(crate) error: syn::Type,
+
+    /// The validation function returns a future that is awaited
+    pub(crate) is_async: bool,
+}
+
+impl Validator {
+    pub(crate) fn new(params: ValidateParams) -> Self {
+        // Any error type that implements `std::error::Error` as well as
+        // string messages can be converted into this boxed error by default
+        let error = params.error.unwrap_or_else(|| {
+            syn::parse_quote!(
+                ::std::boxed::Box<
+                    dyn ::std::error::Error + ::core::marker::Send + ::core::marker::Sync,
+                >
+            )
+        });
+
+        Self {
+            func: params.with,
+            error,
+            is_async: params.is_async,
+        }
+    }
+}
+
+pub(crate) struct ClosureFunc {
+    pub(crate) ident: syn::Ident,
+
+    /// If present overrides the visibility of the start function
+    pub(crate) vis: Option<syn::Visibility>,
 }
 
 pub(crate) trait FinishFuncBody {
@@ -92,53 +322,482 @@ pub(crate) struct Generics {
 
 pub(crate) struct MacroOutput {
     pub(crate) start_func: syn::ItemFn,
+
+    /// Must be placed next to the start function, because it invokes it
+    pub(crate) closure_func: Option<syn::ItemFn>,
+
     pub(crate) other_items: TokenStream2,
 }
 
+impl MacroOutput {
+    /// Adds the attributes to all of the generated items
+    fn add_attrs(&mut self, attrs: &[syn::Attribute]) -> Result {
+        self.start_func.attrs.extend(attrs.iter().cloned());
+
+        if let Some(closure_func) = &mut self.closure_func {
+            closure_func.attrs.extend(attrs.iter().cloned());
+        }
+
+        let mut other_items: syn::File = syn::parse2(std::mem::take(&mut self.other_items))?;
+
+        for item in &mut other_items.items {
+            let Some(item_attrs) = item.attrs_mut() else {
+                bail!(item, "BUG: can't add attributes to a verbatim item");
+            };
+            item_attrs.extend(attrs.iter().cloned());
+        }
+
+        self.other_items = other_items.into_token_stream();
+
+        Ok(())
+    }
+}
+
 impl BuilderGenCtx {
+    fn builder_type_vis(&self) -> &syn::Visibility {
+        self.builder_type_vis.as_ref().unwrap_or(&self.vis)
+    }
+
+    fn finish_func_vis(&self) -> &syn::Visibility {
+        self.finish_func.vis.as_ref().unwrap_or(&self.vis)
+    }
+
     fn member_idents(&self) -> impl Iterator<Item = syn::Ident> + '_ {
-        self.members.iter().map(|member| member.ident.clone())
+        self.stored_members().map(|member| member.ident.clone())
     }
 
-    fn member_assoc_type_idents(&self) -> impl Iterator<Item = &syn::Ident> {
+    /// Members that are stored in the builder's fields
+    fn stored_members(&self) -> impl Iterator<Item = &Member> {
         self.members
             .iter()
+            .filter(|member| !member.is_finish_fn() && !member.is_skipped() && !member.is_flatten())
+    }
+
+    /// Members that are set via setters and tracked in the builder's type state
+    fn named_members(&self) -> impl Iterator<Item = &Member> {
+        self.members.iter().filter(|member| member.is_named())
+    }
+
+    /// Members that are passed as positional parameters to the start function
+    fn start_fn_members(&self) -> impl Iterator<Item = &Member> {
+        self.members.iter().filter(|member| member.is_start_fn())
+    }
+
+    /// Members that are stored in the private fields of the builder without setters
+    fn field_members(&self) -> impl Iterator<Item = &Member> {
+        self.members.iter().filter(|member| member.is_field())
+    }
+
+    /// Members that are passed as positional parameters to the finishing function
+    fn finish_fn_members(&self) -> impl Iterator<Item = &Member> {
+        self.members.iter().filter(|member| member.is_finish_fn())
+    }
+
+    /// Members whose values are passed to the body of the finishing function.
+    /// The values of the members lifted with `#[builder(flatten)]` are passed
+    /// inside of their struct instead.
+    fn body_members(&self) -> impl Iterator<Item = &Member> {
+        self.members
+            .iter()
+            .filter(|member| member.flattened_from.is_none())
+    }
+
+    /// Members listed in the builder's API. The `#[builder(flatten)]` members
+    /// are represented by the members lifted from their structs.
+    fn listed_members(&self) -> impl Iterator<Item = &Member> {
+        self.members.iter().filter(|member| !member.is_flatten())
+    }
+
+    fn member_assoc_type_idents(&self) -> impl Iterator<Item = &syn::Ident> {
+        self.named_members()
             .map(|member| &member.state_assoc_type_ident)
     }
 
-    fn unset_state_types(&self) -> impl Iterator<Item = TokenStream2> + '_ {
-        self.members.iter().map(|arg| arg.unset_state_type())
+    /// Returns `true` if the setters of the member take `&mut self` and don't
+    /// change the type state of the builder. The state of such a member stays
+    /// unset, so whether it was set is known only from its stored value.
+    fn has_mut_setters(&self, member: &Member) -> bool {
+        self.mut_setters.is_present() && !member.is_required()
+    }
+
+    /// Path to the type state trait of the builder
+    fn builder_state_trait(&self) -> TokenStream2 {
+        let builder_mod_ident = &self.builder_mod_ident;
+        quote!(#builder_mod_ident::State)
+    }
+
+    /// Declaration of the type state parameter for the `impl` blocks of the
+    /// builder in any state. It's absent if the builder has no type state
+    /// because of `#[builder(check = "runtime")]`.
+    fn state_param(&self) -> Option<TokenStream2> {
+        if self.runtime_checks.is_some() {
+            return None;
+        }
+
+        let state_var = &self.state_var;
+        let builder_state_trait = self.builder_state_trait();
+        Some(quote!(#state_var: #builder_state_trait))
+    }
+
+    /// The type state argument of the builder. See [`Self::state_param`].
+    fn state_arg(&self) -> Option<&syn::Ident> {
+        self.runtime_checks.is_none().then_some(&self.state_var)
+    }
+
+    /// Expression that creates the builder from the given value of the private
+    /// struct with the values of the members. The type state of the builder is
+    /// inferred from the context.
+    fn builder_expr(&self, private_impl: TokenStream2) -> TokenStream2 {
+        let builder_ident = &self.builder_ident;
+        let state_field = self
+            .state_arg()
+            .map(|_| quote!(__private_state: ::core::marker::PhantomData,));
+
+        quote! {
+            #builder_ident {
+                #state_field
+                __private_impl: #private_impl,
+            }
+        }
+    }
+
+    /// Path to the state type of the builder where no members are set yet
+    fn empty_state(&self) -> TokenStream2 {
+        let builder_mod_ident = &self.builder_mod_ident;
+        quote!(#builder_mod_ident::Empty)
+    }
+
+    /// Path to the state type that marks the given member as set on top
+    /// of the previous state of the builder
+    fn set_state(&self, member: &Member) -> TokenStream2 {
+        let builder_mod_ident = &self.builder_mod_ident;
+        let set_state_ident = set_state_ident(member);
+        quote!(#builder_mod_ident::#set_state_ident)
+    }
+
+    /// Members that must be set before the setters of the given member are
+    /// available, as configured with `#[builder(requires = ...)]`.
+    fn required_by(&self, member: &Member) -> Result<Vec<&Member>> {
+        let Some(requires) = &member.params.requires else {
+            return Ok(vec![]);
+        };
+
+        requires
+            .iter()
+            .map(|ident| {
+                if *ident == member.ident {
+                    bail!(ident, "the member can't require itself to be set");
+                }
+
+                self.referenced_member(ident)
+            })
+            .collect()
+    }
+
+    /// Members that must be unset for the setters of the given member to be
+    /// available. The conflicts configured with `#[builder(conflicts_with = ...)]`
+    /// are symmetric, so they include the members that list the given one.
+    fn conflicting_with(&self, member: &Member) -> Result<Vec<&Member>> {
+        let declared = member
+            .params
+            .conflicts_with
+            .iter()
+            .flat_map(|idents| &**idents);
+
+        let mut conflicting: Vec<&Member> = declared
+            .map(|ident| {
+                if *ident == member.ident {
+                    bail!(ident, "the member can't conflict with itself");
+                }
+
+                let other = self.referenced_member(ident)?;
+
+                for required in [member, other] {
+                    if required.is_required() {
+                        bail!(
+                            ident,
+                            "the member `{}` is required, so it can't conflict with \
+                            other members, because it must always be set",
+                            required.ident.raw_name(),
+                        );
+                    }
+                }
+
+                let is_also_required = member
+                    .params
+                    .requires
+                    .as_ref()
+                    .is_some_and(|requires| requires.contains(ident));
+
+                if is_also_required {
+                    bail!(
+                        ident,
+                        "the member `{}` can't be both required by and conflicting \
+                        with this member",
+                        ident.raw_name(),
+                    );
+                }
+
+                Ok(other)
+            })
+            .try_collect()?;
+
+        let reverse = self.named_members().filter(|other| {
+            other
+                .params
+                .conflicts_with
+                .as_ref()
+                .is_some_and(|idents| idents.contains(&member.ident))
+        });
+
+        for other in reverse {
+            if conflicting.iter().all(|known| known.ident != other.ident) {
+                conflicting.push(other);
+            }
+        }
+
+        Ok(conflicting)
+    }
+
+    /// Finds the member referenced by its name in the attributes of another
+    /// member that configure the dependencies between the members' states.
+    fn referenced_member(&self, ident: &syn::Ident) -> Result<&Member> {
+        let referenced = self
+            .named_members()
+            .find(|other| other.ident == *ident)
+            .ok_or_else(|| {
+                err!(
+                    ident,
+                    "there is no member `{}` with setters in this builder",
+                    ident.raw_name(),
+                )
+            })?;
+
+        if self.has_mut_setters(referenced) {
+            bail!(
+                ident,
+                "the member `{}` has setters that take `&mut self`, so whether \
+                it was set isn't tracked in the type state",
+                ident.raw_name(),
+            );
+        }
+
+        Ok(referenced)
+    }
+
+    /// Returns `true` if the setters of any member require the given one
+    /// to be set.
+    fn is_required_by_others(&self, member: &Member) -> bool {
+        self.named_members().any(|other| {
+            other
+                .params
+                .requires
+                .as_ref()
+                .is_some_and(|requires| requires.contains(&member.ident))
+        })
+    }
+
+    /// Path to the trait that is implemented for the state of the given
+    /// member if it's set. It reports which member wasn't set otherwise.
+    fn is_set_trait(&self, member: &Member) -> TokenStream2 {
+        let bon = &self.bon;
+        let builder_mod_ident = &self.builder_mod_ident;
+        let member_ident = member.public_ident();
+        quote!(#bon::state::IsSetMember<#builder_mod_ident::members::#member_ident>)
+    }
+
+    /// Parameters of the start function and the expressions that initialize
+    /// the builder's fields from them for the members marked with `start_fn`.
+    fn start_fn_params(&self) -> Result<(Vec<TokenStream2>, Vec<TokenStream2>)> {
+        let mut params = vec![];
+        let mut inits = vec![];
+
+        for member in self.start_fn_members() {
+            let (param, init) = self.positional_param(member)?;
+            let ident = &member.ident;
+            params.push(param);
+            inits.push(quote!(#ident: #init));
+        }
+
+        Ok((params, inits))
+    }
+
+    /// Parameters of the finishing function for the members marked with
+    /// `finish_fn` and the expressions that convert them to the member's type.
+    fn finish_fn_params(&self) -> Result<(Vec<TokenStream2>, Vec<TokenStream2>)> {
+        self.finish_fn_members()
+            .map(|member| self.positional_param(member))
+            .collect::<Result<Vec<_>>>()
+            .map(|params| params.into_iter().unzip())
+    }
+
+    /// Declaration of a positional parameter for the member and the expression
+    /// that converts its value to the member's type.
+    fn positional_param(&self, member: &Member) -> Result<(TokenStream2, TokenStream2)> {
+        let ident = &member.ident;
+        let ty = &member.ty;
+
+        if self.member_qualifies_for_into(member, ty)? {
+            return Ok((
+                quote!(#ident: impl ::core::convert::Into<#ty>),
+                quote!(::core::convert::Into::into(#ident)),
+            ));
+        }
+
+        Ok((quote!(#ident: #ty), quote!(#ident)))
+    }
+
+    /// Returns `true` if any member has a `#[builder(validate = ...)]` function
+    /// Members with validators or environment variables make the finishing
+    /// function fallible, because their errors are collected together.
+    fn has_member_checks(&self) -> bool {
+        self.members
+            .iter()
+            .any(|member| member.params.validate.is_some() || member.params.env.is_some())
     }
 
     fn generic_args(&self) -> impl Iterator<Item = syn::GenericArgument> + '_ {
         self.generics.params.iter().map(generic_param_to_arg)
     }
 
-    pub(crate) fn output(self) -> Result<MacroOutput> {
-        let start_func = self.start_func();
-        let builder_state_trait_decl = self.builder_state_trait_decl();
+    pub(crate) fn output(mut self) -> Result<MacroOutput> {
+        if let Some(map) = &self.finish_func.map {
+            let output = map_output(map)?;
+            self.finish_func.output = syn::parse_quote!(-> #output);
+        }
+
+        // The finishing function becomes fallible if there is a validator or
+        // a member with an environment variable. The errors of the members are
+        // returned as is unless there is also a validator of the whole builder,
+        // which defines the error type.
+        let bon = &self.bon;
+        let error = match &self.validator {
+            Some(validator) => Some(validator.error.clone()),
+            None => self
+                .has_member_checks()
+                .then(|| syn::parse_quote!(#bon::ValidationErrors)),
+        };
+
+        if let Some(error) = error {
+            let output = match &self.finish_func.output {
+                syn::ReturnType::Default => quote!(()),
+                syn::ReturnType::Type(_, ty) => quote!(#ty),
+            };
+
+            let infallible_output = std::mem::replace(
+                &mut self.finish_func.output,
+                syn::parse_quote!(-> ::core::result::Result<#output, #error>),
+            );
+            self.finish_func.infallible_output = Some(infallible_output);
+        }
+
+        let mut output = self.output_impl()?;
+
+        // The builder is available under the same conditions as the underlying
+        // item, so it must be documented with the same feature badges
+        output.add_attrs(&self.doc_cfg_attrs)?;
+
+        Ok(output)
+    }
+
+    fn output_impl(&self) -> Result<MacroOutput> {
+        self.validate_runtime_checks()?;
+
+        let start_func = self.start_func()?;
+        let closure_func = self.closure_func()?;
+        let builder_state_trait_decl = self.builder_state_trait_decl()?;
         let builder_decl = self.builder_decl();
+        let member_enum_decl = self.member_enum_decl();
+        let members_info_impl = self.members_info_impl();
         let call_method_impl = self.finish_method_impl()?;
+        let finish_clone_method_impl = self.finish_clone_method_impl()?;
+        let finish_unwrap_method_impl = self.finish_unwrap_method_impl()?;
+        let implements_trait_impl = self.implements_trait_impl()?;
         let setter_methods_impls = self.setter_methods_impls()?;
+        let builder_derives = self.builder_derives()?;
+        let dynamic_methods_impl = self.dynamic_methods_impl();
+        let merge_method_impl = self.merge_method_impl();
+        let instance_into_builder_impl = self.instance_into_builder_impl();
+        let apply_to_method_impl = self.apply_to_method_impl();
+        let sections_impl = self.sections_impl()?;
+        let nested_traits_impl = self.nested_traits_impl();
+        let preset_funcs_impl = self.preset_funcs_impl()?;
+        let dyn_builder_impl = self.dyn_builder_impl()?;
 
         let other_items = quote! {
             #builder_state_trait_decl
             #builder_decl
+            #builder_derives
+            #member_enum_decl
+            #members_info_impl
             #call_method_impl
+            #finish_clone_method_impl
+            #finish_unwrap_method_impl
+            #implements_trait_impl
             #setter_methods_impls
+            #dynamic_methods_impl
+            #merge_method_impl
+            #instance_into_builder_impl
+            #apply_to_method_impl
+            #sections_impl
+            #nested_traits_impl
+            #preset_funcs_impl
+            #dyn_builder_impl
         };
 
         Ok(MacroOutput {
             start_func,
+            closure_func,
             other_items,
         })
     }
 
+    /// Output of the finishing function where the precise capturing syntax
+    /// `use<...>` lists the generic parameters of the builder, the state one
+    /// and the given additional type parameters, which are all in scope of
+    /// the functions that return this output.
+    fn finish_func_output(&self, extra_type_params: &[&syn::Ident]) -> syn::ReturnType {
+        self.precise_output(self.finish_func.output.clone(), extra_type_params)
+    }
+
+    /// Adds the precise capturing syntax to the `impl Trait` types in the
+    /// given output. See [`Self::finish_func_output`] for details.
+    fn precise_output(
+        &self,
+        mut output: syn::ReturnType,
+        extra_type_params: &[&syn::Ident],
+    ) -> syn::ReturnType {
+        let type_params = self
+            .generics
+            .params
+            .iter()
+            .filter_map(|param| match param {
+                syn::GenericParam::Type(param) => Some(param.ident.clone()),
+                syn::GenericParam::Const(param) => Some(param.ident.clone()),
+                syn::GenericParam::Lifetime(_) => None,
+            })
+            .chain(self.state_arg().cloned())
+            .chain(extra_type_params.iter().map(|&param| param.clone()))
+            .collect();
+
+        PreciseCaptures { type_params }.visit_return_type_mut(&mut output);
+        output
+    }
+
+    /// The message of the `#[must_use]` attributes of the builder type and
+    /// the functions that return it
+    fn must_use_message(&self) -> String {
+        format!(
+            "the builder does nothing until you call `{}()` on it to finish building",
+            self.finish_func.ident
+        )
+    }
+
     fn start_func_generics(&self) -> &Generics {
         self.start_func.generics.as_ref().unwrap_or(&self.generics)
     }
 
-    fn start_func(&self) -> syn::ItemFn {
+    fn start_func(&self) -> Result<syn::ItemFn> {
         let builder_ident = &self.builder_ident;
 
         let docs = &self.start_func.attrs;
@@ -160,7 +819,17 @@ impl BuilderGenCtx {
         let where_clause = &generics.where_clause;
         let generic_args = self.generic_args();
 
-        let member_idents = self.member_idents();
+        let named_member_idents = self.named_members().map(|member| &member.ident);
+        let (start_fn_params, start_fn_inits) = self.start_fn_params()?;
+        let field_inits = self.field_members().map(|member| {
+            let ident = &member.ident;
+            let ty = &member.ty;
+            let init = match member.params.field.as_deref() {
+                Some(Some(expr)) => quote!(#expr),
+                _ => quote!(<#ty as ::core::default::Default>::default()),
+            };
+            quote!(#ident: #init)
+        });
 
         let receiver = self
             .assoc_method_ctx
@@ -174,38 +843,193 @@ impl BuilderGenCtx {
             }
         });
 
-        let receiver = receiver.map(|receiver| &receiver.with_self_keyword);
+        let receiver = receiver.map(|receiver| {
+            let receiver = &receiver.with_self_keyword;
+            quote!(#receiver,)
+        });
+
+        let builder = self.builder_expr(quote! {
+            #builder_private_impl_ident {
+                _phantom: ::core::marker::PhantomData,
+                #receiver_field_init
+                #( #start_fn_inits, )*
+                #( #field_inits, )*
+                #( #named_member_idents: ::core::option::Option::None, )*
+            }
+        });
+
+        let must_use_message = self.must_use_message();
 
         let func = quote! {
             #(#docs)*
+            #[must_use = #must_use_message]
             #vis fn #start_func_ident<#(#generics_decl),*>(
                 #receiver
+                #( #start_fn_params, )*
             ) -> #builder_ident<
                 #(#generic_args,)*
             >
             #where_clause
             {
-                #builder_ident {
-                    __private_impl: #builder_private_impl_ident {
-                        _phantom: ::core::marker::PhantomData,
-                        #receiver_field_init
-                        #( #member_idents: ::std::default::Default::default(), )*
-                    }
-                }
+                #builder
             }
         };
 
-        syn::parse_quote!(#func)
+        Ok(syn::parse_quote!(#func))
     }
 
-    fn phantom_data(&self) -> TokenStream2 {
+    fn closure_func(&self) -> Result<Option<syn::ItemFn>> {
+        let state_var = &self.state_var;
+        let Some(closure_func) = &self.closure_func else {
+            return Ok(None);
+        };
+
+        let builder_ident = &self.builder_ident;
+        let builder_state_trait = self.builder_state_trait();
+        let closure_func_ident = &closure_func.ident;
+        let start_func_ident = &self.start_func.ident;
+        let finish_func_ident = &self.finish_func.ident;
+        let asyncness = &self.finish_func.asyncness;
+        let unsafety = &self.finish_func.unsafety;
+        let maybe_await = asyncness.is_some().then(|| quote!(.await));
+
+        let vis = closure_func
+            .vis
+            .as_ref()
+            .or(self.start_func.vis.as_ref())
+            .unwrap_or(&self.vis);
+
+        let generics = self.start_func_generics();
+        let generics_decl = &generics.params;
+        let generic_args = self.generic_args().collect_vec();
+        let where_clause_predicates = generics
+            .where_clause
+            .as_ref()
+            .into_iter()
+            .flat_map(|where_clause| &where_clause.predicates);
+        let state_where_predicates = self.finish_state_where_predicates();
+        let outlives_predicates: Vec<_> = self
+            .phantom_types()
+            .flat_map(implied_outlives_predicates)
+            .collect();
+
+        let receiver = self
+            .assoc_method_ctx
+            .as_ref()
+            .and_then(AssocMethodCtx::as_receiver)
+            .map(|receiver| &receiver.with_self_keyword);
+
+        let (start_fn_params, _) = self.start_fn_params()?;
+        let start_fn_args = self.start_fn_members().map(|member| &member.ident);
+        let (finish_fn_params, _) = self.finish_fn_params()?;
+        let finish_fn_args = self.finish_fn_members().map(|member| &member.ident);
+
+        let start_func_call = if let Some(receiver) = receiver {
+            let self_token = &receiver.self_token;
+            quote!(#self_token.#start_func_ident(#( #start_fn_args ),*))
+        } else if self.start_func.is_assoc {
+            quote!(Self::#start_func_ident(#( #start_fn_args ),*))
+        } else {
+            quote!(#start_func_ident(#( #start_fn_args ),*))
+        };
+
+        let maybe_receiver = receiver.map(|receiver| quote!(#receiver,));
+
+        let configure_bound = quote! {
+            ::core::ops::FnOnce(
+                #builder_ident<#(#generic_args,)*>
+            ) -> #builder_ident<#(#generic_args,)* #state_var>
+        };
+
+        // The type of the closure must be listed in the precise capturing syntax
+        // `use<...>` of the output if there is one, which isn't possible with
+        // `impl Trait` in the argument position. Otherwise, `impl Trait` is used
+        // to keep the closure's type out of the turbofish syntax.
+        let configure_var = &self.configure_var;
+        let (output, configure_param_decl, configure_ty) =
+            if has_precise_captures(&self.finish_func.output) {
+                (
+                    self.finish_func_output(&[configure_var]),
+                    Some(quote!(#configure_var: #configure_bound)),
+                    quote!(#configure_var),
+                )
+            } else {
+                (
+                    self.finish_func_output(&[]),
+                    None,
+                    quote!(impl #configure_bound),
+                )
+            };
+
+        let start_func_link = if self.start_func.is_assoc {
+            format!("Self::{start_func_ident}")
+        } else {
+            start_func_ident.to_string()
+        };
+
+        let docs = format!(
+            "Same as [`{start_func_link}()`], but accepts a closure that configures \
+            the builder, and finishes building by calling `{finish_func_ident}()` \
+            on the returned builder.",
+        );
+
+        let safety_docs = &self.finish_func.safety_docs;
+
+        // The closure function is an entry point of the builder that also
+        // returns the value of the finishing function
+        let attrs = self
+            .start_func
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("deprecated"))
+            .chain(&self.finish_func.attrs);
+
+        let track_caller = self.track_caller_attr();
+
+        let func = quote! {
+            #[doc = #docs]
+            #( #safety_docs )*
+            #( #attrs )*
+            #track_caller
+            #vis #asyncness #unsafety fn #closure_func_ident<
+                #(#generics_decl,)*
+                #state_var: #builder_state_trait,
+                #configure_param_decl
+            >(
+                #maybe_receiver
+                #( #start_fn_params, )*
+                #( #finish_fn_params, )*
+                configure: #configure_ty,
+            ) #output
+            where
+                #( #where_clause_predicates, )*
+                #( #outlives_predicates, )*
+                #( #state_where_predicates, )*
+            {
+                configure(#start_func_call).#finish_func_ident(#( #finish_fn_args ),*) #maybe_await
+            }
+        };
+
+        Ok(Some(syn::parse_quote!(#func)))
+    }
+
+    /// Types stored in the phantom data of the builder, which mark all generic
+    /// parameters of the builder as used
+    fn phantom_types(&self) -> impl Iterator<Item = &syn::Type> {
         let member_types = self.members.iter().map(|member| member.ty.as_ref());
         let receiver_ty = self
             .assoc_method_ctx
             .as_ref()
             .map(AssocMethodCtx::ty_without_self_keyword);
 
-        let types = receiver_ty.into_iter().chain(member_types);
+        receiver_ty
+            .into_iter()
+            .chain(member_types)
+            .chain(&self.extra_phantom_types)
+    }
+
+    fn phantom_data(&self) -> TokenStream2 {
+        let types = self.phantom_types();
 
         quote! {
             ::core::marker::PhantomData<(
@@ -231,40 +1055,169 @@ impl BuilderGenCtx {
                 // That's a weird implicit behavior in Rust, I suppose there is a reasonable
                 // explanation for it, I just didn't care to research it yet ¯\_(ツ)_/¯.
                 #(#types,)*
-
-                // A special case of zero members requires storing `__State` in phantom data
-                // otherwise it would be reported as an unused type parameter.
-                __State,
             )>
         }
     }
 
-    fn builder_state_trait_decl(&self) -> TokenStream2 {
-        let trait_ident = &self.builder_state_trait_ident;
+    fn builder_state_trait_decl(&self) -> Result<TokenStream2> {
+        if self.runtime_checks.is_some() {
+            return Ok(quote!());
+        }
+
+        let bon = &self.bon;
+        let builder_mod_ident = &self.builder_mod_ident;
         let assoc_types_idents = self.member_assoc_type_idents().collect_vec();
-        let vis = &self.vis;
+        let mod_vis = self.builder_type_vis();
+        let vis = mod_vis.clone().into_equivalent_in_child_module()?;
 
-        quote! {
-            #[doc(hidden)]
-            #vis trait #trait_ident {
-                #( type #assoc_types_idents; )*
+        // The docs are placed inside of the child module, so the links
+        // to the builder must go through the parent module
+        let builder_link = format!("[`{0}`](super::{0})", self.builder_ident);
+
+        let mod_docs = format!(
+            "Type state of {builder_link}. Contains the trait that describes the \
+            state, and the types that represent the states of the builder.",
+        );
+
+        let docs = format!(
+            "Type state of {builder_link}. Each associated type corresponds to a member \
+            and is either `bon::state::Set` or `bon::state::Unset` depending on \
+            whether the member was set. See the `bon::state` module for details.",
+        );
+
+        let assoc_types_docs = self.named_members().map(|member| {
+            format!(
+                "State of the `{}` member.",
+                member.public_ident().raw_name()
+            )
+        });
+
+        let empty_state_docs =
+            format!("Initial state of {builder_link} where none of the members are set.");
+
+        let set_states = self.named_members().map(|member| {
+            let set_state_ident = set_state_ident(member);
+            let docs = format!(
+                "State of {builder_link} where the `{}` member is set in addition \
+                to the members set in the state `S`.",
+                member.public_ident().raw_name(),
+            );
+
+            let assoc_types = self.named_members().map(|other| {
+                let assoc_type_ident = &other.state_assoc_type_ident;
+                if other.ident == member.ident {
+                    quote!(type #assoc_type_ident = #bon::state::Set;)
+                } else {
+                    quote!(type #assoc_type_ident = S::#assoc_type_ident;)
+                }
+            });
+
+            quote! {
+                #[doc = #docs]
+                #vis struct #set_state_ident<S: State = Empty>(
+                    ::core::marker::PhantomData<fn() -> S>,
+                );
+
+                impl<S: State> State for #set_state_ident<S> {
+                    #( #assoc_types )*
+                }
             }
+        });
+
+        let merged_state_decl = self.merged_state_decl(&vis);
+        let group_markers_decl = self.group_markers_decl(&vis)?;
+        let member_markers_decl = self.member_markers_decl(&vis)?;
+
+        Ok(quote! {
+            #[doc = #mod_docs]
+            // The names of the state items keep the leading underscore of the
+            // members with `#[builder(keep_leading_underscore)]` e.g. `Set_Internal`
+            #[allow(non_camel_case_types)]
+            #mod_vis mod #builder_mod_ident {
+                #[doc = #docs]
+                #vis trait State {
+                    #(
+                        #[doc = #assoc_types_docs]
+                        type #assoc_types_idents: #bon::state::MemberState;
+                    )*
+                }
+
+                #[doc = #empty_state_docs]
+                #vis struct Empty(());
+
+                impl State for Empty {
+                    #( type #assoc_types_idents = #bon::state::Unset; )*
+                }
+
+                #( #set_states )*
 
-            impl<#(#assoc_types_idents),*> #trait_ident for (#(#assoc_types_idents,)*) {
-                #( type #assoc_types_idents = #assoc_types_idents; )*
+                #merged_state_decl
+
+                #member_markers_decl
+
+                #group_markers_decl
             }
+        })
+    }
+
+    /// Generates the marker types of the members that must be set before calling
+    /// some of the methods of the builder. They're the arguments of the
+    /// `bon::state::IsSetMember` trait, that names them in the compile errors.
+    fn member_markers_decl(&self, vis: &syn::Visibility) -> Result<TokenStream2> {
+        let markers = self
+            .named_members()
+            .filter(|member| member.is_required() || self.is_required_by_others(member))
+            .map(|member| member.public_ident())
+            .collect_vec();
+
+        if markers.is_empty() {
+            return Ok(quote!());
         }
+
+        let marker_vis = vis.clone().into_equivalent_in_child_module()?;
+        let docs = markers.iter().map(|marker| {
+            format!(
+                "Marker of the `{}` member in the errors about it not being set.",
+                marker.raw_name()
+            )
+        });
+
+        Ok(quote! {
+            #[doc = "Marker types of the members in the errors about them not being set."]
+            #vis mod members {
+                #(
+                    #[doc = #docs]
+                    #marker_vis enum #markers {}
+                )*
+            }
+        })
     }
 
     fn builder_decl(&self) -> TokenStream2 {
-        let vis = &self.vis;
+        let state_param = self.state_param();
+        let state_arg = self.state_arg();
+        let vis = self.builder_type_vis();
         let builder_ident = &self.builder_ident;
         let builder_private_impl_ident = &self.builder_private_impl_ident;
-        let builder_state_trait_ident = &self.builder_state_trait_ident;
         let generics_decl = &self.generics.params;
         let where_clause = &self.generics.where_clause;
         let generic_args = self.generic_args();
-        let unset_state_types = self.unset_state_types();
+        let empty_state = self.empty_state();
+        let state_param_with_default = state_param
+            .as_ref()
+            .map(|state_param| quote!(#state_param = #empty_state,));
+
+        // The type state is stored in its own field instead of the phantom data
+        // of the private struct, so that the type of the values of the members
+        // doesn't change when the state does
+        let state_field = state_arg.map(|state_var| {
+            quote! {
+                /// Please don't touch this field. It's an implementation
+                /// detail that is exempt from the API stability guarantees.
+                /// It marks the type state of the builder.
+                __private_state: ::core::marker::PhantomData<#state_var>,
+            }
+        });
         let phantom_data = self.phantom_data();
 
         let receiver_field = self.assoc_method_ctx.as_ref().and_then(|receiver| {
@@ -274,27 +1227,34 @@ impl BuilderGenCtx {
             })
         });
 
-        let members = self.members.iter().map(|member| {
+        let members = self.stored_members().map(|member| {
             let ident = &member.ident;
-            let assoc_type_ident = &member.state_assoc_type_ident;
+
+            if member.is_start_fn() || member.is_field() {
+                let ty = &member.ty;
+                return quote! {
+                    #ident: #ty,
+                };
+            }
+
+            let storage_type = member.storage_type();
             quote! {
-                #ident: __State::#assoc_type_ident,
+                #ident: #storage_type,
             }
         });
 
-        let must_use_message = format!(
-            "the builder does nothing until you call `{}()` on it to finish building",
-            self.finish_func.ident
-        );
+        let must_use_message = self.must_use_message();
 
         quote! {
             #[must_use = #must_use_message]
             #vis struct #builder_ident<
                 #(#generics_decl,)*
-                __State: #builder_state_trait_ident = (#(#unset_state_types,)*),
+                #state_param_with_default
             >
             #where_clause
             {
+                #state_field
+
                 /// Please don't touch this field. It's an implementation
                 /// detail that is exempt from the API stability guarantees.
                 /// It's visible to you only because of the limitations of
@@ -344,21 +1304,24 @@ impl BuilderGenCtx {
                 /// Child modules are kinda implicitly "hoisted" to the top-level of
                 /// the module and they can't see the local symbols defined inside
                 /// of the same function scope.
-                __private_impl: #builder_private_impl_ident<
-                    #(#generic_args,)*
-                    __State
-                >
+                __private_impl: #builder_private_impl_ident<#(#generic_args,)*>,
             }
 
             /// This struct exists only to reduce the number of private fields
             /// that pop up in IDE completions for developers. It groups all
             /// the private fields in it leaving the builder type higher with
             /// just a single field of this type that documents the fact that
-            /// the developers shouldn't touch it.
-            struct #builder_private_impl_ident<
-                #(#generics_decl,)*
-                __State: #builder_state_trait_ident
-            >
+            /// the developers shouldn't touch it, and the type state.
+            ///
+            /// It doesn't depend on the type state, so the setters move it
+            /// into the builder in the next state as a whole after updating
+            /// the value of their member in place.
+            ///
+            /// It's hidden even from the docs generated with the
+            /// `--document-private-items` flag and from the IDE completions,
+            /// because it's not meant to be named by anyone.
+            #[doc(hidden)]
+            struct #builder_private_impl_ident<#(#generics_decl,)*>
             #where_clause
             {
                 _phantom: #phantom_data,
@@ -368,59 +1331,331 @@ impl BuilderGenCtx {
         }
     }
 
-    fn member_expr<'f>(&self, member: &'f Member) -> Result<MemberExpr<'f>> {
+    fn member_enum_decl(&self) -> Option<TokenStream2> {
+        let vis = self.builder_type_vis();
+        let builder_ident = &self.builder_ident;
+        let member_enum_ident = self.builder_member_enum_ident.as_ref()?;
+
+        let variants = self
+            .listed_members()
+            .map(Member::enum_variant_ident)
+            .collect_vec();
+        let names = self
+            .listed_members()
+            .map(|member| member.public_ident().raw_name())
+            .collect_vec();
+
+        let variants_docs = names
+            .iter()
+            .map(|name| format!("Corresponds to the `{name}` member."));
+
+        let docs = format!(
+            "Lists all members of [`{builder_ident}`]. Use it to refer to the \
+            members programmatically, for example in logs or error messages.",
+        );
+
+        Some(quote! {
+            #[doc = #docs]
+            #[derive(
+                ::core::fmt::Debug,
+                ::core::clone::Clone,
+                ::core::marker::Copy,
+                ::core::cmp::PartialEq,
+                ::core::cmp::Eq,
+                ::core::hash::Hash,
+                ::core::cmp::PartialOrd,
+                ::core::cmp::Ord,
+            )]
+            #vis enum #member_enum_ident {
+                #(
+                    #[doc = #variants_docs]
+                    #variants,
+                )*
+            }
+
+            impl #member_enum_ident {
+                /// All members in the order of their declaration.
+                #vis const ALL: &'static [Self] = &[#( Self::#variants, )*];
+
+                /// Returns the name of the member as it appears in the builder API.
+                #vis const fn as_str(&self) -> &'static ::core::primitive::str {
+                    match *self {
+                        #( Self::#variants => #names, )*
+                    }
+                }
+            }
+
+            impl ::core::fmt::Display for #member_enum_ident {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    f.write_str(self.as_str())
+                }
+            }
+        })
+    }
+
+    /// Generates the associated constant with the metadata of all members of
+    /// the builder for introspection.
+    fn members_info_impl(&self) -> TokenStream2 {
+        let state_param = self.state_param();
+        let state_arg = self.state_arg();
+        let bon = &self.bon;
+        let vis = self.builder_type_vis();
+        let builder_ident = &self.builder_ident;
+        let generics_decl = &self.generics.params;
+        let generic_args = self.generic_args();
+        let where_clause_predicates = self
+            .generics
+            .where_clause
+            .as_ref()
+            .into_iter()
+            .flat_map(|where_clause| &where_clause.predicates);
+
+        let members_info = self.listed_members().map(|member| {
+            let name = member.public_ident().raw_name();
+            let type_name = member.ty.to_source_string();
+            let required = if member.is_named() {
+                member.is_required()
+            } else {
+                member.is_start_fn() || member.is_finish_fn()
+            };
+            let has_default = member.params.default.is_some()
+                || member.params.flag.is_present()
+                || member.default_from_instance
+                || member.is_skipped()
+                || member.is_field();
+            let docs = member.doc_text().unwrap_or_default();
+
+            quote! {
+                #bon::private::member_info(#name, #type_name, #required, #has_default, #docs)
+            }
+        });
+
+        quote! {
+            // The lifetimes of the builder aren't referenced in this impl block
+            #[allow(single_use_lifetimes)]
+            impl<
+                #(#generics_decl,)*
+                #state_param
+            >
+            #builder_ident<
+                #(#generic_args,)*
+                #state_arg
+            >
+            where
+                #( #where_clause_predicates, )*
+            {
+                /// Metadata of all members of the builder in the order of their declaration.
+                #vis const MEMBERS: &'static [#bon::MemberInfo] = &[
+                    #( #members_info, )*
+                ];
+            }
+        }
+    }
+
+    /// Expression that moves the value of the member out of the builder. It is
+    /// assigned to a local variable named after the member in the finishing
+    /// function, so default values of the following members can reference it.
+    fn member_var_init(&self, member: &Member) -> Result<TokenStream2> {
+        let bon = &self.bon;
+        let member_ident = &member.ident;
+
+        if member.is_start_fn() || member.is_field() {
+            return Ok(quote!(self.__private_impl.#member_ident));
+        }
+
+        if member.is_finish_fn() {
+            return self.positional_param(member).map(|(_, init)| init);
+        }
+
+        if member.is_flatten() {
+            return Ok(self.flattened_struct_expr(member));
+        }
+
+        if let Some(skip) = &member.params.skip {
+            let ty = &member.ty;
+            return Ok(match skip.as_ref() {
+                Some(expr) => quote!(#expr),
+                None => quote!(<#ty as ::core::default::Default>::default()),
+            });
+        }
+
+        // The instance is created only when the first unset member needs it.
+        // Its fields are moved out into a tuple at once, and each member
+        // takes its own element out of it.
+        let default_from_instance = self
+            .default_from
+            .as_ref()
+            .filter(|_| member.default_from_instance)
+            .and_then(|_| {
+                self.named_members()
+                    .filter(|member| member.default_from_instance)
+                    .position(|other| other.ident == member.ident)
+            })
+            .map(|index| {
+                let index = syn::Index::from(index);
+                quote! {
+                    #bon::private::unwrap_set(
+                        __default.get_or_insert_with(&__default_fields).#index.take()
+                    )
+                }
+            });
+
+        // For `Option` members the value is passed to the function unchanged
+        // unless there is a default instance to take the value from, which is
+        // handled as a fallback for the unset value below
         let maybe_default = member
             .as_optional()
-            // For `Option` members we don't need any `unwrap_or_[else/default]`.
-            // We pass them directly to the function unchanged.
             .filter(|_| !member.ty.is_option())
             .map(|_| {
-                member
-                    .params
-                    .default
-                    .as_ref()
-                    .and_then(|val| val.as_ref().as_ref())
-                    .map(|default| {
-                        let qualified_for_into =
-                            self.member_qualifies_for_into(member, &member.ty)?;
-                        let default = if qualified_for_into {
-                            quote! { std::convert::Into::into((|| #default)()) }
-                        } else {
-                            quote! { #default }
-                        };
-
-                        Result::<_>::Ok(quote! { .unwrap_or_else(|| #default) })
-                    })
-                    .unwrap_or_else(|| Ok(quote! { .unwrap_or_default() }))
+                let explicit_default = member.params.default.as_ref().map(|val| val.as_ref());
+
+                match (explicit_default, &default_from_instance) {
+                    (Some(Some(default)), _) => self
+                        .member_qualifies_for_into(member, &member.ty)
+                        .map(|qualified_for_into| {
+                            if qualified_for_into {
+                                quote! { ::core::convert::Into::into(#default) }
+                            } else {
+                                quote! { #default }
+                            }
+                        }),
+                    // Explicit `#[builder(default)]` takes precedence over the
+                    // default instance of the struct
+                    (Some(None), _) | (None, None) => {
+                        Ok(quote! { ::core::default::Default::default() })
+                    }
+                    (None, Some(default)) => Ok(default.clone()),
+                }
             })
             .transpose()?;
 
+        if member.is_required() && self.runtime_checks.is_some() {
+            let member_name = member.public_ident().raw_name();
+            let builder_name = self.builder_ident.raw_name();
+            return Ok(quote! {
+                #bon::private::expect_set(
+                    self.__private_impl.#member_ident,
+                    #member_name,
+                    #builder_name,
+                )
+            });
+        }
+
+        if member.is_required() {
+            return Ok(quote! {
+                #bon::private::unwrap_set(self.__private_impl.#member_ident)
+            });
+        }
+
+        let mut value = quote!(self.__private_impl.#member_ident);
+
+        // The fallbacks are evaluated in `match` expressions instead of closures,
+        // so that the panics in them are reported at the caller of the finishing
+        // function when it's `#[track_caller]`.
+
+        // The environment variable takes precedence over the default values
+        // The errors of parsing are collected with the errors of the validators
+        let env = member.params.env.as_ref().map(|env| {
+            let env = env.as_str();
+            let member_name = member.public_ident().raw_name();
+            quote!(__member_errors.env_var(#member_name, #env))
+        });
+
+        let option_default = default_from_instance.filter(|_| member.ty.is_option());
+
+        // An `Option` member that was explicitly set to `None` must not be
+        // overridden by the instance. Only the type state can tell that, so
+        // the builders without it take the value from the instance for `None`.
+        let explicitly_set = option_default
+            .as_ref()
+            .filter(|_| !self.has_mut_setters(member))
+            .and_then(|_| self.state_arg())
+            .map(|state_var| {
+                let assoc_type_ident = &member.state_assoc_type_ident;
+                quote!(<#state_var::#assoc_type_ident as #bon::state::MemberState>::IS_SET)
+            });
+
+        for fallback in env.into_iter().chain(option_default) {
+            value = quote! {
+                match #value {
+                    value @ ::core::option::Option::Some(_) => value,
+                    ::core::option::Option::None => #fallback,
+                }
+            };
+        }
+
+        if let Some(explicitly_set) = explicitly_set {
+            value = quote! {
+                if #explicitly_set {
+                    self.__private_impl.#member_ident
+                } else {
+                    #value
+                }
+            };
+        }
+
+        if let Some(default) = maybe_default {
+            value = quote! {
+                match #value {
+                    ::core::option::Option::Some(value) => value,
+                    ::core::option::Option::None => #default,
+                }
+            };
+        }
+
+        Ok(value)
+    }
+
+    /// Expression that passes the local variable of the member to the body
+    /// of the finishing function.
+    fn member_expr<'f>(&self, member: &'f Member) -> MemberExpr<'f> {
         let member_ident = &member.ident;
 
-        let expr = quote! {
-            ::bon::private::IntoSet::into_set(self.__private_impl.#member_ident)
-                .into_inner()
-                #maybe_default
+        // The value was already moved into the struct of the flattened member
+        let expr = if let Some(from) = &member.flattened_from {
+            let flatten_ident = &from.member;
+            let field_ident = &from.field;
+            quote!(#flatten_ident.#field_ident)
+        } else if !member.borrow_on_finish {
+            quote!(#member_ident)
+        } else if member.ty.is_option() {
+            quote!(::core::option::Option::as_deref(&#member_ident))
+        } else {
+            quote!(&*#member_ident)
         };
 
-        Ok(MemberExpr { member, expr })
+        MemberExpr { member, expr }
     }
 
-    fn finish_method_impl(&self) -> Result<TokenStream2> {
-        let member_exprs: Vec<_> = self
-            .members
-            .iter()
-            .map(|member| self.member_expr(member))
-            .try_collect()?;
+    /// Bounds on the `__State` type parameter that require all required members
+    /// and at least one member of every group to be set, i.e. for the finishing
+    /// function to be callable. There are none if the builder has no type state.
+    fn finish_state_where_predicates(&self) -> impl Iterator<Item = TokenStream2> + '_ {
+        let state_var = &self.state_var;
+        self.named_members()
+            .filter(|member| member.is_required() && self.runtime_checks.is_none())
+            .map(move |member| {
+                let member_assoc_type_ident = &member.state_assoc_type_ident;
+                let is_set_trait = self.is_set_trait(member);
+                quote! {
+                    #state_var::#member_assoc_type_ident: #is_set_trait
+                }
+            })
+            .chain(self.group_state_where_predicates())
+    }
 
-        let body = &self.finish_func.body.gen(&member_exprs);
-        let asyncness = &self.finish_func.asyncness;
-        let unsafety = &self.finish_func.unsafety;
-        let vis = &self.vis;
+    /// Generates the `impl` block header for the builder in the state where
+    /// all required members are set and the finishing function is available.
+    fn finish_impl_header(&self, trait_path: Option<&syn::Path>) -> TokenStream2 {
+        self.impl_header(trait_path, trait_path.is_some())
+    }
+
+    /// Generates the `impl` block header for the builder in any state, or in
+    /// the finished state if `finished` is `true`.
+    fn impl_header(&self, trait_path: Option<&syn::Path>, finished: bool) -> TokenStream2 {
+        let state_param = self.state_param();
+        let state_arg = self.state_arg();
         let builder_ident = &self.builder_ident;
-        let builder_state_trait_ident = &self.builder_state_trait_ident;
-        let finish_func_ident = &self.finish_func.ident;
-        let output = &self.finish_func.output;
         let generics_decl = &self.generics.params;
         let generic_builder_args = self.generic_args();
         let where_clause_predicates = self
@@ -430,44 +1665,413 @@ impl BuilderGenCtx {
             .into_iter()
             .flat_map(|where_clause| &where_clause.predicates);
 
-        let state_where_predicates = self.members.iter().map(|member| {
-            let member_assoc_type_ident = &member.state_assoc_type_ident;
-            let set_state_type_param = member.set_state_type_param();
-            quote! {
-                __State::#member_assoc_type_ident:
-                    ::bon::private::IntoSet<#set_state_type_param>
-            }
-        });
+        // The bounds of the inherent finishing function are placed on the function
+        // itself, because the compiler doesn't show the custom diagnostics for
+        // the unsatisfied bounds of the `impl` block when the method is called
+        let state_where_predicates = finished
+            .then(|| self.finish_state_where_predicates())
+            .into_iter()
+            .flatten();
 
-        Ok(quote! {
+        let trait_for = trait_path.map(|trait_path| quote!(#trait_path for));
+
+        quote! {
+            // The state bounds don't mention the member types, so the lifetimes
+            // of the builder may be used only once in this header
+            #[allow(single_use_lifetimes)]
             impl<
                 #(#generics_decl,)*
-                __State: #builder_state_trait_ident
+                #state_param
             >
+            #trait_for
             #builder_ident<
                 #(#generic_builder_args,)*
-                __State
+                #state_arg
             >
             where
                 #( #where_clause_predicates, )*
                 #( #state_where_predicates, )*
+        }
+    }
+
+    /// The `#[track_caller]` attribute for the functions that finish building
+    /// if it was requested with `#[builder(track_caller)]`. It's also added
+    /// automatically to the non-async functions that may panic because of the
+    /// missing members with `#[builder(check = "runtime")]` or that read the
+    /// environment variables, so that the panics point to the caller.
+    fn track_caller_attr(&self) -> Option<TokenStream2> {
+        let is_implied = self.finish_func.asyncness.is_none()
+            && (self.runtime_checks.is_some()
+                || self
+                    .members
+                    .iter()
+                    .any(|member| member.params.env.is_some()));
+
+        (self.track_caller.is_present() || is_implied).then(|| quote!(#[track_caller]))
+    }
+
+    fn finish_method_impl(&self) -> Result<TokenStream2> {
+        if self.track_caller.is_present() && self.finish_func.asyncness.is_some() {
+            bail!(
+                &self.track_caller.span(),
+                "#[track_caller] has no effect on async functions, so it can't be \
+                used if the underlying function is async"
+            );
+        }
+
+        // Members are assigned to local variables in the order of their
+        // declaration, which makes the values of the preceding members
+        // available to the default value expressions of the following ones.
+        let member_vars: Vec<_> = self
+            .members
+            .iter()
+            .map(|member| {
+                let member_ident = &member.ident;
+                let init = self.member_var_init(member)?;
+                let maybe_mut = (self.pre_build.is_some() && member.flattened_from.is_none())
+                    .then(|| quote!(mut));
+                Ok::<_, Error>(quote!(let #maybe_mut #member_ident = #init;))
+            })
+            .try_collect()?;
+
+        let member_exprs: Vec<_> = self
+            .body_members()
+            .map(|member| self.member_expr(member))
+            .collect();
+
+        let mut body = self.finish_func.body.gen(&member_exprs);
+
+        let validate_members = self.has_member_checks().then(|| {
+            let checks = self.members.iter().filter_map(|member| {
+                let func = member.params.validate.as_ref()?;
+                let name = member.public_ident().raw_name();
+                let expr = &self.member_expr(member).expr;
+                let call = quote_spanned!(func.span()=> (#func)(&#expr));
+                Some(quote! {
+                    #[allow(clippy::redundant_closure_call)]
+                    __member_errors.check(#name, #call);
+                })
+            });
+
+            quote! {
+                #( #checks )*
+
+                if let ::core::result::Result::Err(errors) = __member_errors.finish() {
+                    return ::core::result::Result::Err(::core::convert::Into::into(errors));
+                }
+            }
+        });
+
+        let pre_build = self.pre_build.as_ref().map(|pre_build| {
+            let member_idents = self.body_members().map(|member| &member.ident);
+            let call = quote_spanned!(pre_build.span()=> (#pre_build)(#( &mut #member_idents ),*));
+
+            quote! {
+                // The call has the span of the user's code, so the lints
+                // consider the closure called in place to be written by the user
+                #[allow(clippy::redundant_closure_call)]
+                #call;
+            }
+        });
+
+        if let Some(map) = &self.finish_func.map {
+            let call = quote_spanned!(map.span()=> (#map)(#body));
+            body = quote! {{
+                // The call has the span of the user's code, so the lints
+                // consider the closure called in place to be written by the user
+                #[allow(clippy::redundant_closure_call)]
+                let __output = #call;
+                __output
+            }};
+        }
+
+        let validate = self
+            .validator
+            .as_ref()
+            .map(|validator| {
+                let func = &validator.func;
+                let member_refs = member_exprs.iter().map(|member| &member.expr);
+
+                let maybe_await = if validator.is_async {
+                    if self.finish_func.asyncness.is_none() {
+                        bail!(
+                            func,
+                            "async validation requires the finishing function \
+                            to be async, which is the case only if the \
+                            underlying function is async"
+                        );
+                    }
+                    Some(quote!(.await))
+                } else {
+                    None
+                };
+
+                // The call is spanned with the function, so that the type errors
+                // in it point to the function instead of the whole attribute
+                let call = quote_spanned!(func.span()=> (#func)(#( &#member_refs ),*));
+
+                Ok(quote! {
+                    // The call has the span of the user's code, so the lints
+                    // consider the closure called in place to be written by the user
+                    #[allow(clippy::redundant_closure_call)]
+                    let __validation = #call #maybe_await;
+
+                    if let ::core::result::Result::Err(error) = __validation {
+                        return ::core::result::Result::Err(::core::convert::Into::into(error));
+                    }
+                })
+            })
+            .transpose()?;
+
+        if validate.is_some() || validate_members.is_some() {
+            body = quote!(::core::result::Result::Ok(#body));
+        }
+
+        let default_from = self.default_from.as_ref().map(|default_from| {
+            let DefaultFrom {
+                struct_ident,
+                struct_ty,
+                expr,
+            } = default_from;
+
+            let members: Vec<_> = self
+                .named_members()
+                .filter(|member| member.default_from_instance)
+                .collect();
+
+            let fields = members.iter().map(|member| &member.ident);
+            let tys = members.iter().map(|member| &member.ty);
+            let vars: Vec<_> = (0..members.len())
+                .map(|index| quote::format_ident!("__default_{index}"))
+                .collect();
+
+            // The instance is destructured instead of taking the values out
+            // of its fields, so the types of the fields don't need to
+            // implement `Default`
+            quote! {
+                #[allow(unused_mut)]
+                let mut __default = ::core::option::Option::<(
+                    #( ::core::option::Option<#tys>, )*
+                )>::None;
+
+                let __default_fields = || {
+                    let __instance: #struct_ty = #expr;
+                    let #struct_ident { #( #fields: #vars, )* .. } = __instance;
+                    ( #( ::core::option::Option::Some(#vars), )* )
+                };
+            }
+        });
+        let member_errors = self.has_member_checks().then(|| {
+            let bon = &self.bon;
+            quote! {
+                let mut __member_errors = #bon::private::MemberErrors::default();
+            }
+        });
+        let asyncness = &self.finish_func.asyncness;
+        let unsafety = &self.finish_func.unsafety;
+        let safety_docs = &self.finish_func.safety_docs;
+        let attrs = &self.finish_func.attrs;
+        let track_caller = self.track_caller_attr();
+        let vis = self.finish_func_vis();
+        let finish_func_ident = &self.finish_func.ident;
+        let output = self.finish_func_output(&[]);
+        let impl_header = self.finish_impl_header(None);
+        let state_where_predicates = self.finish_state_where_predicates();
+        let (finish_fn_params, _) = self.finish_fn_params()?;
+
+        Ok(quote! {
+            #impl_header
             {
                 /// Finishes building and performs the requested action.
-                #vis #asyncness #unsafety fn #finish_func_ident(self) #output {
+                #( #safety_docs )*
+                #( #attrs )*
+                #track_caller
+                #vis #asyncness #unsafety fn #finish_func_ident(
+                    self,
+                    #( #finish_fn_params, )*
+                ) #output
+                where
+                    #( #state_where_predicates, )*
+                {
+                    #default_from
+                    #member_errors
+                    #( #member_vars )*
+                    #pre_build
+                    #validate_members
+                    #validate
                     #body
                 }
             }
         })
     }
 
-    fn setter_methods_impls(&self) -> Result<TokenStream2> {
-        self.members
+    /// The trait is expected to declare a single method with the same name and
+    /// signature as the finishing function. Only the finishing function is mapped.
+    /// The macro doesn't see the declaration of the trait, so it can't know which
+    /// setters it would contain, and each of them returns a builder with a
+    /// different type state anyway. The dyn-compatible alternative with setters
+    /// is `#[builder(dyn_builder)]`.
+    /// Implements the trait from `#[builder(implements = ...)]` for the builder.
+    /// The methods of the trait are mapped onto the setters and the finishing
+    /// function with the same names. The trait is implemented in any state of
+    /// the builder unless the finishing function is mapped.
+    fn implements_trait_impl(&self) -> Result<Option<TokenStream2>> {
+        let Some(implements) = &self.implements else {
+            return Ok(None);
+        };
+
+        let finish_func_ident = &self.finish_func.ident;
+        let methods = implements
+            .methods
+            .as_deref()
+            .unwrap_or_else(|| std::slice::from_ref(finish_func_ident));
+
+        let (finish_methods, setter_methods): (Vec<_>, Vec<_>) = methods
             .iter()
+            .partition(|method| method.raw_name() == finish_func_ident.raw_name());
+
+        let finish_method = (!finish_methods.is_empty()).then(|| self.implements_finish_method());
+
+        let setter_methods: Vec<_> = setter_methods
+            .into_iter()
+            .map(|method| self.implements_setter_method(method))
+            .try_collect()?;
+
+        let impl_header = self.impl_header(Some(&implements.path), finish_method.is_some());
+
+        Ok(Some(quote! {
+            #impl_header
+            {
+                #finish_method
+                #( #setter_methods )*
+            }
+        }))
+    }
+
+    fn implements_finish_method(&self) -> TokenStream2 {
+        let state_arg = self.state_arg();
+        let asyncness = &self.finish_func.asyncness;
+        let unsafety = &self.finish_func.unsafety;
+        let finish_func_ident = &self.finish_func.ident;
+        let output = self.finish_func_output(&[]);
+        let builder_ident = &self.builder_ident;
+        let generic_builder_args = self.generic_args();
+        let maybe_await = asyncness.is_some().then(|| quote!(.await));
+        let track_caller = self.track_caller_attr();
+
+        // The trait declares the parameters with their exact types, so `Into`
+        // conversions are not applied to them here
+        let finish_fn_params = self.finish_fn_members().map(|member| {
+            let ident = &member.ident;
+            let ty = &member.ty;
+            quote!(#ident: #ty)
+        });
+        let finish_fn_args = self.finish_fn_members().map(|member| &member.ident);
+
+        quote! {
+            #track_caller
+            #asyncness #unsafety fn #finish_func_ident(
+                self,
+                #( #finish_fn_params, )*
+            ) #output {
+                #builder_ident::<#(#generic_builder_args,)* #state_arg>::#finish_func_ident(
+                    self,
+                    #( #finish_fn_args, )*
+                )
+                #maybe_await
+            }
+        }
+    }
+
+    fn setter_methods_impls(&self) -> Result<TokenStream2> {
+        self.validate_setter_names()?;
+
+        if self.runtime_checks.is_some() {
+            return self.runtime_setter_methods_impl();
+        }
+
+        // The members of sections are set via the setters of the sections
+        self.named_members()
+            .filter(|member| member.params.section.is_none())
             .map(|member| self.setter_methods_impls_for_member(member))
             .collect()
     }
 }
 
+/// Name of the module that contains the type state of the builder. It's
+/// the name of the builder in snake case, e.g. `example_builder`.
+fn builder_mod_ident(builder_ident: &syn::Ident) -> syn::Ident {
+    let ident = builder_ident.to_snake_case();
+
+    // The module can't have the same name as the builder type itself,
+    // which is possible if the builder's name is already in snake case
+    if ident.raw_name() == builder_ident.raw_name() {
+        return quote::format_ident!("{}_state", ident.raw_name());
+    }
+
+    ident
+}
+
+/// Picks the names of the generic parameters for the type state of the builder
+/// and for the closure of the closure function, that don't collide with any of
+/// the identifiers used in the given tokens of the underlying item. Otherwise,
+/// the generic parameters could shadow the user's types or conflict with the
+/// user's generic parameters.
+fn generic_param_idents(item: TokenStream2) -> (syn::Ident, syn::Ident) {
+    fn collect_idents(tokens: TokenStream2, idents: &mut BTreeSet<String>) {
+        for token in tokens {
+            match token {
+                proc_macro2::TokenTree::Ident(ident) => {
+                    idents.insert(ident.to_string());
+                }
+                proc_macro2::TokenTree::Group(group) => collect_idents(group.stream(), idents),
+                _ => {}
+            }
+        }
+    }
+
+    let mut idents = BTreeSet::new();
+    collect_idents(item, &mut idents);
+
+    let unique_ident = |base: &str| {
+        let mut name = base.to_owned();
+        let mut index = 1;
+
+        while idents.contains(&name) {
+            index += 1;
+            name = format!("{base}{index}");
+        }
+
+        syn::Ident::new(&name, proc_macro2::Span::call_site())
+    };
+
+    (unique_ident("__State"), unique_ident("__Configure"))
+}
+
+/// Output type of the function configured with `#[builder(finish_fn(map = ...))]`.
+/// It's taken from the explicit return type of the closure, because the macro
+/// can't infer the output type of an arbitrary function.
+fn map_output(map: &syn::Expr) -> Result<syn::Type> {
+    if let syn::Expr::Closure(closure) = map {
+        if let syn::ReturnType::Type(_, ty) = &closure.output {
+            return Ok(ty.as_ref().clone());
+        }
+    }
+
+    bail!(
+        map,
+        "can't infer the output type of the finishing function from this `map` \
+        function; use a closure with an explicit return type instead, \
+        e.g. `|value| -> Arc<Type> {{ Arc::new(value) }}`",
+    );
+}
+
+/// Name of the state type that marks the given member as set. It's defined
+/// inside of the builder's module.
+fn set_state_ident(member: &Member) -> syn::Ident {
+    quote::format_ident!("Set{}", member.state_assoc_type_ident.raw_name())
+}
+
 pub(crate) fn generic_param_to_arg(param: &syn::GenericParam) -> syn::GenericArgument {
     match param {
         syn::GenericParam::Lifetime(param) => {
@@ -484,6 +2088,84 @@ pub(crate) fn generic_param_to_arg(param: &syn::GenericParam) -> syn::GenericArg
     }
 }
 
+/// Lists the given type parameters in the precise capturing syntax `use<...>`
+/// of the `impl Trait` types. All type parameters in scope are required to be
+/// listed there, so the ones the original function listed are replaced. The
+/// lifetimes are preserved as is, because they decide which borrows the
+/// returned value captures.
+struct PreciseCaptures {
+    type_params: Vec<syn::Ident>,
+}
+
+impl VisitMut for PreciseCaptures {
+    fn visit_item_mut(&mut self, _item: &mut syn::Item) {
+        // Don't recurse into nested items because they have their own generics.
+    }
+
+    fn visit_precise_capture_mut(&mut self, capture: &mut syn::PreciseCapture) {
+        let lifetimes = std::mem::take(&mut capture.params)
+            .into_iter()
+            .filter(|param| matches!(param, syn::CapturedParam::Lifetime(_)));
+
+        capture.params = lifetimes
+            .chain(
+                self.type_params
+                    .iter()
+                    .cloned()
+                    .map(syn::CapturedParam::Ident),
+            )
+            .collect();
+    }
+}
+
+/// The outlives predicates that are implied by the references in the given
+/// type, e.g. `T: 'a` for `&'a T`. The functions get them implicitly from
+/// the types of their parameters, but the closure function doesn't have
+/// parameters of the members' types, so it has to state them explicitly.
+fn implied_outlives_predicates(ty: &syn::Type) -> Vec<TokenStream2> {
+    struct Visitor {
+        predicates: Vec<TokenStream2>,
+    }
+
+    impl syn::visit::Visit<'_> for Visitor {
+        fn visit_item(&mut self, _item: &syn::Item) {
+            // Don't recurse into nested items because they have their own generics.
+        }
+
+        fn visit_type_reference(&mut self, reference: &syn::TypeReference) {
+            if let Some(lifetime) = &reference.lifetime {
+                let elem = &reference.elem;
+                self.predicates.push(quote!(#elem: #lifetime));
+            }
+            syn::visit::visit_type_reference(self, reference);
+        }
+    }
+
+    let mut visitor = Visitor { predicates: vec![] };
+    syn::visit::Visit::visit_type(&mut visitor, ty);
+    visitor.predicates
+}
+
+fn has_precise_captures(output: &syn::ReturnType) -> bool {
+    struct Visitor {
+        found: bool,
+    }
+
+    impl syn::visit::Visit<'_> for Visitor {
+        fn visit_item(&mut self, _item: &syn::Item) {
+            // Don't recurse into nested items because they have their own generics.
+        }
+
+        fn visit_precise_capture(&mut self, _capture: &syn::PreciseCapture) {
+            self.found = true;
+        }
+    }
+
+    let mut visitor = Visitor { found: false };
+    syn::visit::Visit::visit_return_type(&mut visitor, output);
+    visitor.found
+}
+
 fn reject_self_references_in_docs(docs: &[syn::Attribute]) -> Result {
     for doc in docs {
         let Some(doc) = &doc.as_doc() else { continue };
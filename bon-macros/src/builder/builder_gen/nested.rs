@@ -0,0 +1,55 @@
+use super::BuilderGenCtx;
+use crate::util::prelude::*;
+use quote::quote;
+
+impl BuilderGenCtx {
+    /// Generates the implementations of the traits from `bon::nested` that
+    /// let the `#[builder(nested)]` setters of other builders create this
+    /// builder and finish it. They're generated only if neither the starting
+    /// nor the finishing function has parameters.
+    pub(crate) fn nested_traits_impl(&self) -> Option<TokenStream2> {
+        let has_builder_impl = self.has_builder_impl.as_ref()?;
+
+        if self.finish_fn_members().next().is_some() {
+            return None;
+        }
+
+        let bon = &self.bon;
+        let struct_ty = &has_builder_impl.struct_ty;
+        let start_func_on = &has_builder_impl.start_func_on;
+        let start_func_ident = &self.start_func.ident;
+        let finish_func_ident = &self.finish_func.ident;
+        let builder_ident = &self.builder_ident;
+        let generics_decl = &self.generics.params;
+        let where_clause = &self.generics.where_clause;
+        let generic_args = self.generic_args().collect::<Vec<_>>();
+        let state_arg = self.state_arg();
+        let output = match &self.finish_func.output {
+            syn::ReturnType::Default => quote!(()),
+            syn::ReturnType::Type(_, ty) => quote!(#ty),
+        };
+        let finish_impl_header =
+            self.finish_impl_header(Some(&syn::parse_quote!(#bon::nested::FinishBuilder)));
+
+        Some(quote! {
+            impl<#(#generics_decl,)*> #bon::nested::HasBuilder for #struct_ty
+            #where_clause
+            {
+                type Builder = #builder_ident<#(#generic_args,)*>;
+
+                fn builder() -> Self::Builder {
+                    <#start_func_on>::#start_func_ident()
+                }
+            }
+
+            #finish_impl_header
+            {
+                type Output = #output;
+
+                fn finish_builder(self) -> Self::Output {
+                    #builder_ident::<#(#generic_args,)* #state_arg>::#finish_func_ident(self)
+                }
+            }
+        })
+    }
+}
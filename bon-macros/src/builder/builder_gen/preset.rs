@@ -0,0 +1,95 @@
+use super::BuilderGenCtx;
+use crate::util::prelude::*;
+use quote::quote;
+
+impl BuilderGenCtx {
+    /// Generates the start functions of the struct for the presets. They call
+    /// the regular starting function and set the members of the preset to the
+    /// given values, which are converted with `Into`.
+    pub(crate) fn preset_funcs_impl(&self) -> Result<Option<TokenStream2>> {
+        let Some(funcs) = &self.preset_funcs else {
+            return Ok(None);
+        };
+
+        let vis = self.start_func.vis.as_ref().unwrap_or(&self.vis);
+        let struct_ty = &funcs.struct_ty;
+        let start_func_on = &funcs.start_func_on;
+        let start_func_ident = &self.start_func.ident;
+        let builder_ident = &self.builder_ident;
+        let generics_decl = &self.generics.params;
+        let generic_args = self.generic_args().collect::<Vec<_>>();
+        let where_clause = &self.generics.where_clause;
+        let (start_fn_params, _) = self.start_fn_params()?;
+        let start_fn_args = self
+            .start_fn_members()
+            .map(|member| &member.ident)
+            .collect::<Vec<_>>();
+        let must_use_message = self.must_use_message();
+
+        let mut preset_funcs = vec![];
+
+        for preset in &funcs.presets {
+            let preset_ident = &preset.name;
+
+            let members = preset
+                .members
+                .iter()
+                .map(|(ident, value)| Ok((self.referenced_member(ident)?, value)))
+                .collect::<Result<Vec<_>>>()?;
+
+            let state = members
+                .iter()
+                .fold(self.empty_state(), |state, (member, _)| {
+                    let set_state = self.set_state(member);
+                    quote!(#set_state<#state>)
+                });
+
+            let members_init = members.iter().map(|(member, value)| {
+                let ident = &member.ident;
+                let value = quote!(::core::convert::Into::into(#value));
+                let value = if member.is_stored_as_is() {
+                    value
+                } else {
+                    quote!(::core::option::Option::Some(#value))
+                };
+                quote!(builder.__private_impl.#ident = #value;)
+            });
+
+            let builder = self.builder_expr(quote!(builder.__private_impl));
+
+            let member_names = members
+                .iter()
+                .map(|(member, _)| format!("`{}`", member.public_ident().raw_name()))
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            let docs = format!(
+                "Creates the builder with the preset `{}`, where the members \
+                {member_names} are already set. It's the same as calling \
+                [`{start_func_ident}`](Self::{start_func_ident}) and then the \
+                setters of these members.",
+                preset_ident.raw_name(),
+            );
+
+            preset_funcs.push(quote! {
+                #[doc = #docs]
+                #[must_use = #must_use_message]
+                #vis fn #preset_ident(
+                    #( #start_fn_params, )*
+                ) -> #builder_ident<#(#generic_args,)* #state> {
+                    let mut builder = <#start_func_on>::#start_func_ident(#( #start_fn_args, )*);
+                    #( #members_init )*
+                    #builder
+                }
+            });
+        }
+
+        Ok(Some(quote! {
+            impl<#(#generics_decl,)*> #struct_ty
+            #where_clause
+            {
+                #( #preset_funcs )*
+            }
+        }))
+    }
+}
@@ -0,0 +1,97 @@
+use super::BuilderGenCtx;
+use crate::util::prelude::*;
+use itertools::Itertools;
+use proc_macro2::Span;
+use quote::quote;
+
+impl BuilderGenCtx {
+    /// Rejects the features that rely on the type state of the builder if the
+    /// builder doesn't have it because of `#[builder(check = "runtime")]`.
+    pub(crate) fn validate_runtime_checks(&self) -> Result {
+        let Some(check_span) = self.runtime_checks else {
+            return Ok(());
+        };
+
+        let into_builder = self.into_builder_funcs.as_ref().map(|funcs| {
+            if funcs.into_builder {
+                "into_builder"
+            } else {
+                "to_builder"
+            }
+        });
+
+        let builder_features = [
+            self.closure_func.as_ref().map(|_| "closure_fn"),
+            self.dynamic.is_present().then_some("dynamic"),
+            self.merge.is_present().then_some("merge"),
+            into_builder,
+            self.apply_to.as_ref().map(|_| "apply_to"),
+            self.preset_funcs.as_ref().map(|_| "preset"),
+            self.derives
+                .deserialize
+                .is_present()
+                .then_some("derive(Deserialize)"),
+            self.derives.args.is_present().then_some("derive(Args)"),
+        ];
+
+        if let Some(feature) = builder_features.into_iter().flatten().next() {
+            bail!(&check_span, "{}", incompatible_feature_message(feature));
+        }
+
+        for member in self.named_members() {
+            let params = &member.params;
+            let member_features: [Option<(Span, &str)>; 4] = [
+                params
+                    .requires
+                    .as_ref()
+                    .map(|requires| (requires.span(), "requires")),
+                params
+                    .conflicts_with
+                    .as_ref()
+                    .map(|conflicts_with| (conflicts_with.span(), "conflicts_with")),
+                params.group.as_ref().map(|group| (group.span(), "group")),
+                params
+                    .section
+                    .as_ref()
+                    .map(|section| (section.span(), "section")),
+            ];
+
+            if let Some((span, feature)) = member_features.into_iter().flatten().next() {
+                bail!(&span, "{}", incompatible_feature_message(feature));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Generates the setters of all members of the builder that has no type
+    /// state. They're available at any time, and the setters that consume
+    /// the builder return it with the member updated in place.
+    pub(crate) fn runtime_setter_methods_impl(&self) -> Result<TokenStream2> {
+        let builder_ident = &self.builder_ident;
+        let generics_decl = &self.generics.params;
+        let where_clause = &self.generics.where_clause;
+        let generic_args = self.generic_args();
+
+        let setter_methods: Vec<_> = self
+            .named_members()
+            .map(|member| self.stateless_setter_methods_for_member(member))
+            .try_collect()?;
+
+        Ok(quote! {
+            impl<#(#generics_decl,)*> #builder_ident<#(#generic_args,)*>
+            #where_clause
+            {
+                #( #setter_methods )*
+            }
+        })
+    }
+}
+
+fn incompatible_feature_message(feature: &str) -> String {
+    format!(
+        "#[builder({feature})] can't be used together with #[builder(check = \"runtime\")], \
+        because it relies on the type state of the builder, which isn't generated \
+        when the members are checked at runtime"
+    )
+}
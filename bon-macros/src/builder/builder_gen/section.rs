@@ -0,0 +1,213 @@
+use super::{BuilderGenCtx, Member};
+use crate::util::prelude::*;
+use itertools::Itertools;
+use quote::{format_ident, quote};
+
+/// Members that share the same `#[builder(section = ...)]`. They are set via
+/// a nested builder, which is passed to the closure of the section's setter.
+pub(crate) struct MemberSection<'a> {
+    pub(crate) ident: &'a syn::Ident,
+    pub(crate) members: Vec<&'a Member>,
+}
+
+impl BuilderGenCtx {
+    /// Sections of members in the order of the first member of each section
+    pub(crate) fn member_sections(&self) -> Vec<MemberSection<'_>> {
+        let mut sections: Vec<MemberSection<'_>> = vec![];
+
+        for member in self.named_members() {
+            let Some(ident) = &member.params.section else {
+                continue;
+            };
+
+            match sections.iter_mut().find(|section| section.ident == ident) {
+                Some(section) => section.members.push(member),
+                None => sections.push(MemberSection {
+                    ident,
+                    members: vec![member],
+                }),
+            }
+        }
+
+        sections
+    }
+
+    /// Name of the nested builder of the section. It's the name of the builder
+    /// with the name of the section inserted before the `Builder` suffix, e.g.
+    /// `ConfigHttpBuilder` for the `http` section of `ConfigBuilder`.
+    fn section_builder_ident(&self, section: &MemberSection<'_>) -> syn::Ident {
+        let builder_name = self.builder_ident.raw_name();
+        let prefix = builder_name
+            .strip_suffix("Builder")
+            .unwrap_or(&builder_name);
+
+        format_ident!(
+            "{prefix}{}Builder",
+            section.ident.to_pascal_case().raw_name()
+        )
+    }
+
+    /// Generates the nested builders of the sections and the setters of the
+    /// sections on the builder.
+    pub(crate) fn sections_impl(&self) -> Result<Option<TokenStream2>> {
+        let sections = self.member_sections();
+
+        if sections.is_empty() {
+            return Ok(None);
+        }
+
+        if self.mut_setters.is_present() {
+            bail!(
+                sections[0].ident,
+                "#[builder(section)] can't be used together with #[builder(mut_setters)], \
+                because the setters of the optional members already take `&mut self`",
+            );
+        }
+
+        let sections: Vec<_> = sections
+            .iter()
+            .map(|section| self.section_impl(section))
+            .try_collect()?;
+
+        Ok(Some(quote!(#( #sections )*)))
+    }
+
+    fn section_impl(&self, section: &MemberSection<'_>) -> Result<TokenStream2> {
+        let state_var = &self.state_var;
+        let bon = &self.bon;
+        let vis = self.builder_type_vis();
+        let builder_ident = &self.builder_ident;
+        let builder_state_trait = self.builder_state_trait();
+        let section_builder_ident = self.section_builder_ident(section);
+        let section_private_impl_ident =
+            format_ident!("__{}PrivateImpl", section_builder_ident.raw_name());
+        let section_ident = &section.ident;
+        let generics_decl = &self.generics.params;
+        let where_clause = &self.generics.where_clause;
+        let generic_args = self.generic_args().collect::<Vec<_>>();
+        let where_clause_predicates = self
+            .generics
+            .where_clause
+            .as_ref()
+            .into_iter()
+            .flat_map(|where_clause| &where_clause.predicates);
+        let phantom_types = self.phantom_types();
+
+        let section_member_idents = section
+            .members
+            .iter()
+            .map(|member| &member.ident)
+            .collect::<Vec<_>>();
+
+        let section_fields = section.members.iter().map(|member| {
+            let ident = &member.ident;
+            let storage_type = member.storage_type();
+            quote!(#ident: #storage_type)
+        });
+
+        let section_setters: Vec<_> = section
+            .members
+            .iter()
+            .map(|member| self.stateless_setter_methods_for_member(member))
+            .try_collect()?;
+
+        // All members of the section are marked as set by the section's setter,
+        // even if the closure didn't set some of them, because the members
+        // are optional
+        let output_state = section
+            .members
+            .iter()
+            .fold(quote!(#state_var), |state, member| {
+                let set_state = self.set_state(member);
+                quote!(#set_state<#state>)
+            });
+
+        let state_where_predicates = section.members.iter().map(|member| {
+            let assoc_type_ident = &member.state_assoc_type_ident;
+            quote!(#state_var::#assoc_type_ident: #bon::state::IsUnset)
+        });
+
+        let builder = self.builder_expr(quote!(self.__private_impl));
+
+        let member_names = section
+            .members
+            .iter()
+            .map(|member| format!("`{}`", member.public_ident().raw_name()))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let section_builder_docs = format!(
+            "Nested builder of the members {member_names} of [`{builder_ident}`]. \
+            It's passed to the closure of [`{builder_ident}::{section_ident}()`]. \
+            The members can be set in any order and repeatedly.",
+        );
+
+        let section_setter_docs = format!(
+            "Configures the members {member_names} with the nested builder passed to \
+            the closure. All of them are marked as set afterwards, and the ones that \
+            weren't set in the closure keep their default values.",
+        );
+
+        let must_use_message = self.must_use_message();
+
+        Ok(quote! {
+            #[doc = #section_builder_docs]
+            #vis struct #section_builder_ident<#(#generics_decl,)*>
+            #where_clause
+            {
+                /// Please don't touch this field. It's an implementation
+                /// detail that is exempt from the API stability guarantees.
+                __private_impl: #section_private_impl_ident<#(#generic_args,)*>,
+            }
+
+            #[doc(hidden)]
+            struct #section_private_impl_ident<#(#generics_decl,)*>
+            #where_clause
+            {
+                _phantom: ::core::marker::PhantomData<(#(#phantom_types,)*)>,
+                #( #section_fields, )*
+            }
+
+            impl<#(#generics_decl,)*> #section_builder_ident<#(#generic_args,)*>
+            #where_clause
+            {
+                #( #section_setters )*
+            }
+
+            // The state bounds don't mention the member types, so the lifetimes
+            // of the builder may be used only once in this header
+            #[allow(single_use_lifetimes)]
+            impl<
+                #(#generics_decl,)*
+                #state_var: #builder_state_trait
+            >
+            #builder_ident<
+                #(#generic_args,)*
+                #state_var
+            >
+            where
+                #( #where_clause_predicates, )*
+                #( #state_where_predicates, )*
+            {
+                #[doc = #section_setter_docs]
+                #[must_use = #must_use_message]
+                #vis fn #section_ident(
+                    mut self,
+                    configure: impl ::core::ops::FnOnce(
+                        #section_builder_ident<#(#generic_args,)*>
+                    ) -> #section_builder_ident<#(#generic_args,)*>,
+                ) -> #builder_ident<#(#generic_args,)* #output_state> {
+                    let section = configure(#section_builder_ident {
+                        __private_impl: #section_private_impl_ident {
+                            _phantom: ::core::marker::PhantomData,
+                            #( #section_member_idents: ::core::option::Option::None, )*
+                        }
+                    });
+
+                    #( self.__private_impl.#section_member_idents = section.__private_impl.#section_member_idents; )*
+                    #builder
+                }
+            }
+        })
+    }
+}
@@ -1,111 +1,344 @@
 use super::{member::Member, BuilderGenCtx};
-use crate::builder::builder_gen::AssocMethodCtx;
+use crate::builder::params::{MaybeSetters, OnParams};
 use crate::util::prelude::*;
 use darling::ast::GenericParamExt;
 use itertools::Itertools;
-use quote::{quote, ToTokens};
-use std::collections::BTreeSet;
+use quote::quote;
+use std::collections::{BTreeMap, BTreeSet};
 
 impl BuilderGenCtx {
     pub(crate) fn setter_methods_impls_for_member(&self, member: &Member) -> Result<TokenStream2> {
-        let output_members_states = self.members.iter().map(|other_member| {
-            if other_member.ident == member.ident {
-                return member.set_state_type().to_token_stream();
-            }
-
-            let state_assoc_type_ident = &other_member.state_assoc_type_ident;
-            quote!(__State::#state_assoc_type_ident)
-        });
-
+        let state_var = &self.state_var;
+        let bon = &self.bon;
         let state_assoc_type_ident = &member.state_assoc_type_ident;
         let builder_ident = &self.builder_ident;
-        let builder_state_trait_ident = &self.builder_state_trait_ident;
+        let builder_state_trait = self.builder_state_trait();
         let generics_decl = &self.generics.params;
         let generic_args = self.generic_args().collect_vec();
-        let where_clause = &self.generics.where_clause;
-        let unset_state_type = member.unset_state_type();
-        let output_builder_alias_ident = quote::format_ident!(
-            "__{}Set{}",
-            builder_ident.raw_name(),
-            state_assoc_type_ident.raw_name()
-        );
+        let set_state = self.set_state(member);
+        let overwritable = member.params.overwritable.is_present();
+        let mut_setters = self.has_mut_setters(member);
 
-        // A case where there is just one member is special, because the type alias would
-        // receive a generic `__State` parameter that it wouldn't use, so we create it
-        // only if there are 2 or more members.
-        let (output_builder_alias_state_var_decl, output_builder_alias_state_arg) =
-            (self.members.len() > 1)
-                .then(|| (quote!(__State: #builder_state_trait_ident), quote!(__State)))
-                .unzip();
+        // All setters of the member, including the collection ones, are available
+        // only in the states where the members it depends on are set, and the
+        // members it conflicts with are unset
+        let requires_predicates = self.required_by(member)?.into_iter().map(|required| {
+            let assoc_type_ident = &required.state_assoc_type_ident;
+            let is_set_trait = self.is_set_trait(required);
+            quote!(#state_var::#assoc_type_ident: #is_set_trait)
+        });
 
-        let setter_methods = MemberSettersCtx::new(
-            self,
-            member,
-            quote! {
-                #output_builder_alias_ident<
-                    #(#generic_args,)*
-                    #output_builder_alias_state_arg
+        let conflicts_predicates = self
+            .conflicting_with(member)?
+            .into_iter()
+            .map(|conflicting| {
+                let assoc_type_ident = &conflicting.state_assoc_type_ident;
+                quote!(#state_var::#assoc_type_ident: #bon::state::IsUnset)
+            });
+
+        let dependency_predicates = requires_predicates
+            .chain(conflicts_predicates)
+            .collect_vec();
+
+        // Setting an overwritable member that is already set keeps the state
+        // of the builder the same, so the setter can be called in a loop
+        let overwrite_state = quote! {
+            <
+                #state_var::#state_assoc_type_ident as #bon::private::OverwriteState<
+                    #state_var,
+                    #set_state<#state_var>,
                 >
-            },
-        )
-        .setter_methods()?;
+            >::Output
+        };
+        let overwrite_state_predicates = quote! {
+            #state_var::#state_assoc_type_ident: #bon::private::OverwriteState<
+                #state_var,
+                #set_state<#state_var>,
+            >,
+            #overwrite_state: #builder_state_trait,
+        };
 
-        let vis = &self.vis;
+        let output_state = if overwritable {
+            overwrite_state.clone()
+        } else {
+            quote!(#set_state<#state_var>)
+        };
 
-        Ok(quote! {
-            // This lint is ignored, because bounds in type aliases are still useful
-            // to make the following example usage compile:
-            // ```
-            // type Bar<T: IntoIterator> = T::Item;
-            // ```
-            // In this case the bound is necessary for `T::Item` access to be valid.
-            // The compiler proposes this:
-            //
-            // > use fully disambiguated paths (i.e., `<T as Trait>::Assoc`) to refer
-            // > to associated types in type aliases
-            //
-            // But, come on... Why would you want to write `T::Item` as `<T as IntoIterator>::Item`
-            // especially if that `T::Item` access is used in multiple places? It's a waste of time
-            // to implement logic that rewrites the user's type expressions to that syntax when just
-            // having bounds on the type alias is enough already.
-            #[allow(type_alias_bounds)]
-            // This is `doc(hidden)` with the same visibility as the setter to reduce the noise in
-            // the docs generated by `rustdoc`. Rustdoc auto-inlines type aliases if they aren't exposed
-            // as part of the public API of the crate. This is a workaround to prevent that.
-            #[doc(hidden)]
-            #vis type #output_builder_alias_ident<
-                #(#generics_decl,)*
-                #output_builder_alias_state_var_decl
-            >
-            // The where clause in this position will be deprecated. The preferred
-            // position will be at the end of the entire type alias syntax construct.
-            // See details at https://github.com/rust-lang/rust/issues/112792.
-            //
-            // However, at the time of this writing the only way to put the where
-            // clause on a type alias is here.
-            #where_clause
-            = #builder_ident<
-                #(#generic_args,)*
-                ( #(#output_members_states,)* )
-            >;
+        let builder_type = |state: &TokenStream2| {
+            if mut_setters {
+                quote!(&mut Self)
+            } else {
+                quote! {
+                    #builder_ident<
+                        #(#generic_args,)*
+                        #state
+                    >
+                }
+            }
+        };
+
+        let return_type = builder_type(&output_state);
+
+        let setters_ctx = MemberSettersCtx::new(self, member, return_type.clone());
+
+        let setter_methods = setters_ctx.setter_methods()?;
 
+        // The setters under the alias set the same member. The collection
+        // setters aren't aliased, because they may have custom names.
+        let alias_setter_methods = member
+            .params
+            .alias
+            .as_ref()
+            .map(|alias| {
+                let mut alias_ctx = MemberSettersCtx::new(self, member, return_type);
+                alias_ctx.alias = Some(alias);
+                alias_ctx.setter_methods()
+            })
+            .transpose()?;
+
+        let where_clause_predicates = self
+            .generics
+            .where_clause
+            .as_ref()
+            .into_iter()
+            .flat_map(|where_clause| &where_clause.predicates)
+            .collect_vec();
+
+        // The setters that accumulate the value of a collection member are
+        // available in any state of the member. They keep the state the same
+        // once the member is set, so they can be called in a loop.
+        let collection_state_predicates = (!mut_setters).then_some(&overwrite_state_predicates);
+        let collection_setters_ctx =
+            MemberSettersCtx::new(self, member, builder_type(&overwrite_state));
+        let collection_setters_impl =
+            collection_setters_ctx
+                .collection_setter_methods()?
+                .map(|methods| {
+                    quote! {
+                        impl<
+                            #(#generics_decl,)*
+                            #state_var: #builder_state_trait
+                        >
+                        #builder_ident<
+                            #(#generic_args,)*
+                            #state_var
+                        >
+                        where
+                            #( #where_clause_predicates, )*
+                            #( #dependency_predicates, )*
+                            #collection_state_predicates
+                        {
+                            #methods
+                        }
+                    }
+                });
+
+        // Setters that take `&mut self` don't change the type state, and setters
+        // of overwritable members can be called in any state of the member
+        let state_where_predicates = if mut_setters {
+            quote!()
+        } else if overwritable {
+            overwrite_state_predicates
+        } else {
+            quote! {
+                #state_var::#state_assoc_type_ident: #bon::state::IsUnset,
+            }
+        };
+
+        Ok(quote! {
             impl<
                 #(#generics_decl,)*
-                __State: #builder_state_trait_ident<
-                    #state_assoc_type_ident = #unset_state_type
-                >
+                #state_var: #builder_state_trait
             >
             #builder_ident<
                 #(#generic_args,)*
-                __State
+                #state_var
             >
-            #where_clause
+            where
+                #( #where_clause_predicates, )*
+                #( #dependency_predicates, )*
+                #state_where_predicates
             {
                 #setter_methods
+                #alias_setter_methods
             }
+
+            #collection_setters_impl
         })
     }
 
+    /// Generates the setters of the member for a builder that doesn't have a
+    /// type state, i.e. the nested builder of the member's section or the
+    /// builder with `#[builder(check = "runtime")]`. They can be called in
+    /// any order and repeatedly.
+    pub(crate) fn stateless_setter_methods_for_member(
+        &self,
+        member: &Member,
+    ) -> Result<TokenStream2> {
+        let return_type = if self.has_mut_setters(member) {
+            quote!(&mut Self)
+        } else {
+            quote!(Self)
+        };
+
+        let mut setters_ctx = MemberSettersCtx::new(self, member, return_type.clone());
+        setters_ctx.stateless = true;
+
+        let setter_methods = setters_ctx.setter_methods()?;
+        let collection_setter_methods = setters_ctx.collection_setter_methods()?;
+
+        let alias_setter_methods = member
+            .params
+            .alias
+            .as_ref()
+            .map(|alias| {
+                let mut alias_ctx = MemberSettersCtx::new(self, member, return_type);
+                alias_ctx.alias = Some(alias);
+                alias_ctx.stateless = true;
+                alias_ctx.setter_methods()
+            })
+            .transpose()?;
+
+        Ok(quote! {
+            #setter_methods
+            #alias_setter_methods
+            #collection_setter_methods
+        })
+    }
+
+    /// Generates the method of the trait from `#[builder(implements(...))]` that
+    /// is mapped onto the setter with the given name. Only the setters that take
+    /// `&mut self` can be mapped, because the other ones change the type of the
+    /// builder. The trait declares the parameter with the exact type of the
+    /// member, and the method returns nothing, so that the trait stays
+    /// dyn-compatible.
+    pub(crate) fn implements_setter_method(&self, name: &syn::Ident) -> Result<TokenStream2> {
+        let setter_name = name.raw_name();
+
+        let found = self
+            .named_members()
+            .filter(|member| member.params.section.is_none())
+            .find_map(|member| {
+                let setters_ctx = MemberSettersCtx::new(self, member, TokenStream2::new());
+
+                if setters_ctx.setter_method_name("").raw_name() == setter_name {
+                    return Some((member, false));
+                }
+
+                setters_ctx
+                    .maybe_setter_method_name()
+                    .filter(|maybe| maybe.raw_name() == setter_name)
+                    .map(|_| (member, true))
+            });
+
+        let Some((member, is_maybe)) = found else {
+            bail!(
+                name,
+                "the builder has no setter or finishing function named `{setter_name}`",
+            );
+        };
+
+        if !self.has_mut_setters(member) {
+            bail!(
+                name,
+                "the setter `{setter_name}` changes the type state of the builder, so it \
+                can't be mapped onto a trait method; only the setters of the optional \
+                members with #[builder(mut_setters)] can be mapped",
+            );
+        }
+
+        if member.params.with.is_some() || member.params.flag.is_present() {
+            bail!(
+                name,
+                "the setter `{setter_name}` has a custom signature defined by \
+                #[builder(with)] or #[builder(flag)], so it can't be mapped onto \
+                a trait method",
+            );
+        }
+
+        let ident = &member.ident;
+        let inner_type = member.inner_type();
+
+        let (value_type, value) = if is_maybe {
+            (quote!(::core::option::Option<#inner_type>), quote!(value))
+        } else {
+            (
+                quote!(#inner_type),
+                quote!(::core::option::Option::Some(value)),
+            )
+        };
+
+        Ok(quote! {
+            fn #name(&mut self, value: #value_type) {
+                self.__private_impl.#ident = #value;
+            }
+        })
+    }
+
+    /// Checks that the setters of different members don't have the same names.
+    /// Otherwise, the compiler reports duplicate definitions of the methods in
+    /// the generated code, which doesn't tell what members caused them.
+    pub(crate) fn validate_setter_names(&self) -> Result {
+        let finish_func_name = self.finish_func.ident.raw_name();
+        let mut setters = BTreeMap::<String, &Member>::new();
+
+        // The setters of the members of sections are defined on the nested
+        // builders, so they can't collide with the setters of the builder
+        let members = self
+            .named_members()
+            .filter(|member| member.params.section.is_none());
+
+        for member in members {
+            let setters_ctx = MemberSettersCtx::new(self, member, TokenStream2::new());
+
+            let alias_setter_names = member.params.alias.as_ref().map(|alias| {
+                let mut alias_ctx = MemberSettersCtx::new(self, member, TokenStream2::new());
+                alias_ctx.alias = Some(alias);
+                alias_ctx.setter_method_names()
+            });
+
+            let names = setters_ctx
+                .setter_method_names()
+                .into_iter()
+                .chain(alias_setter_names.into_iter().flatten())
+                .chain(setters_ctx.collection_setter_method_names());
+
+            let member_name = member.ident.raw_name();
+
+            for name in names {
+                let setter_name = name.raw_name();
+
+                if setter_name == finish_func_name {
+                    bail!(
+                        &name,
+                        "the setter `{setter_name}` of the member `{member_name}` \
+                        has the same name as the finishing function; rename the \
+                        member with #[builder(name = ...)] or the finishing function \
+                        with #[builder(finish_fn = ...)]",
+                    );
+                }
+
+                let other = *setters.entry(setter_name.clone()).or_insert(member);
+
+                // The collisions between the setters of the same member
+                // are reported when the member's attributes are validated
+                if other.ident == member.ident {
+                    continue;
+                }
+
+                bail!(
+                    &name,
+                    "the setter `{setter_name}` of the member `{member_name}` has \
+                    the same name as a setter of the member `{}`; rename one of \
+                    the members with #[builder(name = ...)]",
+                    other.ident.raw_name(),
+                );
+            }
+        }
+
+        Ok(())
+    }
+
     // XXX: this behavior is heavily documented in `into-conversions.md`. Please
     // keep the docs and the implementation in sync.
     pub(crate) fn member_qualifies_for_into(
@@ -113,13 +346,38 @@ impl BuilderGenCtx {
         member: &Member,
         ty: &syn::Type,
     ) -> Result<bool> {
+        let on_into = self.on_into(ty);
+
+        let default_value = match on_into {
+            Some((_, on_into)) => on_into,
+            None => self.type_qualifies_for_into(ty),
+        };
+
         // User override takes the wheel entirely
         let Some(user_override) = &member.params.into else {
-            return Ok(self.type_qualifies_for_into(ty));
+            // Owned members replace borrowed types, so their setters need to
+            // accept both owned and borrowed values to remain compatible
+            return Ok(default_value || member.borrow_on_finish);
         };
 
         let override_value = user_override.as_ref().value;
-        let default_value = self.type_qualifies_for_into(ty);
+
+        if let Some((on, on_into)) = on_into.filter(|(_, on_into)| *on_into == override_value) {
+            let type_pattern = &on.type_pattern;
+            let (enabled, into) = if on_into {
+                ("enabled", "into")
+            } else {
+                ("disabled", "into = false")
+            };
+
+            bail!(
+                &user_override.span(),
+                "This attribute is redundant and can be removed. `Into` is already \
+                {enabled} for this {} by `#[builder(on({}, {into}))]`.",
+                member.origin,
+                quote!(#type_pattern),
+            );
+        }
 
         if default_value != override_value {
             // Override makes sense since it changes the default behavior
@@ -141,6 +399,26 @@ impl BuilderGenCtx {
         );
     }
 
+    /// Same as [`Self::member_qualifies_for_into`], but for the items of the
+    /// collection members. The `#[builder(into)]` override of the member
+    /// applies to the type of the member itself, so it's not considered here.
+    fn item_qualifies_for_into(&self, ty: &syn::Type) -> bool {
+        match self.on_into(ty) {
+            Some((_, on_into)) => on_into,
+            None => self.type_qualifies_for_into(ty),
+        }
+    }
+
+    /// Finds the first `#[builder(on(...))]` that matches the type and
+    /// configures the `Into` conversion for it. Returns it along with the
+    /// value that enables or disables the conversion.
+    fn on_into(&self, ty: &syn::Type) -> Option<(&OnParams, bool)> {
+        self.on_params.iter().find_map(|on| {
+            let into = on.into.as_ref()?;
+            ty.matches(&on.type_pattern).then(|| (on, into.value))
+        })
+    }
+
     fn type_qualifies_for_into(&self, ty: &syn::Type) -> bool {
         // Only simple type paths qualify for `impl Into`
         let Some(path) = ty.as_path() else {
@@ -152,6 +430,13 @@ impl BuilderGenCtx {
             return false;
         }
 
+        // `Cow` is an exception from the rule for the types with generic parameters.
+        // Its whole point is to accept both borrowed and owned values, and
+        // `impl Into<Cow<'a, B>>` accepts both `&'a B` and `B::Owned`.
+        if ty.is_final_segment("Cow") {
+            return true;
+        }
+
         // Types with generic parameters don't qualify
         let has_generic_params = path
             .path
@@ -196,112 +481,687 @@ struct MemberSettersCtx<'a> {
     builder_gen: &'a BuilderGenCtx,
     member: &'a Member,
     return_type: TokenStream2,
-    norm_member_ident: syn::Ident,
+
+    /// If present, the setters are generated under this name instead of
+    /// the public name of the member
+    alias: Option<&'a syn::Ident>,
+
+    /// If `true`, the setters are generated for a builder that doesn't have
+    /// a type state, i.e. the nested builder of the member's section or the
+    /// builder with `#[builder(check = "runtime")]`
+    stateless: bool,
 }
 
 impl<'a> MemberSettersCtx<'a> {
     fn new(builder_gen: &'a BuilderGenCtx, member: &'a Member, return_type: TokenStream2) -> Self {
-        let member_ident = &member.ident.to_string();
-        let norm_member_ident = member_ident
-            // Remove the leading underscore from the member name since it's used
-            // to denote unused symbols in Rust. That doesn't mean the builder
-            // API should expose that knowledge to the caller.
-            .strip_prefix('_')
-            .unwrap_or(member_ident);
-
-        // Preserve the original identifier span to make IDE go to definition correctly
-        // and make error messages point to the correct place.
-        let norm_member_ident = syn::Ident::new_maybe_raw(norm_member_ident, member.ident.span());
-
         Self {
             builder_gen,
             member,
             return_type,
-            norm_member_ident,
+            alias: None,
+            stateless: false,
         }
     }
 
-    fn setter_method_name(&self) -> syn::Ident {
-        self.member
-            .params
-            .name
-            .clone()
-            .unwrap_or_else(|| self.norm_member_ident.clone())
+    /// Name of the setter with the given `prefix` that goes after the
+    /// prefix configured with `#[builder(setter_prefix = ...)]` if any.
+    fn setter_method_name(&self, prefix: &str) -> syn::Ident {
+        let name = match self.alias {
+            Some(alias) => alias.clone(),
+            None => self.member.public_ident(),
+        };
+
+        self.prefixed_method_name(prefix, name)
+    }
+
+    /// Name of the collection setter, which is either specified explicitly
+    /// or consists of the given prefix and the name of the member
+    fn collection_setter_method_name(&self, name: &Option<syn::Ident>, prefix: &str) -> syn::Ident {
+        match name {
+            Some(name) => self.prefixed_method_name("", name.clone()),
+            None => self.setter_method_name(prefix),
+        }
+    }
+
+    /// Names of the setters generated under the public name of the member or
+    /// its alias. It must be kept in sync with the methods that generate them.
+    fn setter_method_names(&self) -> Vec<syn::Ident> {
+        let member = self.member;
+        let mut names = vec![self.setter_method_name("")];
+
+        if member.params.with.is_some() {
+            if member.as_optional().is_some() {
+                names.push(self.setter_method_name("maybe_"));
+            }
+            return names;
+        }
+
+        if member.params.flag.is_present() {
+            names.push(self.setter_method_name("with_"));
+            return names;
+        }
+
+        if member.as_optional().is_some() {
+            names.extend(self.maybe_setter_method_name());
+        }
+
+        if member.params.try_into.is_present() {
+            names.push(self.setter_method_name("try_"));
+        }
+
+        names
+    }
+
+    /// Names of the setters that accumulate the value of a collection member
+    fn collection_setter_method_names(&self) -> impl Iterator<Item = syn::Ident> + '_ {
+        let params = &self.member.params;
+
+        [
+            (&params.push, "push_"),
+            (&params.insert, "insert_"),
+            (&params.extend, "extend_"),
+        ]
+        .into_iter()
+        .filter_map(|(name, prefix)| {
+            let name = name.as_ref()?;
+            Some(self.collection_setter_method_name(name, prefix))
+        })
+    }
+
+    fn prefixed_method_name(&self, prefix: &str, name: syn::Ident) -> syn::Ident {
+        let setter_prefix = self
+            .builder_gen
+            .setter_prefix
+            .as_ref()
+            .map(|setter_prefix| setter_prefix.raw_name())
+            .unwrap_or_default();
+
+        if setter_prefix.is_empty() && prefix.is_empty() {
+            return name;
+        }
+
+        // The leading underscore of the name goes before the prefixes to keep
+        // the name in snake case e.g. `_maybe_internal` for `_internal`
+        let raw_name = name.raw_name();
+        let (underscore, raw_name) = match raw_name.strip_prefix('_') {
+            Some(raw_name) => ("_", raw_name),
+            None => ("", raw_name.as_str()),
+        };
+
+        quote::format_ident!(
+            "{underscore}{setter_prefix}{prefix}{raw_name}",
+            span = name.span()
+        )
     }
 
     fn setter_methods(&self) -> Result<TokenStream2> {
         let member_type = self.member.ty.as_ref();
 
+        if let Some(closure) = &self.member.params.with {
+            return Ok(self.setters_with_closure(closure));
+        }
+
+        if self.member.params.nested.is_present() {
+            return Ok(self.setters_for_nested_member());
+        }
+
+        if self.member.params.flag.is_present() {
+            return Ok(self.setters_for_flag_member());
+        }
+
         if let Some(inner_type) = self.member.as_optional() {
             return self.setters_for_optional_member(inner_type);
         }
 
-        let qualified_for_into = self
-            .builder_gen
-            .member_qualifies_for_into(self.member, &self.member.ty)?;
+        if let Some(maybe) = &self.member.params.maybe {
+            bail!(
+                &maybe.span(),
+                "#[builder(maybe)] is supported only for optional members, because \
+                only they have a setter that accepts an `Option`",
+            );
+        }
 
-        let (fn_param_type, maybe_into_call) = if qualified_for_into {
-            (quote!(impl Into<#member_type>), quote!(.into()))
+        let (fn_param_type, value) = if let Some(target) = self.as_ref_target(member_type)? {
+            (
+                quote!(impl ::core::convert::AsRef<#target>),
+                as_ref_to_owned(&target),
+            )
+        } else if self
+            .builder_gen
+            .member_qualifies_for_into(self.member, &self.member.ty)?
+        {
+            (
+                quote!(impl ::core::convert::Into<#member_type>),
+                quote!(::core::convert::Into::into(value)),
+            )
         } else {
-            (quote!(#member_type), quote!())
+            (quote!(#member_type), quote!(value))
         };
 
-        Ok(self.setter_method(MemberSetterMethod {
-            method_name: self.setter_method_name(),
+        let setter = self.setter_method(MemberSetterMethod {
+            method_name: self.setter_method_name(""),
             fn_params: quote!(value: #fn_param_type),
-            member_init: quote!(::bon::private::Set::new(value #maybe_into_call)),
+            member_init: quote!(::core::option::Option::Some(#value)),
             overwrite_docs: None,
+            try_into: None,
+            extend: None,
+            nested: None,
+        });
+
+        let try_setter = self.try_setter_method(member_type);
+
+        Ok(quote! {
+            #setter
+            #try_setter
+        })
+    }
+
+    /// Generates the `try_` setter if `#[builder(try_into)]` is enabled for
+    /// the member. The setter accepts a value convertible into `target_type`,
+    /// which is the type of the member or the type under the `Option`.
+    fn try_setter_method(&self, target_type: &syn::Type) -> Option<TokenStream2> {
+        if !self.member.params.try_into.is_present() {
+            return None;
+        }
+
+        let setter_method_name = self.setter_method_name("");
+
+        Some(self.setter_method(MemberSetterMethod {
+            method_name: self.setter_method_name("try_"),
+            fn_params: quote!(value: __Value),
+            member_init: quote!(::core::option::Option::Some(value)),
+            overwrite_docs: Some(format!(
+                "Same as [`Self::{setter_method_name}`], but accepts a value that \
+                is converted with `TryInto`. Returns the conversion error if it fails.",
+            )),
+            try_into: Some(target_type.clone()),
+            extend: None,
+            nested: None,
         }))
     }
 
-    fn setters_for_optional_member(&self, inner_type: &syn::Type) -> Result<TokenStream2> {
-        let qualified_for_into = self
-            .builder_gen
-            .member_qualifies_for_into(self.member, inner_type)?;
+    /// Generates the setters that accumulate the value of a collection member
+    /// incrementally. They are available in any state of the member and can be
+    /// called multiple times. If the member wasn't set yet, then they start
+    /// from an empty collection.
+    pub(crate) fn collection_setter_methods(&self) -> Result<Option<TokenStream2>> {
+        let setters = [
+            self.push_setter_method()?,
+            self.insert_setter_method()?,
+            self.extend_setter_method(),
+        ];
 
-        let (inner_type, maybe_conv_call, maybe_map_conv_call) = if qualified_for_into {
-            (
-                quote!(impl Into<#inner_type>),
-                quote!(.into()),
-                quote!(.map(Into::into)),
-            )
+        if setters.iter().all(Option::is_none) {
+            return Ok(None);
+        }
+
+        Ok(Some(setters.into_iter().flatten().concat()))
+    }
+
+    fn push_setter_method(&self) -> Result<Option<TokenStream2>> {
+        let Some(push) = &self.member.params.push else {
+            return Ok(None);
+        };
+
+        let collection_type = self.member.inner_type();
+
+        let item_type = collection_type
+            .is_final_segment("Vec")
+            .then(|| collection_type.type_param("Vec"))
+            .flatten()
+            .ok_or_else(|| {
+                err!(
+                    &push.span(),
+                    "#[builder(push)] is supported only for members of type \
+                    `Vec<T>` or `Option<Vec<T>>`",
+                )
+            })?;
+
+        let (item_param_type, item) = self.collection_item_param(item_type, quote!(item));
+
+        let method_name = self.collection_setter_method_name(push, "push_");
+
+        let setter_method_name = self.setter_method_name("");
+
+        Ok(Some(self.setter_method(MemberSetterMethod {
+            method_name,
+            fn_params: quote!(item: #item_param_type),
+            member_init: self.collection_member_init(quote! {
+                collection.push(#item);
+            }),
+            overwrite_docs: Some(format!(
+                "Appends a single item to the collection. Can be called multiple \
+                times. Use [`Self::{setter_method_name}`] to set the entire collection \
+                at once.",
+            )),
+            try_into: None,
+            extend: None,
+            nested: None,
+        })))
+    }
+
+    fn insert_setter_method(&self) -> Result<Option<TokenStream2>> {
+        let Some(insert) = &self.member.params.insert else {
+            return Ok(None);
+        };
+
+        let collection_type = self.member.inner_type();
+
+        let entry_types = ["HashMap", "BTreeMap"]
+            .into_iter()
+            .find(|map| collection_type.is_final_segment(map))
+            .and_then(|map| match collection_type.type_args(map)?.as_slice() {
+                [key, value, ..] => Some((*key, *value)),
+                _ => None,
+            });
+
+        let Some((key_type, value_type)) = entry_types else {
+            bail!(
+                &insert.span(),
+                "#[builder(insert)] is supported only for members of type \
+                `HashMap<K, V>`, `BTreeMap<K, V>` or an `Option` of them",
+            );
+        };
+
+        let (key_param_type, key) = self.collection_item_param(key_type, quote!(key));
+        let (value_param_type, value) = self.collection_item_param(value_type, quote!(value));
+
+        let method_name = self.collection_setter_method_name(insert, "insert_");
+
+        let setter_method_name = self.setter_method_name("");
+
+        Ok(Some(self.setter_method(MemberSetterMethod {
+            method_name,
+            fn_params: quote!(key: #key_param_type, value: #value_param_type),
+            member_init: self.collection_member_init(quote! {
+                collection.insert(#key, #value);
+            }),
+            overwrite_docs: Some(format!(
+                "Inserts a single entry into the map. If the map already had the \
+                same key, then the value is replaced. Can be called multiple times. \
+                Use [`Self::{setter_method_name}`] to set the entire map at once.",
+            )),
+            try_into: None,
+            extend: None,
+            nested: None,
+        })))
+    }
+
+    fn extend_setter_method(&self) -> Option<TokenStream2> {
+        let extend = self.member.params.extend.as_ref()?;
+
+        let method_name = self.collection_setter_method_name(extend, "extend_");
+
+        let setter_method_name = self.setter_method_name("");
+
+        Some(self.setter_method(MemberSetterMethod {
+            method_name,
+            fn_params: quote!(items: __Items),
+            member_init: self.collection_member_init(quote! {
+                ::core::iter::Extend::extend(collection, items);
+            }),
+            overwrite_docs: Some(format!(
+                "Extends the collection with the contents of an iterator. Can be \
+                called multiple times. Use [`Self::{setter_method_name}`] to set \
+                the entire collection at once.",
+            )),
+            try_into: None,
+            extend: Some(self.member.inner_type().clone()),
+            nested: None,
+        }))
+    }
+
+    /// Type of the parameter of a collection setter for the item of the given
+    /// type and the expression that converts the parameter into the item.
+    fn collection_item_param(
+        &self,
+        item_type: &syn::Type,
+        param: TokenStream2,
+    ) -> (TokenStream2, TokenStream2) {
+        if self.builder_gen.item_qualifies_for_into(item_type) {
+            return (
+                quote!(impl ::core::convert::Into<#item_type>),
+                quote!(::core::convert::Into::into(#param)),
+            );
+        }
+
+        (quote!(#item_type), param)
+    }
+
+    /// Expression that takes the collection of the member from the builder,
+    /// applies the `update` to it, and stores it back. The `update` accesses
+    /// the collection via the `collection` variable of `&mut` type.
+    fn collection_member_init(&self, update: TokenStream2) -> TokenStream2 {
+        let member_ident = &self.member.ident;
+
+        // The variable isn't named `value` to avoid shadowing the parameters
+        // of the setters that are referenced in the `update`
+        // The setters that take `&mut self` can't move the value out of the builder
+        let member = if self.builder_gen.has_mut_setters(self.member) {
+            quote!(::core::option::Option::take(&mut self.__private_impl.#member_ident))
         } else {
-            (quote!(#inner_type), quote!(), quote!())
+            quote!(self.__private_impl.#member_ident)
         };
 
-        let setter_method_name = self.setter_method_name();
+        quote! {{
+            let mut member = #member;
+            let collection = member.get_or_insert_with(::core::default::Default::default);
+            #update
+            member
+        }}
+    }
+
+    /// Generates the setters with the parameters of the closure specified via
+    /// `#[builder(with = closure)]`. The closure produces the value of the member.
+    fn setters_with_closure(&self, closure: &syn::ExprClosure) -> TokenStream2 {
+        let (idents, types): (Vec<_>, Vec<_>) = closure
+            .inputs
+            .iter()
+            .enumerate()
+            .filter_map(|(i, input)| {
+                // Non-typed inputs are rejected during the validation of the member
+                let syn::Pat::Type(input) = input else {
+                    return None;
+                };
+
+                let ident = match input.pat.as_ref() {
+                    syn::Pat::Ident(pat) if pat.subpat.is_none() => pat.ident.clone(),
+                    _ => quote::format_ident!("__arg{i}"),
+                };
+
+                Some((ident, &input.ty))
+            })
+            .unzip();
+
+        let value = quote!((#closure)(#( #idents ),*));
+
+        if self.member.as_optional().is_none() {
+            return self.setter_method(MemberSetterMethod {
+                method_name: self.setter_method_name(""),
+                fn_params: quote!(#( #idents: #types ),*),
+                member_init: quote!(::core::option::Option::Some(#value)),
+                overwrite_docs: None,
+                try_into: None,
+                extend: None,
+                nested: None,
+            });
+        }
+
+        let setter_method_name = self.setter_method_name("");
+
+        // The `maybe_` setter accepts all parameters of the closure as a tuple
+        // unless there is exactly one parameter
+        let (maybe_type, maybe_pat) = if let ([ident], [ty]) = (idents.as_slice(), types.as_slice())
+        {
+            (quote!(#ty), quote!(#ident))
+        } else {
+            (quote!(( #( #types, )* )), quote!(( #( #idents, )* )))
+        };
 
         let methods = [
             MemberSetterMethod {
-                method_name: quote::format_ident!("maybe_{}", setter_method_name.raw_name()),
-                fn_params: quote!(value: Option<#inner_type>),
-                member_init: quote!(::bon::private::Set::new(value #maybe_map_conv_call)),
+                method_name: self.setter_method_name("maybe_"),
+                fn_params: quote!(value: ::core::option::Option<#maybe_type>),
+                member_init: quote!(::core::option::Option::map(value, |#maybe_pat| #value)),
                 overwrite_docs: Some(format!(
                     "Same as [`Self::{setter_method_name}`], but accepts \
                     an `Option` as input. See that method's documentation for \
                     more details.",
                 )),
+                try_into: None,
+                extend: None,
+                nested: None,
             },
+            MemberSetterMethod {
+                method_name: setter_method_name,
+                fn_params: quote!(#( #idents: #types ),*),
+                member_init: quote!(::core::option::Option::Some(#value)),
+                overwrite_docs: None,
+                try_into: None,
+                extend: None,
+                nested: None,
+            },
+        ];
+
+        methods
+            .into_iter()
+            .map(|method| self.setter_method(method))
+            .concat()
+    }
+
+    /// Generates the setter that accepts a closure configuring the builder of
+    /// the member's type for `#[builder(nested)]`. The `maybe_` setter of an
+    /// optional member accepts an already built value instead.
+    fn setters_for_nested_member(&self) -> TokenStream2 {
+        let bon = &self.builder_gen.bon;
+        let value_type = self.member.as_optional().unwrap_or(&self.member.ty);
+        let setter_method_name = self.setter_method_name("");
+
+        let maybe_method = self
+            .member
+            .as_optional()
+            .and_then(|_| self.maybe_setter_method_name())
+            .map(|method_name| MemberSetterMethod {
+                method_name,
+                fn_params: quote!(value: ::core::option::Option<#value_type>),
+                member_init: quote!(value),
+                overwrite_docs: Some(format!(
+                    "Same as [`Self::{setter_method_name}`], but accepts an already \
+                    built value as an `Option`.",
+                )),
+                try_into: None,
+                extend: None,
+                nested: None,
+            });
+
+        let methods = [
+            Some(MemberSetterMethod {
+                method_name: setter_method_name,
+                fn_params: quote! {
+                    configure: impl ::core::ops::FnOnce(
+                        <#value_type as #bon::nested::HasBuilder>::Builder
+                    ) -> __Builder
+                },
+                member_init: quote!(::core::option::Option::Some(value)),
+                overwrite_docs: None,
+                try_into: None,
+                extend: None,
+                nested: Some(value_type.clone()),
+            }),
+            maybe_method,
+        ];
+
+        methods
+            .into_iter()
+            .flatten()
+            .map(|method| self.setter_method(method))
+            .concat()
+    }
+
+    fn setters_for_optional_member(&self, inner_type: &syn::Type) -> Result<TokenStream2> {
+        let try_into_type = inner_type;
+
+        let (inner_type, value, maybe_value) =
+            if let Some(target) = self.as_ref_target(inner_type)? {
+                let value = as_ref_to_owned(&target);
+                (
+                    quote!(impl ::core::convert::AsRef<#target>),
+                    value.clone(),
+                    quote!(::core::option::Option::map(value, |value| #value)),
+                )
+            } else if self
+                .builder_gen
+                .member_qualifies_for_into(self.member, inner_type)?
+            {
+                (
+                    quote!(impl ::core::convert::Into<#inner_type>),
+                    quote!(::core::convert::Into::into(value)),
+                    quote!(::core::option::Option::map(
+                        value,
+                        ::core::convert::Into::into
+                    )),
+                )
+            } else {
+                (quote!(#inner_type), quote!(value), quote!(value))
+            };
+
+        let setter_method_name = self.setter_method_name("");
+
+        let maybe_method = self
+            .maybe_setter_method_name()
+            .map(|method_name| MemberSetterMethod {
+                method_name,
+                fn_params: quote!(value: ::core::option::Option<#inner_type>),
+                member_init: maybe_value,
+                overwrite_docs: Some(format!(
+                    "Same as [`Self::{setter_method_name}`], but accepts \
+                    an `Option` as input. See that method's documentation for \
+                    more details.",
+                )),
+                try_into: None,
+                extend: None,
+                nested: None,
+            });
+
+        let methods = [
             // We intentionally keep the name and signature of the setter method
             // for an optional member that accepts the value under the option the
             // same as the setter method for the required member to keep the API
             // of the builder compatible when a required member becomes optional.
             // To be able to explicitly pass an `Option` value to the setter method
             // users need to use the `maybe_{member_ident}` method.
-            MemberSetterMethod {
+            Some(MemberSetterMethod {
                 method_name: setter_method_name,
                 fn_params: quote!(value: #inner_type),
-                member_init: quote!(::bon::private::Set::new(Some(value #maybe_conv_call))),
+                member_init: quote!(::core::option::Option::Some(#value)),
                 overwrite_docs: None,
-            },
+                try_into: None,
+                extend: None,
+                nested: None,
+            }),
+            maybe_method,
         ];
 
         let setters = methods
             .into_iter()
+            .flatten()
             .map(|method| self.setter_method(method))
             .concat();
 
-        Ok(setters)
+        let try_setter = self.try_setter_method(try_into_type);
+
+        Ok(quote! {
+            #setters
+            #try_setter
+        })
+    }
+
+    /// Type `T` for the `impl AsRef<T>` parameter of the setter if
+    /// `#[builder(as_ref)]` is enabled for the member. If it isn't specified
+    /// explicitly, then it's inferred from the well-known owned types.
+    fn as_ref_target(&self, ty: &syn::Type) -> Result<Option<syn::Type>> {
+        let Some(as_ref) = &self.member.params.as_ref else {
+            return Ok(None);
+        };
+
+        if let Some(target) = as_ref.as_ref() {
+            return Ok(Some(target.clone()));
+        }
+
+        let target: syn::Type = if ty.is_final_segment("String") {
+            syn::parse_quote!(str)
+        } else if ty.is_final_segment("PathBuf") {
+            syn::parse_quote!(::std::path::Path)
+        } else if ty.is_final_segment("OsString") {
+            syn::parse_quote!(::std::ffi::OsStr)
+        } else if let Some(item) = ty.type_param("Vec").filter(|_| ty.is_final_segment("Vec")) {
+            syn::parse_quote!([#item])
+        } else {
+            bail!(
+                &as_ref.span(),
+                "can't infer the type for `impl AsRef<T>` from the type of this {}. \
+                It's inferred only for `String`, `PathBuf`, `OsString` and `Vec<T>`. \
+                Specify it explicitly with #[builder(as_ref = Type)]",
+                self.member.origin,
+            );
+        };
+
+        Ok(Some(target))
+    }
+
+    /// Name of the setter of an optional member that accepts an `Option`, or
+    /// `None` if the setter is disabled. The member-level configuration takes
+    /// precedence over the one on the builder.
+    fn maybe_setter_method_name(&self) -> Option<syn::Ident> {
+        let maybe = self
+            .member
+            .params
+            .maybe
+            .as_deref()
+            .or(self.builder_gen.maybe_setters.as_ref());
+
+        match maybe {
+            Some(MaybeSetters::Disabled) => None,
+            Some(MaybeSetters::Prefix(prefix)) => Some(self.setter_method_name(&prefix.raw_name())),
+            None => Some(self.setter_method_name("maybe_")),
+        }
+    }
+
+    fn setters_for_flag_member(&self) -> TokenStream2 {
+        let setter_method_name = self.setter_method_name("");
+
+        let methods = [
+            MemberSetterMethod {
+                method_name: self.setter_method_name("with_"),
+                fn_params: quote!(value: bool),
+                member_init: quote!(::core::option::Option::Some(value)),
+                overwrite_docs: Some(format!(
+                    "Same as [`Self::{setter_method_name}`], but accepts the \
+                    `bool` value explicitly.",
+                )),
+                try_into: None,
+                extend: None,
+                nested: None,
+            },
+            MemberSetterMethod {
+                method_name: setter_method_name,
+                fn_params: quote!(),
+                member_init: quote!(::core::option::Option::Some(true)),
+                overwrite_docs: None,
+                try_into: None,
+                extend: None,
+                nested: None,
+            },
+        ];
+
+        methods
+            .into_iter()
+            .map(|method| self.setter_method(method))
+            .concat()
+    }
+
+    /// Docs of the setter under the alias that refer to the setter under the
+    /// public name of the member. The setter is marked as `#[deprecated]` if
+    /// the note is specified.
+    fn alias_docs(&self, alias: &syn::Ident, method_name: &syn::Ident) -> Vec<syn::Attribute> {
+        let method_name = method_name.raw_name();
+        let prefix = method_name
+            .strip_suffix(&alias.raw_name())
+            .unwrap_or_default();
+
+        let docs = format!(
+            "Alias of [`Self::{prefix}{}`].",
+            self.member.public_ident().raw_name()
+        );
+
+        let mut docs = vec![syn::parse_quote!(#[doc = #docs])];
+
+        if let Some(note) = &self.member.params.deprecated {
+            let note = note.as_str();
+            docs.push(syn::parse_quote!(#[deprecated = #note]));
+        }
+
+        docs
     }
 
     fn setter_method(&self, method: MemberSetterMethod) -> TokenStream2 {
@@ -311,45 +1171,95 @@ impl<'a> MemberSettersCtx<'a> {
             fn_params,
             member_init,
             overwrite_docs,
+            try_into,
+            extend,
+            nested,
         } = method;
 
-        let docs = match overwrite_docs {
-            Some(docs) => vec![syn::parse_quote!(#[doc = #docs])],
-            None => self.member.docs.clone(),
+        let docs = match (self.alias, overwrite_docs) {
+            (Some(alias), _) => self.alias_docs(alias, &method_name),
+            (None, Some(docs)) => vec![syn::parse_quote!(#[doc = #docs])],
+            (None, None) => self.member.docs.clone(),
         };
 
-        let vis = &self.builder_gen.vis;
+        let vis = self.builder_gen.builder_type_vis();
 
-        let builder_ident = &self.builder_gen.builder_ident;
-        let builder_private_impl_ident = &self.builder_gen.builder_private_impl_ident;
-        let member_idents = self.builder_gen.member_idents();
-        let maybe_receiver_field = self
-            .builder_gen
-            .assoc_method_ctx
-            .as_ref()
-            .and_then(AssocMethodCtx::as_receiver)
-            .is_some()
-            .then(|| quote!(receiver: self.__private_impl.receiver,));
-
-        let member_exprs = self.builder_gen.members.iter().map(|other_member| {
-            if other_member.ident == self.member.ident {
-                return member_init.clone();
-            }
+        // All setters update the member in place. The ones that change the
+        // type state then move the private struct into the builder with the
+        // next state.
+        let mut_setters = self.builder_gen.has_mut_setters(self.member);
+        let member_ident = &self.member.ident;
+        let update = quote! {
+            self.__private_impl.#member_ident = #member_init;
+        };
+        let (receiver, builder) = if mut_setters {
+            (quote!(&mut self), quote!({ #update self }))
+        } else if self.stateless {
+            (quote!(mut self), quote!({ #update self }))
+        } else {
+            let builder = self.builder_gen.builder_expr(quote!(self.__private_impl));
+            (quote!(mut self), quote!({ #update #builder }))
+        };
 
-            let ident = &other_member.ident;
-            quote!(self.__private_impl.#ident)
+        // Dropping the builder returned by the setter is always a bug, unless
+        // the setter takes `&mut self`. The fallible setters return a `Result`,
+        // which is `#[must_use]` already.
+        let must_use = (!mut_setters).then(|| {
+            let must_use_message = self.builder_gen.must_use_message();
+            quote!(#[must_use = #must_use_message])
         });
 
+        if let Some(collection_type) = extend {
+            return quote! {
+                #( #docs )*
+                #must_use
+                #vis fn #method_name<__Items: ::core::iter::IntoIterator>(
+                    #receiver,
+                    #fn_params
+                ) -> #return_type
+                where
+                    #collection_type: ::core::iter::Extend<__Items::Item>,
+                {
+                    #builder
+                }
+            };
+        }
+
+        if let Some(value_type) = nested {
+            let bon = &self.builder_gen.bon;
+            return quote! {
+                #( #docs )*
+                #must_use
+                #vis fn #method_name<__Builder: #bon::nested::FinishBuilder<Output = #value_type>>(
+                    #receiver,
+                    #fn_params
+                ) -> #return_type {
+                    let value = #bon::nested::FinishBuilder::finish_builder(
+                        configure(<#value_type as #bon::nested::HasBuilder>::builder())
+                    );
+                    #builder
+                }
+            };
+        }
+
+        let Some(target_type) = try_into else {
+            return quote! {
+                #( #docs )*
+                #must_use
+                #vis fn #method_name(#receiver, #fn_params) -> #return_type {
+                    #builder
+                }
+            };
+        };
+
         quote! {
             #( #docs )*
-            #vis fn #method_name(self, #fn_params) -> #return_type {
-                #builder_ident {
-                    __private_impl: #builder_private_impl_ident {
-                        _phantom: ::core::marker::PhantomData,
-                        #maybe_receiver_field
-                        #( #member_idents: #member_exprs, )*
-                    }
-                }
+            #vis fn #method_name<__Value: ::core::convert::TryInto<#target_type>>(
+                #receiver,
+                #fn_params
+            ) -> ::core::result::Result<#return_type, __Value::Error> {
+                let value = ::core::convert::TryInto::try_into(value)?;
+                ::core::result::Result::Ok(#builder)
             }
         }
     }
@@ -360,4 +1270,25 @@ struct MemberSetterMethod {
     fn_params: TokenStream2,
     member_init: TokenStream2,
     overwrite_docs: Option<String>,
+
+    /// If present, the setter accepts a value that is fallibly converted into
+    /// this type, and returns a `Result` with the conversion error.
+    try_into: Option<syn::Type>,
+
+    /// If present, the setter accepts any iterable value whose items can be
+    /// used to extend the collection of this type.
+    extend: Option<syn::Type>,
+
+    /// If present, the setter accepts a closure that configures the builder
+    /// of this type, and the value is built from the returned builder.
+    nested: Option<syn::Type>,
+}
+
+/// Converts the `value` parameter of type `impl AsRef<T>` into an owned value
+fn as_ref_to_owned(target: &syn::Type) -> TokenStream2 {
+    quote! {
+        ::std::borrow::ToOwned::to_owned(
+            ::core::convert::AsRef::<#target>::as_ref(&value)
+        )
+    }
 }
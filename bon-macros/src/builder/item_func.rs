@@ -1,5 +1,5 @@
 use super::builder_gen::input_func::{FuncInputCtx, FuncInputParams};
-use super::builder_gen::MacroOutput;
+use super::builder_gen::{BuilderGenCtx, MacroOutput};
 use crate::util::prelude::*;
 use quote::quote;
 use syn::visit_mut::VisitMut;
@@ -17,16 +17,38 @@ pub(crate) fn generate(params: FuncInputParams, orig_func: syn::ItemFn) -> Resul
         params,
     };
 
-    let adapted_func = ctx.adapted_func()?;
+    let adapted_func = ctx.adapted_func();
+
+    let owned_output = ctx
+        .clone()
+        .into_owned_builder_gen_ctx()?
+        .map(BuilderGenCtx::output)
+        .transpose()?;
 
     let MacroOutput {
         start_func,
+        closure_func,
         other_items,
     } = ctx.into_builder_gen_ctx()?.output()?;
 
+    let owned_output = owned_output.map(|output| {
+        let MacroOutput {
+            start_func,
+            closure_func: _,
+            other_items,
+        } = output;
+
+        quote! {
+            #start_func
+            #other_items
+        }
+    });
+
     Ok(quote! {
         #start_func
+        #closure_func
         #other_items
+        #owned_output
 
         // Keep original function at the end. It seems like rust-analyzer
         // does better job of highlighting syntax when it is here. Assuming
@@ -1,17 +1,40 @@
 use super::builder_gen::input_func::{FuncInputCtx, FuncInputParams, ImplCtx};
+use super::builder_gen::BuilderGenCtx;
 use crate::util::prelude::*;
-use darling::ast::NestedMeta;
 use darling::FromMeta;
 use itertools::{Either, Itertools};
 use quote::quote;
 use std::rc::Rc;
 use syn::visit_mut::VisitMut;
 
-pub(crate) fn generate(mut orig_impl_block: syn::ItemImpl) -> Result<TokenStream2> {
+pub(crate) fn generate(
+    orig_impl_block: syn::ItemImpl,
+    bon: Option<syn::Path>,
+) -> Result<TokenStream2> {
     if let Some((_, trait_path, _)) = &orig_impl_block.trait_ {
         bail!(trait_path, "Impls of traits are not supported yet");
     }
 
+    let (impl_block, other_items) = generate_impl_block(orig_impl_block, None, bon)?;
+
+    Ok(quote! {
+        #(#other_items)*
+        #impl_block
+    })
+}
+
+/// Generates the builders for the `#[builder]` methods of the impl block.
+/// Returns the impl block with the starting functions of the builders and
+/// the items that must be placed next to it.
+///
+/// The builders of the methods of a trait definition are generated from an
+/// impl block for the generic `Self` type. In this case the name of the trait
+/// is used as the prefix of the builders' names instead of the `Self` type.
+pub(super) fn generate_impl_block(
+    mut orig_impl_block: syn::ItemImpl,
+    trait_ident: Option<syn::Ident>,
+    bon: Option<syn::Path>,
+) -> Result<(syn::ItemImpl, Vec<TokenStream2>)> {
     let (other_items, builder_funcs): (Vec<_>, Vec<_>) =
         orig_impl_block.items.into_iter().partition_map(|item| {
             let syn::ImplItem::Fn(fn_item) = item else {
@@ -67,6 +90,8 @@ pub(crate) fn generate(mut orig_impl_block: syn::ItemImpl) -> Result<TokenStream
     let impl_ctx = Rc::new(ImplCtx {
         self_ty: norm_impl_block.self_ty,
         generics: norm_impl_block.generics,
+        trait_ident,
+        bon,
     });
 
     let outputs: Vec<_> = std::iter::zip(orig_impl_block.items, norm_impl_block.items)
@@ -78,17 +103,18 @@ pub(crate) fn generate(mut orig_impl_block: syn::ItemImpl) -> Result<TokenStream
                 unreachable!();
             };
 
-            let norm_func = impl_item_fn_into_fn_item(norm_func)?;
-            let orig_func = impl_item_fn_into_fn_item(orig_func)?;
+            // The `default` keyword of the specialization feature is preserved
+            // only on the adapted function, which has the original body
+            let defaultness = orig_func.defaultness;
+
+            let norm_func = impl_item_fn_into_fn_item(norm_func);
+            let orig_func = impl_item_fn_into_fn_item(orig_func);
 
             let meta = orig_func
                 .attrs
                 .iter()
                 .filter(|attr| attr.path().is_ident("builder"))
-                .map(|attr| {
-                    let meta_list = darling::util::parse_attribute_to_meta_list(attr)?;
-                    NestedMeta::parse_meta_list(meta_list.tokens).map_err(Into::into)
-                })
+                .map(|attr| attr.parse_nested_metas())
                 .flatten_ok()
                 .collect::<Result<Vec<_>>>()?;
 
@@ -101,46 +127,79 @@ pub(crate) fn generate(mut orig_impl_block: syn::ItemImpl) -> Result<TokenStream
                 params,
             };
 
-            Result::<_>::Ok((ctx.adapted_func()?, ctx.into_builder_gen_ctx()?.output()?))
+            let adapted_func = ctx.adapted_func();
+            let adapted_func = item_fn_into_impl_item_fn(adapted_func, defaultness);
+
+            let owned_output = ctx
+                .clone()
+                .into_owned_builder_gen_ctx()?
+                .map(BuilderGenCtx::output)
+                .transpose()?;
+
+            let output = ctx.into_builder_gen_ctx()?.output()?;
+
+            Result::<_>::Ok((adapted_func, output, owned_output))
         })
         .try_collect()?;
 
-    let new_impl_items = outputs.iter().flat_map(|(adapted_func, output)| {
-        let start_func = &output.start_func;
-        [
-            syn::parse_quote!(#start_func),
-            syn::parse_quote!(#adapted_func),
-        ]
-    });
+    let new_impl_items = outputs
+        .iter()
+        .flat_map(|(adapted_func, output, owned_output)| {
+            let start_funcs = std::iter::once(output)
+                .chain(owned_output)
+                .map(|output| &output.start_func);
+
+            start_funcs
+                .chain(&output.closure_func)
+                .map(|func| syn::parse_quote!(#func))
+                .chain([syn::ImplItem::Fn(adapted_func.clone())])
+        });
 
     norm_selfful_impl_block.items = other_items;
     norm_selfful_impl_block.items.extend(new_impl_items);
 
-    let other_items = outputs.iter().map(|(_, output)| &output.other_items);
+    let other_items = outputs
+        .into_iter()
+        .flat_map(|(_, output, owned_output)| std::iter::once(output).chain(owned_output))
+        .map(|output| output.other_items)
+        .collect();
 
-    Ok(quote! {
-        #(#other_items)*
-        #norm_selfful_impl_block
-    })
+    Ok((norm_selfful_impl_block, other_items))
 }
 
-fn impl_item_fn_into_fn_item(func: syn::ImplItemFn) -> Result<syn::ItemFn> {
+fn impl_item_fn_into_fn_item(func: syn::ImplItemFn) -> syn::ItemFn {
     let syn::ImplItemFn {
         attrs,
         vis,
-        defaultness,
+        defaultness: _,
         sig,
         block,
     } = func;
 
-    if let Some(defaultness) = &defaultness {
-        bail!(defaultness, "Default functions are not supported yet");
+    syn::ItemFn {
+        attrs,
+        vis,
+        sig,
+        block: Box::new(block),
     }
+}
 
-    Ok(syn::ItemFn {
+fn item_fn_into_impl_item_fn(
+    func: syn::ItemFn,
+    defaultness: Option<syn::Token![default]>,
+) -> syn::ImplItemFn {
+    let syn::ItemFn {
         attrs,
         vis,
         sig,
-        block: Box::new(block),
-    })
+        block,
+    } = func;
+
+    syn::ImplItemFn {
+        attrs,
+        vis,
+        defaultness,
+        sig,
+        block: *block,
+    }
 }
@@ -8,28 +8,80 @@ pub(crate) fn generate(
     orig_struct: syn::ItemStruct,
 ) -> Result<TokenStream2> {
     let ctx = StructInputCtx::new(params, orig_struct);
-
     let adapted_struct = ctx.adapted_struct();
+    let flatten_callback = ctx.flatten_callback();
+    let builder_items = generate_builder_items(ctx)?;
+
+    Ok(quote! {
+        #builder_items
+        #flatten_callback
+        #adapted_struct
+    })
+}
+
+/// The derive macro can't modify the struct, so it outputs only the builder
+/// items. The `#[builder(...)]` attributes on the struct are inert in this case.
+pub(crate) fn generate_for_derive(
+    params: StructInputParams,
+    orig_struct: syn::ItemStruct,
+) -> Result<TokenStream2> {
+    let ctx = StructInputCtx::new(params, orig_struct);
+    let flatten_callback = ctx.flatten_callback();
+    let builder_items = generate_builder_items(ctx)?;
+
+    Ok(quote! {
+        #builder_items
+        #flatten_callback
+    })
+}
+
+fn generate_builder_items(ctx: StructInputCtx) -> Result<TokenStream2> {
+    let struct_ident = ctx.struct_ident().clone();
+    let start_func_on = ctx.start_func_on().cloned();
 
     let MacroOutput {
         mut start_func,
+        mut closure_func,
         other_items,
     } = ctx.into_builder_gen_ctx()?.output()?;
 
-    let impl_generics = std::mem::take(&mut start_func.sig.generics);
+    let start_func_impl = match start_func_on {
+        // The generics of the struct stay on the start function itself because
+        // they aren't constrained by the type that the function is defined on.
+        Some(start_func_on) => quote! {
+            impl #start_func_on {
+                #start_func
+                #closure_func
+            }
+        },
+        None => {
+            let impl_generics = std::mem::take(&mut start_func.sig.generics);
 
-    let (generics_decl, generic_args, where_clause) = impl_generics.split_for_impl();
+            // The closure function shares the generics of the struct with the
+            // start function. They are declared on the impl block instead.
+            if let Some(closure_func) = &mut closure_func {
+                let params = &mut closure_func.sig.generics.params;
+                *params = std::mem::take(params)
+                    .into_iter()
+                    .skip(impl_generics.params.len())
+                    .collect();
+            }
 
-    let struct_ident = &adapted_struct.ident;
+            let (generics_decl, generic_args, where_clause) = impl_generics.split_for_impl();
 
-    Ok(quote! {
-        impl #generics_decl #struct_ident #generic_args
-            #where_clause
-        {
-            #start_func
+            quote! {
+                impl #generics_decl #struct_ident #generic_args
+                    #where_clause
+                {
+                    #start_func
+                    #closure_func
+                }
+            }
         }
+    };
 
+    Ok(quote! {
+        #start_func_impl
         #other_items
-        #adapted_struct
     })
 }
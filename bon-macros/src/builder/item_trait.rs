@@ -0,0 +1,165 @@
+use crate::util::prelude::*;
+use itertools::{Either, Itertools};
+use quote::quote;
+use syn::visit_mut::VisitMut;
+
+/// Name of the type parameter that represents the `Self` type of the trait
+/// in the generated builders
+const SELF_TY_PARAM: &str = "__Self";
+
+pub(crate) fn generate(
+    mut item_trait: syn::ItemTrait,
+    bon: Option<syn::Path>,
+) -> Result<TokenStream2> {
+    let (other_items, builder_funcs): (Vec<_>, Vec<_>) =
+        item_trait.items.into_iter().partition_map(|item| {
+            let syn::TraitItem::Fn(fn_item) = item else {
+                return Either::Left(item);
+            };
+
+            let has_builder_attr = fn_item
+                .attrs
+                .iter()
+                .any(|attr| attr.path().is_ident("builder"));
+
+            if has_builder_attr {
+                Either::Right(fn_item)
+            } else {
+                Either::Left(syn::TraitItem::Fn(fn_item))
+            }
+        });
+
+    if builder_funcs.is_empty() {
+        bail!(
+            &proc_macro2::Span::call_site(),
+            "There are no #[builder] functions in the trait, so there is no \
+            need for a #[bon] attribute on the trait"
+        );
+    }
+
+    let vis = &item_trait.vis;
+
+    let impl_items: Vec<_> = builder_funcs
+        .into_iter()
+        .map(|func| {
+            let syn::TraitItemFn {
+                attrs,
+                sig,
+                default,
+                semi_token: _,
+            } = func;
+
+            let Some(block) = default else {
+                bail!(
+                    &sig.ident,
+                    "#[builder] is supported only for the provided methods \
+                    of a trait, because the builder calls the method's body"
+                );
+            };
+
+            // The builders get the visibility of the trait
+            Ok(syn::ImplItem::Fn(syn::ImplItemFn {
+                attrs,
+                vis: vis.clone(),
+                defaultness: None,
+                sig,
+                block,
+            }))
+        })
+        .try_collect()?;
+
+    // The builders are generated as if the methods were defined in an impl
+    // block for a generic type that implements the trait
+    let trait_ident = &item_trait.ident;
+    let (_, trait_generic_args, _) = item_trait.generics.split_for_impl();
+    let self_ty_param = syn::Ident::new(SELF_TY_PARAM, proc_macro2::Span::call_site());
+
+    let mut impl_generics = item_trait.generics.clone();
+
+    // Defaults of the generic parameters aren't allowed in impl blocks
+    for param in &mut impl_generics.params {
+        match param {
+            syn::GenericParam::Type(param) => {
+                param.eq_token = None;
+                param.default = None;
+            }
+            syn::GenericParam::Const(param) => {
+                param.eq_token = None;
+                param.default = None;
+            }
+            syn::GenericParam::Lifetime(_) => {}
+        }
+    }
+
+    impl_generics
+        .params
+        .push(syn::parse_quote!(#self_ty_param: #trait_ident #trait_generic_args));
+
+    let impl_block = syn::ItemImpl {
+        attrs: vec![],
+        defaultness: None,
+        unsafety: None,
+        impl_token: Default::default(),
+        generics: impl_generics,
+        trait_: None,
+        self_ty: syn::parse_quote!(#self_ty_param),
+        brace_token: Default::default(),
+        items: impl_items,
+    };
+
+    let (impl_block, builder_items) =
+        super::item_impl::generate_impl_block(impl_block, Some(trait_ident.clone()), bon)?;
+
+    let builder_funcs = impl_block.items.into_iter().map(|item| {
+        let syn::ImplItem::Fn(func) = item else {
+            unreachable!("BUG: only functions are generated for the trait");
+        };
+
+        let syn::ImplItemFn {
+            attrs,
+            vis: _,
+            defaultness: _,
+            mut sig,
+            block,
+        } = func;
+
+        // The builders store the `Self` type, so they require it to be sized.
+        // The bound also keeps the trait object safe, because the methods
+        // with it aren't callable on trait objects.
+        sig.generics
+            .make_where_clause()
+            .predicates
+            .push(syn::parse_quote!(Self: ::core::marker::Sized));
+
+        let mut func = syn::TraitItemFn {
+            attrs,
+            sig,
+            default: Some(block),
+            semi_token: None,
+        };
+
+        ReplaceSelfTyParam.visit_trait_item_fn_mut(&mut func);
+
+        syn::TraitItem::Fn(func)
+    });
+
+    item_trait.items = other_items;
+    item_trait.items.extend(builder_funcs);
+
+    Ok(quote! {
+        #(#builder_items)*
+        #item_trait
+    })
+}
+
+/// Replaces the type parameter of the builders that represents the `Self`
+/// type with the `Self` keyword in the items placed inside of the trait.
+struct ReplaceSelfTyParam;
+
+impl VisitMut for ReplaceSelfTyParam {
+    fn visit_ident_mut(&mut self, ident: &mut syn::Ident) {
+        if ident == SELF_TY_PARAM {
+            *ident = syn::Ident::new("Self", ident.span());
+        }
+    }
+}
@@ -2,14 +2,41 @@ mod builder_gen;
 mod params;
 
 pub(crate) mod item_impl;
+pub(crate) mod item_trait;
+
+pub(crate) use params::{parse_crate_path, parse_syntax};
 
 mod item_func;
 mod item_struct;
 
+use crate::normalization::{ExpandCfg, ExpandCfgOutput, ExpandFlatten, ExpandFlattenOutput};
 use crate::util::prelude::*;
 use darling::FromMeta;
+use itertools::Itertools;
 
 pub(crate) fn generate_for_item(params: TokenStream2, item: syn::Item) -> Result<TokenStream2> {
+    let expand_cfg = ExpandCfg {
+        macro_ident: syn::parse_quote!(builder),
+        params,
+        item,
+    };
+
+    let (params, item) = match expand_cfg.expand_cfg()? {
+        ExpandCfgOutput::Expanded { params, item } => (params, *item),
+        ExpandCfgOutput::Recurse(output) => return Ok(output),
+    };
+
+    let expand_flatten = ExpandFlatten {
+        macro_ident: syn::parse_quote!(builder),
+        params,
+        item,
+    };
+
+    let (params, item) = match expand_flatten.expand_flatten()? {
+        ExpandFlattenOutput::Expanded { params, item } => (params, *item),
+        ExpandFlattenOutput::Recurse(output) => return Ok(output),
+    };
+
     let params = &darling::ast::NestedMeta::parse_meta_list(params)?;
 
     match item {
@@ -24,3 +51,23 @@ pub(crate) fn generate_for_item(params: TokenStream2, item: syn::Item) -> Result
         }
     }
 }
+
+pub(crate) fn generate_for_derive(item: syn::Item) -> Result<TokenStream2> {
+    let syn::Item::Struct(item) = item else {
+        bail!(
+            &item,
+            "The derive is expected to be placed only on a `struct` declaration"
+        )
+    };
+
+    // Multiple `#[builder(...)]` attributes are merged into a single list of params
+    let params: Vec<_> = item
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("builder"))
+        .map(|attr| attr.parse_nested_metas())
+        .flatten_ok()
+        .try_collect()?;
+
+    item_struct::generate_for_derive(FromMeta::from_list(&params)?, item)
+}
@@ -1,14 +1,186 @@
 use crate::util::prelude::*;
+use darling::util::SpannedValue;
 use darling::FromMeta;
 use quote::quote;
 
-#[derive(Debug, FromMeta)]
+#[derive(Debug, Clone, FromMeta)]
 pub(crate) struct BuilderParams {
-    pub(crate) finish_fn: Option<syn::Ident>,
-    pub(crate) builder_type: Option<syn::Ident>,
+    /// Overrides the name and visibility of the finishing function, and
+    /// configures the function that transforms its output
+    pub(crate) finish_fn: Option<FinishFnParams>,
+
+    /// Overrides the name and visibility of the builder type
+    pub(crate) builder_type: Option<ItemParams>,
+
+    /// Visibility of all items generated for the builder. The visibility of
+    /// the underlying item is used by default.
+    pub(crate) vis: Option<syn::Visibility>,
+
+    /// Prefix that is prepended to the names of all setters
+    pub(crate) setter_prefix: Option<syn::Ident>,
+
+    /// Configures the setters of the optional members that accept an `Option`
+    pub(crate) maybe: Option<MaybeSetters>,
+
+    /// A trait that should be implemented for the builder by delegating to
+    /// the setters and the finishing function with the same names
+    pub(crate) implements: Option<ImplementsParams>,
+
+    /// Enables the generation of a function that accepts a closure to
+    /// configure the builder and finishes building in a single call.
+    pub(crate) closure_fn: Option<ClosureFnParams>,
+
+    /// A function that checks the values of the members before finishing
+    /// and makes the finishing function fallible.
+    pub(crate) validate: Option<ValidateParams>,
+
+    /// A function that receives mutable references to the values of all
+    /// members before the validation and finishing, e.g. to normalize them.
+    pub(crate) pre_build: Option<syn::Expr>,
+
+    /// Settings applied to all members of the types that match the pattern
+    #[darling(multiple)]
+    pub(crate) on: Vec<OnParams>,
+
+    /// Traits that should be implemented for the builder
+    #[darling(default)]
+    pub(crate) derive: BuilderDerives,
+
+    /// Generates the finishing function that takes the builder by reference
+    /// and clones the values of the members, so that the builder can be
+    /// finished multiple times
+    pub(crate) finish_fn_clone: darling::util::Flag,
+
+    /// Enables the runtime mode of the builder, where the members are set
+    /// by their names from dynamic values.
+    pub(crate) dynamic: darling::util::Flag,
+
+    /// Generates the `{BuilderName}Member` enum that lists all members
+    pub(crate) member_enum: darling::util::Flag,
+
+    /// Generates the `merge` method that combines two partially built builders
+    pub(crate) merge: darling::util::Flag,
+
+    /// Makes the setters of the optional members take `&mut self` and return
+    /// `&mut Self` instead of consuming the builder
+    pub(crate) mut_setters: darling::util::Flag,
+
+    /// Configures whether the required members are checked to be set at
+    /// compile time with the type state, or at runtime when finishing.
+    pub(crate) check: Option<SpannedValue<BuilderCheck>>,
+
+    /// Generates a dyn-compatible trait for the builder with runtime checks,
+    /// so that it can be used as a trait object
+    pub(crate) dyn_builder: darling::util::Flag,
+
+    /// Adds `#[track_caller]` to the finishing function, so that the panics
+    /// in it point to the place where the builder is finished.
+    pub(crate) track_caller: darling::util::Flag,
+
+    /// Keeps the leading underscore in the names of the setters of the members
+    /// whose names start with it
+    pub(crate) keep_leading_underscore: darling::util::Flag,
+
+    /// Path to the `bon` crate that the generated code references. It's
+    /// useful when `bon` is re-exported by another crate.
+    #[darling(rename = "crate", default, with = parse_crate_path)]
+    pub(crate) bon: Option<syn::Path>,
+
+    /// Declarations of the structs of the `#[builder(flatten)]` members. They
+    /// are passed to the macro by the callbacks generated for the builders of
+    /// the structs, and the last requested one comes first.
+    #[darling(rename = "__flatten", multiple)]
+    pub(crate) flattened: Vec<FlattenedStruct>,
 }
 
-#[derive(Debug, Default)]
+/// Configures the setters of the optional members that accept an `Option`.
+/// They're named with the `maybe_` prefix by default.
+#[derive(Debug, Clone)]
+pub(crate) enum MaybeSetters {
+    /// The setters aren't generated
+    Disabled,
+
+    /// The setters are named with the given prefix instead of `maybe_`
+    Prefix(syn::Ident),
+}
+
+impl FromMeta for MaybeSetters {
+    fn from_expr(expr: &syn::Expr) -> Result<Self> {
+        let bool = match expr {
+            syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Bool(bool),
+                ..
+            }) => bool,
+            syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(str),
+                ..
+            }) => return Ok(Self::Prefix(str.parse()?)),
+            _ => return Ok(Self::Prefix(syn::parse2(quote!(#expr))?)),
+        };
+
+        if bool.value {
+            bail!(
+                bool,
+                "The setters that accept an `Option` are generated by default, \
+                so `= true` is redundant. Use `= false` to disable them or \
+                `= prefix_` to change their prefix."
+            );
+        }
+
+        Ok(Self::Disabled)
+    }
+}
+
+/// Configures how the builder checks that the required members are set
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, FromMeta)]
+pub(crate) enum BuilderCheck {
+    /// The type state of the builder tracks which members are set, so that
+    /// finishing the builder without a required member doesn't compile
+    #[default]
+    #[darling(rename = "compile_time")]
+    CompileTime,
+
+    /// The builder has no type state, and finishing it without a required
+    /// member panics. This makes the generated code smaller and faster to compile.
+    #[darling(rename = "runtime")]
+    Runtime,
+}
+
+impl BuilderCheck {
+    /// Span of `check = "runtime"` if the builder checks the required members
+    /// at runtime instead of tracking them in the type state
+    pub(crate) fn runtime_span(check: Option<SpannedValue<Self>>) -> Option<proc_macro2::Span> {
+        check
+            .filter(|check| **check == Self::Runtime)
+            .map(|check| check.span())
+    }
+}
+
+#[derive(Debug, Clone, Default, FromMeta)]
+pub(crate) struct BuilderDerives {
+    #[darling(rename = "Clone")]
+    pub(crate) clone: darling::util::Flag,
+
+    #[darling(rename = "Debug")]
+    pub(crate) debug: darling::util::Flag,
+
+    #[darling(rename = "PartialEq")]
+    pub(crate) partial_eq: darling::util::Flag,
+
+    #[darling(rename = "Eq")]
+    pub(crate) eq: darling::util::Flag,
+
+    #[darling(rename = "Deserialize")]
+    pub(crate) deserialize: darling::util::Flag,
+
+    #[darling(rename = "Args")]
+    pub(crate) args: darling::util::Flag,
+
+    #[darling(rename = "JsonSchema")]
+    pub(crate) json_schema: darling::util::Flag,
+}
+
+#[derive(Debug, Clone, Default)]
 pub(crate) struct ItemParams {
     pub(crate) name: Option<syn::Ident>,
     pub(crate) vis: Option<syn::Visibility>,
@@ -17,8 +189,8 @@ pub(crate) struct ItemParams {
 impl FromMeta for ItemParams {
     fn from_meta(meta: &syn::Meta) -> Result<Self> {
         if let syn::Meta::NameValue(meta) = meta {
-            let val = &meta.value;
-            let name = syn::parse2(quote!(#val))?;
+            // Both identifiers and string literals are accepted
+            let name = syn::Ident::from_expr(&meta.value)?;
 
             return Ok(Self {
                 name: Some(name),
@@ -54,3 +226,459 @@ impl FromMeta for ItemParams {
         Ok(me)
     }
 }
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct FinishFnParams {
+    pub(crate) name: Option<syn::Ident>,
+    pub(crate) vis: Option<syn::Visibility>,
+
+    /// Function that transforms the output of the finishing function, e.g.
+    /// `Arc::new` to return the value wrapped in an `Arc`
+    pub(crate) map: Option<syn::Expr>,
+
+    /// Name of the additional finishing function that panics if the validation
+    /// fails instead of returning the error
+    pub(crate) unwrap: Option<syn::Ident>,
+}
+
+impl FromMeta for FinishFnParams {
+    fn from_meta(meta: &syn::Meta) -> Result<Self> {
+        if let syn::Meta::NameValue(_) = meta {
+            let ItemParams { name, vis } = ItemParams::from_meta(meta)?;
+            return Ok(Self {
+                name,
+                vis,
+                map: None,
+                unwrap: None,
+            });
+        }
+
+        #[derive(Debug, FromMeta)]
+        struct Full {
+            name: Option<syn::Ident>,
+            vis: Option<syn::Visibility>,
+            map: Option<syn::Expr>,
+            unwrap: Option<syn::Ident>,
+        }
+
+        let full = Full::from_meta(meta)?;
+
+        let is_empty = matches!(
+            full,
+            Full {
+                name: None,
+                vis: None,
+                map: None,
+                unwrap: None,
+            }
+        );
+
+        if is_empty {
+            bail!(meta, "expected at least one parameter in parentheses");
+        }
+
+        let me = Self {
+            name: full.name,
+            vis: full.vis,
+            map: full.map,
+            unwrap: full.unwrap,
+        };
+
+        Ok(me)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct ImplementsParams {
+    pub(crate) path: syn::Path,
+
+    /// Names of the setters and the finishing function that the trait declares.
+    /// Only the finishing function is mapped if the list isn't specified.
+    pub(crate) methods: Option<Vec<syn::Ident>>,
+}
+
+impl FromMeta for ImplementsParams {
+    fn from_meta(meta: &syn::Meta) -> Result<Self> {
+        if let syn::Meta::NameValue(_) = meta {
+            let path = parse_syntax(meta)?.ok_or_else(|| err!(meta, "expected a trait path"))?;
+            return Ok(Self {
+                path,
+                methods: None,
+            });
+        }
+
+        let (path, methods) =
+            meta.require_list()?
+                .parse_args_with(|input: syn::parse::ParseStream<'_>| {
+                    let path = input.parse::<syn::Path>()?;
+                    input.parse::<syn::Token![,]>()?;
+
+                    let key = input.parse::<syn::Ident>()?;
+                    if key != "methods" {
+                        return Err(syn::Error::new(key.span(), "expected `methods(...)`"));
+                    }
+
+                    let content;
+                    syn::parenthesized!(content in input);
+                    let methods = content
+                        .parse_terminated(<syn::Ident as syn::parse::Parse>::parse, syn::Token![,])?
+                        .into_iter()
+                        .collect::<Vec<_>>();
+
+                    input.parse::<Option<syn::Token![,]>>()?;
+
+                    Ok((path, methods))
+                })?;
+
+        Ok(Self {
+            path,
+            methods: Some(methods),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ClosureFnParams {
+    pub(crate) name: Option<syn::Ident>,
+    pub(crate) vis: Option<syn::Visibility>,
+}
+
+impl FromMeta for ClosureFnParams {
+    fn from_meta(meta: &syn::Meta) -> Result<Self> {
+        if let syn::Meta::Path(_) = meta {
+            return Ok(Self::default());
+        }
+
+        let ItemParams { name, vis } = ItemParams::from_meta(meta)?;
+
+        Ok(Self { name, vis })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct OnParams {
+    /// Type pattern that may contain `_` placeholders
+    pub(crate) type_pattern: syn::Type,
+
+    /// Enables or disables `Into` conversions for the members of the matching
+    /// types regardless of the default qualification rules
+    pub(crate) into: Option<SpannedValue<StrictBool>>,
+}
+
+impl FromMeta for OnParams {
+    fn from_meta(meta: &syn::Meta) -> Result<Self> {
+        let meta = meta.require_list()?;
+
+        let (type_pattern, rest) = meta.parse_args_with(|input: syn::parse::ParseStream<'_>| {
+            let type_pattern = input.parse::<syn::Type>()?;
+            let rest = if input.is_empty() {
+                TokenStream2::new()
+            } else {
+                input.parse::<syn::Token![,]>()?;
+                input.parse::<TokenStream2>()?
+            };
+            Ok((type_pattern, rest))
+        })?;
+
+        #[derive(Debug, FromMeta)]
+        struct Settings {
+            into: Option<SpannedValue<StrictBool>>,
+        }
+
+        let settings = Settings::from_list(&darling::ast::NestedMeta::parse_meta_list(rest)?)?;
+
+        if settings.into.is_none() {
+            bail!(
+                meta,
+                "expected at least one setting after the type pattern e.g. `on({}, into)`",
+                quote!(#type_pattern),
+            );
+        }
+
+        Ok(Self {
+            type_pattern,
+            into: settings.into,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct ValidateParams {
+    /// Function or closure that accepts references to the members
+    pub(crate) with: syn::Expr,
+
+    /// Error type returned by the finishing function
+    pub(crate) error: Option<syn::Type>,
+
+    /// The validation function returns a future that is awaited
+    pub(crate) is_async: bool,
+}
+
+impl FromMeta for ValidateParams {
+    fn from_meta(meta: &syn::Meta) -> Result<Self> {
+        if let syn::Meta::NameValue(meta) = meta {
+            return Ok(Self {
+                with: meta.value.clone(),
+                error: None,
+                is_async: false,
+            });
+        }
+
+        #[derive(Debug, FromMeta)]
+        struct Full {
+            with: Option<syn::Expr>,
+            async_with: Option<syn::Expr>,
+
+            #[darling(default, with = parse_syntax)]
+            error: Option<syn::Type>,
+        }
+
+        let Full {
+            with,
+            async_with,
+            error,
+        } = Full::from_meta(meta)?;
+
+        let (with, is_async) = match (with, async_with) {
+            (Some(with), None) => (with, false),
+            (None, Some(async_with)) => (async_with, true),
+            (Some(_), Some(async_with)) => bail!(
+                &async_with,
+                "`with` and `async_with` can't be specified together"
+            ),
+            (None, None) => bail!(
+                meta,
+                "expected either `with` or `async_with` to be specified"
+            ),
+        };
+
+        Ok(Self {
+            with,
+            error,
+            is_async,
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct StartFnParams {
+    pub(crate) name: Option<syn::Ident>,
+    pub(crate) vis: Option<syn::Visibility>,
+
+    /// The type on which the start function should be defined. By default,
+    /// it's defined on the type for which the builder is generated.
+    pub(crate) on: Option<syn::Type>,
+}
+
+impl FromMeta for StartFnParams {
+    fn from_meta(meta: &syn::Meta) -> Result<Self> {
+        if let syn::Meta::NameValue(_) = meta {
+            let ItemParams { name, vis } = ItemParams::from_meta(meta)?;
+            return Ok(Self {
+                name,
+                vis,
+                on: None,
+            });
+        }
+
+        #[derive(Debug, FromMeta)]
+        struct Full {
+            name: Option<syn::Ident>,
+            vis: Option<syn::Visibility>,
+
+            #[darling(default, with = parse_syntax)]
+            on: Option<syn::Type>,
+        }
+
+        let full = Full::from_meta(meta)?;
+
+        let is_empty = matches!(
+            full,
+            Full {
+                name: None,
+                vis: None,
+                on: None,
+            }
+        );
+
+        if is_empty {
+            bail!(meta, "expected at least one parameter in parentheses");
+        }
+
+        let me = Self {
+            name: full.name,
+            vis: full.vis,
+            on: full.on,
+        };
+
+        Ok(me)
+    }
+}
+
+/// Configures how the members of the struct of a `#[builder(flatten)]`
+/// member are lifted into the builder
+#[derive(Debug, Clone, Default)]
+pub(crate) struct FlattenParams {
+    /// Prefix that is prepended to the names of the lifted members with
+    /// an underscore between them
+    pub(crate) prefix: Option<syn::Ident>,
+}
+
+impl FromMeta for FlattenParams {
+    fn from_meta(meta: &syn::Meta) -> Result<Self> {
+        if let syn::Meta::Path(_) = meta {
+            return Ok(Self::default());
+        }
+
+        #[derive(Debug, FromMeta)]
+        struct Full {
+            prefix: syn::Ident,
+        }
+
+        let Full { prefix } = Full::from_meta(meta)?;
+
+        Ok(Self {
+            prefix: Some(prefix),
+        })
+    }
+}
+
+/// Declaration of the struct of a `#[builder(flatten)]` member passed in the
+/// `__flatten(...)` parameter. Only the fields and the generics are used.
+#[derive(Debug, Clone)]
+pub(crate) struct FlattenedStruct(pub(crate) syn::ItemStruct);
+
+impl FromMeta for FlattenedStruct {
+    fn from_meta(meta: &syn::Meta) -> Result<Self> {
+        Ok(Self(meta.require_list()?.parse_args()?))
+    }
+}
+
+/// Start function configured with `#[builder(preset(...))]` that creates
+/// the builder with some members already set
+#[derive(Debug, Clone)]
+pub(crate) struct PresetParams {
+    pub(crate) name: syn::Ident,
+
+    /// Members that are set by the preset and the expressions of their values
+    pub(crate) members: Vec<(syn::Ident, syn::Expr)>,
+}
+
+impl FromMeta for PresetParams {
+    fn from_list(items: &[darling::ast::NestedMeta]) -> Result<Self> {
+        let mut name = None;
+        let mut members: Vec<(syn::Ident, syn::Expr)> = vec![];
+
+        for item in items {
+            let darling::ast::NestedMeta::Meta(syn::Meta::NameValue(meta)) = item else {
+                bail!(item, "expected `name = ...` or `member = value`");
+            };
+
+            let Some(ident) = meta.path.get_ident() else {
+                bail!(&meta.path, "expected the name of a member");
+            };
+
+            if ident == "name" {
+                if name.is_some() {
+                    bail!(ident, "the name of the preset is already specified");
+                }
+
+                // Both identifiers and string literals are accepted
+                name = Some(syn::Ident::from_expr(&meta.value)?);
+                continue;
+            }
+
+            if members.iter().any(|(other, _)| other == ident) {
+                bail!(
+                    ident,
+                    "the member `{}` is already set in this preset",
+                    ident.raw_name()
+                );
+            }
+
+            members.push((ident.clone(), meta.value.clone()));
+        }
+
+        let Some(name) = name else {
+            return Err(Error::custom(
+                "expected the name of the preset e.g. `preset(name = localhost, ...)`",
+            ));
+        };
+
+        if members.is_empty() {
+            bail!(
+                &name,
+                "expected at least one member set by the preset e.g. `member = value`"
+            );
+        }
+
+        Ok(Self { name, members })
+    }
+}
+
+/// This primitive represents the syntax that accepts only two states:
+/// a word e.g. `#[attr(field)]` represents true, and an expression with
+/// `false` e.g. `#[attr(field = false)]` represents false. No other syntax
+/// is accepted. That's why it's called a "strict" bool.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct StrictBool {
+    pub(crate) value: bool,
+}
+
+impl FromMeta for StrictBool {
+    fn from_word() -> Result<Self> {
+        Ok(Self { value: true })
+    }
+
+    fn from_bool(value: bool) -> Result<Self> {
+        if !value {
+            return Ok(Self { value: false });
+        }
+
+        // Error span is set by default trait impl in the caller
+        Err(Error::custom(format_args!(
+            "No need to write `= true`. Just mentioning the attribute is enough \
+            to set it to `true`, so remove the `= true` part.",
+        )))
+    }
+}
+
+/// Parses the type or path from the `key = Type` syntax. Simple paths may be
+/// written directly, but the ones with generic arguments need to be wrapped in
+/// a string literal e.g. `key = "Type<u32>"` to be valid attribute syntax.
+pub(crate) fn parse_syntax<T: syn::parse::Parse>(meta: &syn::Meta) -> Result<Option<T>> {
+    let val = &meta.require_name_value()?.value;
+
+    let syntax = match val {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(str),
+            ..
+        }) => str.parse()?,
+        _ => syn::parse2(quote!(#val))?,
+    };
+
+    Ok(Some(syntax))
+}
+
+/// Parses the path to the `bon` crate. It must be absolute, because the generated
+/// code references it from the child modules as well.
+pub(crate) fn parse_crate_path(meta: &syn::Meta) -> Result<Option<syn::Path>> {
+    let Some(path) = parse_syntax::<syn::Path>(meta)? else {
+        return Ok(None);
+    };
+
+    let is_absolute = path.leading_colon.is_some()
+        || path
+            .segments
+            .first()
+            .is_some_and(|segment| segment.ident == "crate" || segment.ident == "$crate");
+
+    if !is_absolute {
+        bail!(
+            &path,
+            "Expected an absolute path that starts with `::`, `crate` or `$crate`"
+        );
+    }
+
+    Ok(Some(path))
+}
@@ -7,6 +7,7 @@ mod normalization;
 mod util;
 
 use proc_macro::TokenStream;
+use util::prelude::*;
 
 /// Can be placed on top of a free function or an associated method or a struct
 /// declaration. Generates a builder for the item beneath it.
@@ -47,12 +48,46 @@ pub fn builder(params: TokenStream, item: TokenStream) -> TokenStream {
         .into()
 }
 
+/// Derives a builder for the struct. This is an alternative to placing the
+/// `#[builder]` attribute on top of the struct. The top-level parameters of the
+/// builder are specified with the `#[builder(...)]` attributes on the struct.
+///
+/// See the [`builder`] macro docs for details.
+///
+/// # Quick example
+///
+/// ```rust ignore
+/// #[derive(bon::Builder)]
+/// #[builder(finish_fn = assemble)]
+/// struct User {
+///     name: String,
+///     age: Option<u32>,
+/// }
+///
+/// let user = User::builder()
+///     .name("Bon")
+///     .assemble();
+///
+/// assert_eq!(user.name, "Bon");
+/// ```
+#[proc_macro_derive(Builder, attributes(builder))]
+pub fn derive_builder(item: TokenStream) -> TokenStream {
+    syn::parse(item)
+        .map_err(Into::into)
+        .and_then(builder::generate_for_derive)
+        .unwrap_or_else(|err: Error| err.write_errors())
+        .into()
+}
+
 /// Companion macro for [`builder`]. You should place it on top of the `impl` block
 /// where you want to define methods with the [`builder`] macro.
 ///
 /// It provides the necessary context to the [`builder`] macros on top of the functions
 /// inside of the `impl` block. You'll get compile errors without that context.
 ///
+/// It can also be placed on top of a trait definition to generate builders for
+/// the provided methods of the trait.
+///
 /// For details on this macro including the reason why it's needed see this
 /// paragraph in the [overview](https://elastio.github.io/bon/docs/guide/overview#builder-for-an-associated-method).
 ///
@@ -95,8 +130,9 @@ pub fn builder(params: TokenStream, item: TokenStream) -> TokenStream {
 /// [`builder`]: macro@builder
 #[proc_macro_attribute]
 pub fn bon(params: TokenStream, item: TokenStream) -> TokenStream {
-    util::parse_attr_macro_input(params, item.clone())
-        .and_then(|(opts, item)| bon::generate(opts, item))
+    syn::parse(item.clone())
+        .map_err(Into::into)
+        .and_then(|item| bon::generate(params.into(), item))
         .unwrap_or_else(|err| error::error_into_token_stream(err, item.into()))
         .into()
 }
@@ -0,0 +1,317 @@
+use crate::util::prelude::*;
+use proc_macro2::TokenTree;
+use quote::quote;
+use syn::parse::Parser;
+use syn::punctuated::Punctuated;
+use syn::visit_mut::VisitMut;
+
+/// Name of the parameter that the values of the evaluated predicates of the
+/// `cfg_attr` attributes are passed back to the macro in.
+const CFG_RESULTS_PARAM: &str = "__cfgs";
+
+pub(crate) enum ExpandCfgOutput {
+    /// There were no `cfg_attr` attributes with the `builder` attributes in them
+    /// and no `cfg` attributes on the members, or all of them were already expanded.
+    Expanded {
+        params: TokenStream2,
+        item: Box<syn::Item>,
+    },
+
+    /// Proc macros can't evaluate the cfg predicates, so the macro must be invoked
+    /// once again via the callback that evaluates them with the `#[cfg]` attributes.
+    Recurse(TokenStream2),
+}
+
+pub(crate) struct ExpandCfg {
+    /// Name of the macro that should be invoked with the evaluated predicates.
+    /// It's resolved relative to the path of the `bon` crate.
+    pub(crate) macro_ident: syn::Ident,
+    pub(crate) params: TokenStream2,
+    pub(crate) item: syn::Item,
+}
+
+impl ExpandCfg {
+    /// Expands the `cfg_attr` attributes that contain the `builder` attributes
+    /// inside of the item. The compiler doesn't expand them in the input of an
+    /// attribute macro, but the macro needs to see the `builder` attributes.
+    /// The members disabled by their `cfg` attributes are removed from the item.
+    pub(crate) fn expand_cfg(mut self) -> Result<ExpandCfgOutput> {
+        let results = self.take_results()?;
+        let has_results = results.is_some();
+
+        let mut visitor = ExpandCfgAttrs {
+            predicates: vec![],
+            results: results.map(Vec::into_iter),
+            errors: Error::accumulator(),
+        };
+
+        visitor.visit_item_mut(&mut self.item);
+        visitor.errors.finish()?;
+
+        if has_results {
+            if let Some(unused) = visitor.results.and_then(|mut results| results.next()) {
+                bail!(
+                    &proc_macro2::Span::call_site(),
+                    "BUG: the cfg predicate value `{unused}` wasn't used"
+                );
+            }
+        }
+
+        if visitor.predicates.is_empty() {
+            return Ok(ExpandCfgOutput::Expanded {
+                params: self.params,
+                item: Box::new(self.item),
+            });
+        }
+
+        // The predicates are only collected in this case, so the item is unchanged
+        let Self {
+            macro_ident,
+            params,
+            item,
+        } = self;
+
+        let predicates = visitor.predicates;
+        let bon = bon_path(&params);
+
+        Ok(ExpandCfgOutput::Recurse(quote! {
+            #bon::__eval_cfg_callback! {
+                {}
+                ( #( (#predicates) )* )
+                ( #bon::#macro_ident )
+                ( #params )
+                #item
+            }
+        }))
+    }
+
+    /// Takes the values of the predicates evaluated by the callback from the
+    /// beginning of the macro's parameters if the macro was invoked by it.
+    fn take_results(&mut self) -> Result<Option<Vec<bool>>> {
+        let mut tokens = std::mem::take(&mut self.params).into_iter();
+
+        let results = match (tokens.next(), tokens.next()) {
+            (Some(TokenTree::Ident(ident)), Some(TokenTree::Group(group)))
+                if ident == CFG_RESULTS_PARAM =>
+            {
+                group
+            }
+            (first, second) => {
+                self.params = first.into_iter().chain(second).chain(tokens).collect();
+                return Ok(None);
+            }
+        };
+
+        let results = Punctuated::<syn::LitBool, syn::Token![,]>::parse_terminated
+            .parse2(results.stream())?
+            .into_iter()
+            .map(|lit| lit.value)
+            .collect();
+
+        // Skip the comma that separates the results from the rest of the parameters
+        let mut tokens = tokens.peekable();
+        if let Some(TokenTree::Punct(punct)) = tokens.peek() {
+            if punct.as_char() == ',' {
+                tokens.next();
+            }
+        }
+
+        self.params = tokens.collect();
+
+        Ok(Some(results))
+    }
+}
+
+/// Path to the `bon` crate from the `crate = ...` parameter of the macro if
+/// there is one. The errors are ignored here, because the parameters are
+/// validated anyway once the `cfg_attr` attributes are expanded.
+pub(super) fn bon_path(params: &TokenStream2) -> syn::Path {
+    let metas = darling::ast::NestedMeta::parse_meta_list(params.clone()).unwrap_or_default();
+
+    metas
+        .iter()
+        .find_map(|meta| {
+            let darling::ast::NestedMeta::Meta(syn::Meta::NameValue(meta)) = meta else {
+                return None;
+            };
+
+            if !meta.path.is_ident("crate") {
+                return None;
+            }
+
+            match &meta.value {
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(str),
+                    ..
+                }) => str.parse().ok(),
+                value => syn::parse2(quote!(#value)).ok(),
+            }
+        })
+        .unwrap_or_else(|| syn::parse_quote!(::bon))
+}
+
+struct ExpandCfgAttrs {
+    /// Predicates of the `cfg_attr` attributes that are yet to be evaluated
+    predicates: Vec<syn::Meta>,
+
+    /// Values of the evaluated predicates in the same order in which the
+    /// `cfg_attr` attributes are visited
+    results: Option<std::vec::IntoIter<bool>>,
+
+    errors: darling::error::Accumulator,
+}
+
+enum ExpandedAttr {
+    /// The attribute must be left as is
+    Unchanged,
+
+    /// The attribute is replaced with the given attributes
+    Replaced(Vec<syn::Meta>),
+
+    /// The `#[cfg(...)]` predicate is false, so the member must be removed
+    Disabled,
+}
+
+impl ExpandCfgAttrs {
+    fn expand(&mut self, attrs: &mut Vec<syn::Attribute>) {
+        self.expand_impl(attrs, false);
+    }
+
+    /// Returns `false` if the attributes contain a `#[cfg(...)]` predicate that
+    /// is false, in which case the member must be removed.
+    fn expand_member(&mut self, attrs: &mut Vec<syn::Attribute>) -> bool {
+        self.expand_impl(attrs, true)
+    }
+
+    /// The `#[cfg(...)]` attributes are evaluated only for the members. The
+    /// ones on the items are left to the compiler.
+    fn expand_impl(&mut self, attrs: &mut Vec<syn::Attribute>, is_member: bool) -> bool {
+        let mut expanded = Vec::with_capacity(attrs.len());
+        let mut enabled = true;
+
+        for attr in std::mem::take(attrs) {
+            let result = self.expand_attr(&attr, is_member);
+            let Some(result) = self.errors.handle(result) else {
+                expanded.push(attr);
+                continue;
+            };
+
+            match result {
+                ExpandedAttr::Unchanged => expanded.push(attr),
+                ExpandedAttr::Replaced(metas) => {
+                    expanded.extend(metas.into_iter().map(|meta| syn::Attribute {
+                        meta,
+                        ..attr.clone()
+                    }));
+                }
+                ExpandedAttr::Disabled => enabled = false,
+            }
+        }
+
+        *attrs = expanded;
+
+        enabled
+    }
+
+    fn expand_attr(&mut self, attr: &syn::Attribute, is_member: bool) -> Result<ExpandedAttr> {
+        // The builder is generated only for the members that are enabled by
+        // their `#[cfg(...)]` attributes. The callback evaluates the predicates
+        // one by one, so the builder is generated only once no matter how many
+        // predicates there are.
+        if is_member && attr.path().is_ident("cfg") {
+            let predicate = attr.meta.require_list()?.parse_args()?;
+
+            let Some(results) = &mut self.results else {
+                self.predicates.push(predicate);
+                return Ok(ExpandedAttr::Unchanged);
+            };
+
+            let Some(result) = results.next() else {
+                bail!(attr, "BUG: the value of the cfg predicate is missing");
+            };
+
+            return Ok(if result {
+                ExpandedAttr::Replaced(vec![])
+            } else {
+                ExpandedAttr::Disabled
+            });
+        }
+
+        if !attr.path().is_ident("cfg_attr") {
+            return Ok(ExpandedAttr::Unchanged);
+        }
+
+        let mut metas = attr
+            .meta
+            .require_list()?
+            .parse_args_with(Punctuated::<syn::Meta, syn::Token![,]>::parse_terminated)?
+            .into_iter();
+
+        let Some(predicate) = metas.next() else {
+            return Ok(ExpandedAttr::Unchanged);
+        };
+
+        let metas: Vec<_> = metas.collect();
+
+        if !metas.iter().any(|meta| meta.path().is_ident("builder")) {
+            return Ok(ExpandedAttr::Unchanged);
+        }
+
+        let Some(results) = &mut self.results else {
+            self.predicates.push(predicate);
+            return Ok(ExpandedAttr::Unchanged);
+        };
+
+        let Some(result) = results.next() else {
+            bail!(attr, "BUG: the value of the cfg predicate is missing");
+        };
+
+        Ok(ExpandedAttr::Replaced(if result { metas } else { vec![] }))
+    }
+
+    /// Removes the fields that are disabled by their `#[cfg(...)]` attributes
+    fn expand_fields(&mut self, fields: &mut syn::Fields) {
+        let fields = match fields {
+            syn::Fields::Named(fields) => &mut fields.named,
+            syn::Fields::Unnamed(fields) => &mut fields.unnamed,
+            syn::Fields::Unit => return,
+        };
+
+        *fields = std::mem::take(fields)
+            .into_iter()
+            .filter_map(|mut field| self.expand_member(&mut field.attrs).then_some(field))
+            .collect();
+    }
+}
+
+impl VisitMut for ExpandCfgAttrs {
+    fn visit_item_struct_mut(&mut self, item: &mut syn::ItemStruct) {
+        self.expand(&mut item.attrs);
+        self.expand_fields(&mut item.fields);
+    }
+
+    // The bodies of the functions aren't visited, because the items nested
+    // in them are processed by their own macro invocations
+
+    fn visit_item_fn_mut(&mut self, func: &mut syn::ItemFn) {
+        self.expand(&mut func.attrs);
+        self.visit_signature_mut(&mut func.sig);
+    }
+
+    fn visit_impl_item_fn_mut(&mut self, func: &mut syn::ImplItemFn) {
+        self.expand(&mut func.attrs);
+        self.visit_signature_mut(&mut func.sig);
+    }
+
+    fn visit_trait_item_fn_mut(&mut self, func: &mut syn::TraitItemFn) {
+        self.expand(&mut func.attrs);
+        self.visit_signature_mut(&mut func.sig);
+    }
+
+    fn visit_signature_mut(&mut self, sig: &mut syn::Signature) {
+        sig.inputs = std::mem::take(&mut sig.inputs)
+            .into_iter()
+            .filter_map(|mut arg| self.expand_member(arg.attrs_mut()).then_some(arg))
+            .collect();
+    }
+}
@@ -0,0 +1,192 @@
+use super::cfg::bon_path;
+use crate::util::prelude::*;
+use darling::ast::NestedMeta;
+use quote::{quote, ToTokens};
+
+/// Name of the parameter that the declaration of the struct of a
+/// `#[builder(flatten)]` member is passed to the macro in.
+const FLATTENED_PARAM: &str = "__flatten";
+
+pub(crate) enum ExpandFlattenOutput {
+    /// There are no `#[builder(flatten)]` members, or the declarations of the
+    /// structs of all of them were already passed to the macro.
+    Expanded {
+        params: TokenStream2,
+        item: Box<syn::Item>,
+    },
+
+    /// The macro sees only the tokens of the item it's placed on, so it must be
+    /// invoked once again via the callback generated for the builder of the
+    /// struct of the next `#[builder(flatten)]` member to see its fields.
+    Recurse(TokenStream2),
+}
+
+pub(crate) struct ExpandFlatten {
+    /// Name of the macro that the callback should invoke. It's resolved
+    /// relative to the path of the `bon` crate.
+    pub(crate) macro_ident: syn::Ident,
+    pub(crate) params: TokenStream2,
+    pub(crate) item: syn::Item,
+}
+
+impl ExpandFlatten {
+    /// Requests the declarations of the structs of the `#[builder(flatten)]`
+    /// members one by one. Every callback puts the declaration of its struct
+    /// into the `__flatten(...)` parameter before the other parameters.
+    pub(crate) fn expand_flatten(self) -> Result<ExpandFlattenOutput> {
+        // The errors are reported once the parameters are parsed for real
+        let Ok(params) = NestedMeta::parse_meta_list(self.params.clone()) else {
+            return Ok(self.expanded());
+        };
+
+        let passed = params
+            .iter()
+            .filter(|param| {
+                matches!(param, NestedMeta::Meta(meta) if meta.path().is_ident(FLATTENED_PARAM))
+            })
+            .count();
+
+        let extends = params
+            .iter()
+            .find_map(|param| match param {
+                NestedMeta::Meta(meta) if meta.path().is_ident("extends") => Some(meta),
+                _ => None,
+            })
+            .map(crate::builder::parse_syntax::<syn::Type>)
+            .transpose()?
+            .flatten();
+
+        let member_types = flattened_member_types(&self.item, extends.as_ref())?;
+
+        let Some(ty) = member_types.get(passed) else {
+            return Ok(self.expanded());
+        };
+
+        let callback = callback_path(ty)?;
+
+        let Self {
+            macro_ident,
+            params,
+            item,
+        } = self;
+
+        let bon = bon_path(&params);
+
+        Ok(ExpandFlattenOutput::Recurse(quote! {
+            #callback! {
+                ( #bon::#macro_ident )
+                ( #params )
+                #item
+            }
+        }))
+    }
+
+    fn expanded(self) -> ExpandFlattenOutput {
+        ExpandFlattenOutput::Expanded {
+            params: self.params,
+            item: Box::new(self.item),
+        }
+    }
+}
+
+/// Name of the `macro_rules!` callback generated for the builder of the struct
+/// that passes the declaration of the struct to the macro that invokes it.
+pub(crate) fn flatten_callback_ident(struct_ident: &syn::Ident) -> syn::Ident {
+    quote::format_ident!(
+        "__bon_flatten_{}",
+        struct_ident.raw_name(),
+        span = struct_ident.span()
+    )
+}
+
+/// Checks if the field stores the base struct of `#[builder(extends = ...)]`.
+/// The types are compared syntactically, because the macro can't resolve them.
+pub(crate) fn is_extended_type(ty: &syn::Type, extends: &syn::Type) -> bool {
+    ty.to_token_stream().to_string() == extends.to_token_stream().to_string()
+}
+
+/// Types of the `#[builder(flatten)]` members in the order of their declaration.
+/// This includes the field of the base struct of `#[builder(extends = ...)]`.
+fn flattened_member_types<'a>(
+    item: &'a syn::Item,
+    extends: Option<&syn::Type>,
+) -> Result<Vec<&'a syn::Type>> {
+    let members: Vec<_> = match item {
+        syn::Item::Struct(item) => item
+            .fields
+            .iter()
+            .map(|field| (&field.attrs, &field.ty))
+            .collect(),
+        syn::Item::Fn(item) => item
+            .sig
+            .inputs
+            .iter()
+            .filter_map(syn::FnArg::as_typed)
+            .map(|arg| (&arg.attrs, &*arg.ty))
+            .collect(),
+        _ => return Ok(vec![]),
+    };
+
+    let mut types = vec![];
+
+    for (attrs, ty) in members {
+        let is_extended = extends.is_some_and(|extends| is_extended_type(ty, extends));
+
+        if is_extended || has_flatten_attr(attrs)? {
+            types.push(ty);
+        }
+    }
+
+    Ok(types)
+}
+
+fn has_flatten_attr(attrs: &[syn::Attribute]) -> Result<bool> {
+    for attr in attrs {
+        if !attr.path().is_ident("builder") {
+            continue;
+        }
+
+        let is_flatten = attr
+            .parse_nested_metas()?
+            .iter()
+            .any(|meta| matches!(meta, NestedMeta::Meta(meta) if meta.path().is_ident("flatten")));
+
+        if is_flatten {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
+/// The callback is imported into the module of the struct, so it's available
+/// by the same path as the struct, but with a different last segment.
+fn callback_path(ty: &syn::Type) -> Result<syn::Path> {
+    let path = match ty {
+        syn::Type::Path(path) if path.qself.is_none() => &path.path,
+        _ => bail!(
+            ty,
+            "#[builder(flatten)] requires the type of the member to be a path \
+            to a struct with the #[builder] attribute on top of it"
+        ),
+    };
+
+    let mut path = path.clone();
+
+    let Some(last) = path.segments.last_mut() else {
+        bail!(ty, "BUG: the path of the type has no segments");
+    };
+
+    if !last.arguments.is_none() {
+        bail!(
+            &last.arguments,
+            "the struct of a #[builder(flatten)] member can't have generic \
+            parameters, because the types of its fields would have to be \
+            specialized with the generic arguments of the member's type"
+        );
+    }
+
+    last.ident = flatten_callback_ident(&last.ident);
+
+    Ok(path)
+}
@@ -1,7 +1,13 @@
+mod cfg;
+mod flatten;
 mod impl_traits;
 mod lifetimes;
 mod self_ty;
 
+pub(crate) use cfg::{ExpandCfg, ExpandCfgOutput};
+pub(crate) use flatten::{
+    flatten_callback_ident, is_extended_type, ExpandFlatten, ExpandFlattenOutput,
+};
 pub(crate) use impl_traits::NormalizeImplTraits;
 pub(crate) use lifetimes::NormalizeLifetimes;
 pub(crate) use self_ty::NormalizeSelfTy;
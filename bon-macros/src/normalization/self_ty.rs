@@ -45,8 +45,27 @@ impl VisitMut for NormalizeSelfTy<'_> {
     fn visit_type_path_mut(&mut self, type_path: &mut syn::TypePath) {
         syn::visit_mut::visit_type_path_mut(self, type_path);
 
-        let syn::TypePath { qself, path } = type_path;
+        let span = type_path.span();
+        self.normalize_self_projection(&mut type_path.qself, &mut type_path.path, span);
+    }
 
+    fn visit_expr_path_mut(&mut self, expr_path: &mut syn::ExprPath) {
+        syn::visit_mut::visit_expr_path_mut(self, expr_path);
+
+        // Expressions may reference associated items of `Self` such as
+        // `Self::CONST` or `Self::new()` e.g. in default values of members
+        let span = expr_path.span();
+        self.normalize_self_projection(&mut expr_path.qself, &mut expr_path.path, span);
+    }
+}
+
+impl NormalizeSelfTy<'_> {
+    fn normalize_self_projection(
+        &self,
+        qself: &mut Option<syn::QSelf>,
+        path: &mut syn::Path,
+        span: proc_macro2::Span,
+    ) {
         let is_self_projection =
             qself.is_none() && path.starts_with_segment("Self") && path.segments.len() > 1;
 
@@ -61,10 +80,11 @@ impl VisitMut for NormalizeSelfTy<'_> {
             .skip(1)
             .collect();
 
-        let span = type_path.span();
+        // The `::` after the `<Type>` is represented as a leading colon of the path
+        path.leading_colon = Some(syn::Token![::](span));
 
         // QSelf doesn't implement `Parse` trait
-        type_path.qself = Some(syn::QSelf {
+        *qself = Some(syn::QSelf {
             lt_token: syn::Token![<](span),
             ty: Box::new(self.self_ty.clone()),
             position: 0,
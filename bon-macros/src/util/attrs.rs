@@ -1,4 +1,7 @@
+use crate::util::prelude::*;
+use darling::ast::NestedMeta;
 use easy_ext::ext;
+use syn::punctuated::Punctuated;
 
 #[ext(AttributeExt)]
 pub(crate) impl syn::Attribute {
@@ -20,4 +23,43 @@ pub(crate) impl syn::Attribute {
 
         Some(&attr.value)
     }
+
+    /// Returns `true` if the attribute represents a `#[doc(cfg(...))]` attribute,
+    /// that describes the conditions under which the item is available in the docs.
+    fn is_doc_cfg(&self) -> bool {
+        let syn::Meta::List(attr) = &self.meta else {
+            return false;
+        };
+
+        if !attr.path.is_ident("doc") {
+            return false;
+        }
+
+        attr.parse_args::<syn::Meta>()
+            .is_ok_and(|meta| meta.path().is_ident("cfg"))
+    }
+
+    /// Parses the parameters of an attribute like `#[builder(...)]`. The attribute
+    /// without parentheses is treated as one with an empty list of parameters.
+    ///
+    /// The errors about an unexpected end of the parameters point to the closing
+    /// parenthesis of the attribute, unlike the ones from `darling`, which point
+    /// to the call site of the macro because it parses the tokens without their
+    /// delimiters.
+    fn parse_nested_metas(&self) -> Result<Vec<NestedMeta>> {
+        let list = match &self.meta {
+            syn::Meta::Path(_) => return Ok(vec![]),
+            syn::Meta::List(list) => list,
+            syn::Meta::NameValue(meta) => bail!(
+                meta,
+                "Name-value arguments are not supported. Use #[{}(...)]",
+                darling::util::path_to_string(&meta.path),
+            ),
+        };
+
+        let metas =
+            list.parse_args_with(Punctuated::<NestedMeta, syn::Token![,]>::parse_terminated)?;
+
+        Ok(metas.into_iter().collect())
+    }
 }
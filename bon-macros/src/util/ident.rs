@@ -1,5 +1,5 @@
 use easy_ext::ext;
-use heck::ToPascalCase;
+use heck::{ToPascalCase, ToSnakeCase};
 use proc_macro2::Span;
 
 #[ext(IdentExt)]
@@ -23,17 +23,47 @@ pub(crate) impl syn::Ident {
         // is anyway not allowed even as a raw identifier:
         // https://internals.rust-lang.org/t/raw-identifiers-dont-work-for-all-identifiers/9094
         //
-        // So no need to handle raw identifiers here.
-        syn::Ident::new(&self.raw_name().to_pascal_case(), Span::call_site())
+        // So no need to handle raw identifiers here. The `Self` name is produced
+        // for the idents like `_self`, so it gets a trailing underscore instead.
+        let name = self.raw_name().to_pascal_case();
+        let name = if name == "Self" {
+            "Self_".to_owned()
+        } else {
+            name
+        };
+
+        syn::Ident::new(&name, Span::call_site())
+    }
+
+    /// Converts the ident to snake_case without preserving its span. See
+    /// [`IdentExt::to_pascal_case`] for the reasoning behind the span loss.
+    ///
+    /// If the resulting name is a keyword, then a raw identifier is created.
+    /// The keywords that can't be raw identifiers (`self`, `super`, `crate`)
+    /// get a trailing underscore instead.
+    fn to_snake_case(&self) -> Self {
+        let name = self.raw_name().to_snake_case();
+
+        if let Ok(ident) = syn::parse_str(&name) {
+            return ident;
+        }
+
+        if matches!(name.as_str(), "self" | "super" | "crate") {
+            return syn::Ident::new(&format!("{name}_"), Span::call_site());
+        }
+
+        syn::Ident::new_raw(&name, Span::call_site())
     }
 
     /// Creates a new ident with the given name and span. If the name starts with
-    /// `r#` then automatically creates a raw ident.
+    /// `r#` or it's a keyword then automatically creates a raw ident.
     fn new_maybe_raw(name: &str, span: Span) -> Self {
-        if let Some(name) = name.strip_prefix("r#") {
-            syn::Ident::new_raw(name, span)
-        } else {
+        let name = name.strip_prefix("r#").unwrap_or(name);
+
+        if syn::parse_str::<syn::Ident>(name).is_ok() {
             syn::Ident::new(name, span)
+        } else {
+            syn::Ident::new_raw(name, span)
         }
     }
 
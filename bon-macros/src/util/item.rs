@@ -0,0 +1,29 @@
+use easy_ext::ext;
+
+#[ext(ItemExt)]
+pub(crate) impl syn::Item {
+    /// Returns the attributes of the item. There are no attributes only
+    /// for the [`syn::Item::Verbatim`] items, that syn can't parse.
+    fn attrs_mut(&mut self) -> Option<&mut Vec<syn::Attribute>> {
+        let attrs = match self {
+            syn::Item::Const(item) => &mut item.attrs,
+            syn::Item::Enum(item) => &mut item.attrs,
+            syn::Item::ExternCrate(item) => &mut item.attrs,
+            syn::Item::Fn(item) => &mut item.attrs,
+            syn::Item::ForeignMod(item) => &mut item.attrs,
+            syn::Item::Impl(item) => &mut item.attrs,
+            syn::Item::Macro(item) => &mut item.attrs,
+            syn::Item::Mod(item) => &mut item.attrs,
+            syn::Item::Static(item) => &mut item.attrs,
+            syn::Item::Struct(item) => &mut item.attrs,
+            syn::Item::Trait(item) => &mut item.attrs,
+            syn::Item::TraitAlias(item) => &mut item.attrs,
+            syn::Item::Type(item) => &mut item.attrs,
+            syn::Item::Union(item) => &mut item.attrs,
+            syn::Item::Use(item) => &mut item.attrs,
+            _ => return None,
+        };
+
+        Some(attrs)
+    }
+}
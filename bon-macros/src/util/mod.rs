@@ -1,11 +1,10 @@
 mod attrs;
 mod fn_arg;
 mod ident;
+mod item;
 mod path;
 mod ty;
-
-use prelude::*;
-use proc_macro::TokenStream;
+mod vis;
 
 pub(crate) mod prelude {
     /// A handly alias for [`proc_macro2::TokenStream`].
@@ -24,27 +23,13 @@ pub(crate) mod prelude {
     pub(crate) use super::attrs::AttributeExt;
     pub(crate) use super::fn_arg::FnArgExt;
     pub(crate) use super::ident::IdentExt;
+    pub(crate) use super::item::ItemExt;
     pub(crate) use super::path::PathExt;
     pub(crate) use super::ty::TypeExt;
+    pub(crate) use super::vis::VisibilityExt;
     pub(crate) use super::{bail, err};
 }
 
-/// Parse a pair of [`proc_macro::TokenStream`] that an attribute macro accepts
-/// into a structured input.
-pub(crate) fn parse_attr_macro_input<Params, Item>(
-    params: TokenStream,
-    item: TokenStream,
-) -> Result<(Params, Item)>
-where
-    Params: darling::FromMeta,
-    Item: syn::parse::Parse,
-{
-    let meta = darling::ast::NestedMeta::parse_meta_list(params.into())?;
-    let params = Params::from_list(&meta)?;
-    let item = syn::parse(item)?;
-    Ok((params, item))
-}
-
 /// Inspired by `anyhow::bail`, but returns a [`Result`] with [`darling::Error`].
 /// It accepts the value that implements [`syn::spanned::Spanned`] to attach the
 /// span to the error.
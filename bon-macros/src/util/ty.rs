@@ -29,25 +29,34 @@ pub(crate) impl syn::Type {
 
     /// Detects if the type is `desired_type` and returns its generic type parameter
     fn type_param(&self, desired_type: &str) -> Option<&syn::Type> {
+        self.type_args(desired_type)?.first().copied()
+    }
+
+    /// Detects if the type is `desired_type` and returns its generic type
+    /// arguments. Lifetimes and const arguments are skipped.
+    fn type_args(&self, desired_type: &str) -> Option<Vec<&syn::Type>> {
         let path = self.as_path()?;
 
-        let vec_segment = path
+        let segment = path
             .path
             .segments
             .iter()
             .find(|&segment| segment.ident == desired_type)?;
 
-        let syn::PathArguments::AngleBracketed(args) = &vec_segment.arguments else {
+        let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
             return None;
         };
 
-        let arg = args.args.first()?;
-
-        let syn::GenericArgument::Type(arg) = arg else {
-            return None;
-        };
+        let args = args
+            .args
+            .iter()
+            .filter_map(|arg| match arg {
+                syn::GenericArgument::Type(arg) => Some(arg),
+                _ => None,
+            })
+            .collect();
 
-        Some(arg)
+        Some(args)
     }
 
     /// Detects if the type is [`Option`] and returns its generic type parameter
@@ -60,6 +69,37 @@ pub(crate) impl syn::Type {
         self.is_final_segment("Option")
     }
 
+    /// Checks if the type matches the given pattern. The pattern is a type that
+    /// may contain `_` placeholders, each of them matches any type. Lifetimes are
+    /// ignored during the comparison, and paths must be written the same way.
+    fn matches(&self, pattern: &syn::Type) -> bool {
+        match (self.peel(), pattern.peel()) {
+            (_, Self::Infer(_)) => true,
+            (Self::Path(ty), Self::Path(pattern)) => {
+                ty.qself.is_none()
+                    && pattern.qself.is_none()
+                    && path_matches(&ty.path, &pattern.path)
+            }
+            (Self::Reference(ty), Self::Reference(pattern)) => {
+                ty.mutability.is_some() == pattern.mutability.is_some()
+                    && ty.elem.matches(&pattern.elem)
+            }
+            (Self::Slice(ty), Self::Slice(pattern)) => ty.elem.matches(&pattern.elem),
+            (Self::Array(ty), Self::Array(pattern)) => {
+                ty.elem.matches(&pattern.elem) && tokens_eq(&ty.len, &pattern.len)
+            }
+            (Self::Tuple(ty), Self::Tuple(pattern)) => {
+                ty.elems.len() == pattern.elems.len()
+                    && ty
+                        .elems
+                        .iter()
+                        .zip(&pattern.elems)
+                        .all(|(ty, pattern)| ty.matches(pattern))
+            }
+            (ty, pattern) => tokens_eq(ty, pattern),
+        }
+    }
+
     /// Recursively strips the [`syn::Type::Group`] and [`syn::Type::Paren`] wrappers
     fn peel(&self) -> &Self {
         match self {
@@ -68,4 +108,80 @@ pub(crate) impl syn::Type {
             _ => self,
         }
     }
+
+    /// Renders the type the way it's usually written in the source code,
+    /// without the spaces that the [`Display`] impl of the tokens puts
+    /// between the punctuation.
+    ///
+    /// [`Display`]: std::fmt::Display
+    fn to_source_string(&self) -> String {
+        use quote::ToTokens;
+
+        let replacements = [
+            (" :: ", "::"),
+            (":: ", "::"),
+            (" <", "<"),
+            ("< ", "<"),
+            (" >", ">"),
+            (" ,", ","),
+            ("& ", "&"),
+            ("( ", "("),
+            (" )", ")"),
+            ("[ ", "["),
+            (" ]", "]"),
+            (" ;", ";"),
+        ];
+
+        replacements
+            .iter()
+            .fold(self.to_token_stream().to_string(), |output, (from, to)| {
+                output.replace(from, to)
+            })
+    }
+}
+
+fn path_matches(path: &syn::Path, pattern: &syn::Path) -> bool {
+    if path.leading_colon.is_some() != pattern.leading_colon.is_some()
+        || path.segments.len() != pattern.segments.len()
+    {
+        return false;
+    }
+
+    path.segments
+        .iter()
+        .zip(&pattern.segments)
+        .all(|(segment, pattern)| {
+            if segment.ident != pattern.ident {
+                return false;
+            }
+
+            let (
+                syn::PathArguments::AngleBracketed(args),
+                syn::PathArguments::AngleBracketed(pattern),
+            ) = (&segment.arguments, &pattern.arguments)
+            else {
+                return tokens_eq(&segment.arguments, &pattern.arguments);
+            };
+
+            let is_not_lifetime =
+                |arg: &&syn::GenericArgument| !matches!(arg, syn::GenericArgument::Lifetime(_));
+
+            let args: Vec<_> = args.args.iter().filter(is_not_lifetime).collect();
+            let pattern: Vec<_> = pattern.args.iter().filter(is_not_lifetime).collect();
+
+            args.len() == pattern.len()
+                && args
+                    .iter()
+                    .zip(pattern)
+                    .all(|(arg, pattern)| match (arg, pattern) {
+                        (syn::GenericArgument::Type(arg), syn::GenericArgument::Type(pattern)) => {
+                            arg.matches(pattern)
+                        }
+                        (arg, pattern) => tokens_eq(arg, pattern),
+                    })
+        })
+}
+
+fn tokens_eq(left: &impl quote::ToTokens, right: &impl quote::ToTokens) -> bool {
+    left.to_token_stream().to_string() == right.to_token_stream().to_string()
 }
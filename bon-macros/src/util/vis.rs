@@ -0,0 +1,46 @@
+use crate::util::prelude::*;
+use easy_ext::ext;
+
+#[ext(VisibilityExt)]
+pub(crate) impl syn::Visibility {
+    /// Returns the visibility that grants the same access as this one when it's
+    /// used on an item inside of a child module. It's required, because the
+    /// relative visibilities like `pub(super)` or the private visibility are
+    /// resolved relative to the module where the item is defined.
+    ///
+    /// For example, the private visibility in the parent module is equivalent
+    /// to `pub(super)` in the child module.
+    fn into_equivalent_in_child_module(mut self) -> Result<syn::Visibility> {
+        match &mut self {
+            syn::Visibility::Public(_) => Ok(self),
+            syn::Visibility::Inherited => Ok(syn::parse_quote!(pub(super))),
+            syn::Visibility::Restricted(vis) => {
+                let first = vis
+                    .path
+                    .segments
+                    .first()
+                    .map(|segment| segment.ident.to_string());
+
+                match first.as_deref() {
+                    // `pub(crate)` and `pub(in crate::...)` are absolute
+                    _ if vis.path.leading_colon.is_some() => Ok(self),
+                    Some("crate") => Ok(self),
+                    Some("self") => {
+                        let segment = &mut vis.path.segments[0];
+                        segment.ident = syn::Ident::new("super", segment.ident.span());
+                        Ok(self)
+                    }
+                    Some("super") => {
+                        let path = &vis.path;
+                        Ok(syn::parse_quote!(pub(in super::#path)))
+                    }
+                    _ => bail!(
+                        &vis.path,
+                        "Expected `crate`, `self` or `super` at the start \
+                        of the visibility path"
+                    ),
+                }
+            }
+        }
+    }
+}
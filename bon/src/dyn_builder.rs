@@ -0,0 +1,70 @@
+//! Dyn-compatible traits of the builders generated with `#[builder(dyn_builder)]`.
+//!
+//! The builder with `#[builder(check = "runtime")]` doesn't have a type state,
+//! so it can additionally implement a dyn-compatible trait. The trait is named
+//! after the builder with the `Dyn` prefix before the `Builder` suffix, e.g.
+//! `ConfigDynBuilder` for `ConfigBuilder`. It has a `set_` method for every
+//! member and a fallible finishing function that takes `self: Box<Self>`, so
+//! the builder can be stored and passed around as a trait object:
+//!
+//! ```
+//! use bon::builder;
+//!
+//! #[builder(check = "runtime", dyn_builder)]
+//! struct Config {
+//!     name: String,
+//!     port: Option<u16>,
+//! }
+//!
+//! fn configure(builder: &mut dyn ConfigDynBuilder) {
+//!     builder.set_port(8080);
+//! }
+//!
+//! let mut builder: Box<dyn ConfigDynBuilder> = Box::new(Config::builder());
+//! configure(&mut *builder);
+//! builder.set_name("server".to_owned());
+//!
+//! let config = builder.try_build().unwrap();
+//!
+//! assert_eq!(config.name, "server");
+//! assert_eq!(config.port, Some(8080));
+//! ```
+
+use crate::BoxError;
+use std::fmt;
+
+/// Error returned by the finishing function of the dyn-compatible trait.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// The required member wasn't set before finishing building.
+    MissingMember {
+        /// Name of the member
+        name: &'static str,
+    },
+
+    /// The values of the members didn't pass the validation configured with
+    /// `#[builder(validate = ...)]`.
+    Validation {
+        /// The error returned by the regular finishing function
+        source: BoxError,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingMember { name } => write!(f, "the member `{name}` wasn't set"),
+            Self::Validation { source } => write!(f, "validation failed: {source}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Validation { source } => Some(&**source),
+            Self::MissingMember { .. } => None,
+        }
+    }
+}
@@ -0,0 +1,64 @@
+//! Runtime mode of the builders generated with `#[builder(dynamic)]`.
+//!
+//! In this mode the builder exposes a `set_by_name` method that sets a member
+//! identified by its name from a [`serde_json::Value`], and a `try_build`
+//! method that checks at runtime that all required members were set. This is
+//! useful when the values of the members come from a dynamic map, so the
+//! compile-time type state can't be used to track them.
+
+use std::fmt;
+
+/// Error returned by the methods of the builder's runtime mode.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// The builder doesn't have a member with the given name.
+    UnknownMember {
+        /// Name of the member that was requested
+        name: String,
+    },
+
+    /// The member was already set via its setter, which is tracked in the
+    /// type state of the builder, so it can't be overwritten.
+    AlreadySet {
+        /// Name of the member
+        name: &'static str,
+    },
+
+    /// The value can't be deserialized into the type of the member.
+    InvalidValue {
+        /// Name of the member
+        name: &'static str,
+
+        /// The error returned by the deserializer
+        source: serde_json::Error,
+    },
+
+    /// The required member wasn't set before finishing building.
+    MissingMember {
+        /// Name of the member
+        name: &'static str,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownMember { name } => write!(f, "unknown member `{name}`"),
+            Self::AlreadySet { name } => write!(f, "the member `{name}` was already set"),
+            Self::InvalidValue { name, source } => {
+                write!(f, "invalid value for the member `{name}`: {source}")
+            }
+            Self::MissingMember { name } => write!(f, "the member `{name}` wasn't set"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidValue { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
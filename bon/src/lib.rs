@@ -2,6 +2,23 @@
 
 pub use bon_macros::*;
 
+pub mod dyn_builder;
+
+pub mod nested;
+
+pub mod state;
+
+mod member_info;
+
+pub use member_info::MemberInfo;
+
+mod validation;
+
+pub use validation::{BoxError, MemberError, ValidationErrors};
+
+#[cfg(feature = "serde_json")]
+pub mod dynamic;
+
 /// Symbols used by macros. They are not stable and are considered an implementation detail.
 #[doc(hidden)]
 pub mod private;
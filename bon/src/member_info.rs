@@ -0,0 +1,24 @@
+/// Describes a member of a builder. The builders expose the metadata of all
+/// their members via the associated constant `MEMBERS`, which is useful for
+/// generating documentation or UIs from the builders.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct MemberInfo {
+    /// Name of the member as it appears in the builder API.
+    pub name: &'static str,
+
+    /// Type of the member as it's written in the source code.
+    pub type_name: &'static str,
+
+    /// Whether the member must be set before finishing building. This includes
+    /// the positional members of the starting and finishing functions.
+    pub required: bool,
+
+    /// Whether the member has a default value, which is used if it isn't set.
+    /// Members of type `Option<T>` that default to `None` implicitly don't
+    /// count.
+    pub has_default: bool,
+
+    /// Text of the member's doc comments. It's empty if there are no docs.
+    pub docs: &'static str,
+}
@@ -0,0 +1,54 @@
+//! Traits that let the builder of one struct configure a member whose type
+//! has a builder of its own.
+//!
+//! They are implemented automatically by `#[builder]` on structs and used by
+//! the setters of the members annotated with `#[builder(nested)]`. Such setter
+//! accepts a closure that receives the builder of the member's type, so the
+//! member can be configured inline without importing that builder:
+//!
+//! ```
+//! use bon::builder;
+//!
+//! #[builder]
+//! struct Retry {
+//!     attempts: u32,
+//!     backoff_ms: Option<u64>,
+//! }
+//!
+//! #[builder]
+//! struct Client {
+//!     url: String,
+//!
+//!     #[builder(nested)]
+//!     retry: Retry,
+//! }
+//!
+//! let client = Client::builder()
+//!     .url("https://example.com")
+//!     .retry(|retry| retry.attempts(3).backoff_ms(100))
+//!     .build();
+//!
+//! assert_eq!(client.retry.attempts, 3);
+//! assert_eq!(client.retry.backoff_ms, Some(100));
+//! ```
+
+/// Implemented for the structs with a builder generated by `#[builder]`. It's
+/// not implemented if the starting function of the builder has parameters,
+/// or if the visibility of the builder type is overridden.
+pub trait HasBuilder {
+    /// The builder in its initial state
+    type Builder;
+
+    /// Creates the builder. It's the same as calling the starting function.
+    fn builder() -> Self::Builder;
+}
+
+/// Implemented for the builders of structs in the states where they can be
+/// finished. It's not implemented if the finishing function has parameters.
+pub trait FinishBuilder {
+    /// The output of the finishing function
+    type Output;
+
+    /// Finishes building. It's the same as calling the finishing function.
+    fn finish_builder(self) -> Self::Output;
+}
@@ -1,61 +1,123 @@
-use std::mem::MaybeUninit;
+/// Used to prevent the implementations of the type state traits outside of this crate.
+pub trait Sealed {}
 
-/// [`MaybeUninit`] is used to make the memory layout of this struct be equal
-/// to `T` such that the compiler may optimize away moving data between it and
-/// [`Set<T>`].
-#[derive(Debug)]
-struct Unset<T>(MaybeUninit<T>);
-
-impl<T> Default for Unset<T> {
-    fn default() -> Self {
-        Self(MaybeUninit::uninit())
+/// Unwraps the value of a required member in the finishing function. The
+/// type state of the builder guarantees that the member is set at this point.
+#[inline(always)]
+pub fn unwrap_set<T>(value: Option<T>) -> T {
+    match value {
+        Some(value) => value,
+        None => unreachable!("BUG: the type state of the builder guarantees the member is set"),
     }
 }
 
-#[derive(Debug)]
-pub struct Required<T>(Unset<Option<T>>);
-
-impl<T> Default for Required<T> {
-    fn default() -> Self {
-        Self(Unset::default())
+/// Unwraps the value of a required member in the finishing function of the
+/// builder with `#[builder(check = "runtime")]`, which doesn't track whether
+/// the members are set in its type.
+#[track_caller]
+pub fn expect_set<T>(value: Option<T>, member: &str, builder: &str) -> T {
+    match value {
+        Some(value) => value,
+        None => panic!("the required member `{member}` of `{builder}` wasn't set"),
     }
 }
 
-#[derive(Debug)]
-pub struct Optional<T>(Unset<T>);
+/// Selects the state of the builder after a setter of an overwritable member
+/// is called. If the member was already set, then the state doesn't change,
+/// which allows calling the setter repeatedly, for example, in a loop.
+pub trait OverwriteState<Current, Next> {
+    type Output;
+}
 
-impl<T> Default for Optional<T> {
-    fn default() -> Self {
-        Self(Unset::default())
-    }
+impl<Current, Next> OverwriteState<Current, Next> for crate::state::Set {
+    type Output = Current;
 }
 
-impl<T> IntoSet<Option<T>> for Optional<T> {
-    fn into_set(self) -> Set<Option<T>> {
-        Set::new(None)
-    }
+impl<Current, Next> OverwriteState<Current, Next> for crate::state::Unset {
+    type Output = Next;
 }
 
-#[repr(transparent)]
-#[derive(Debug)]
-pub struct Set<T>(T);
+/// Selects the state of a member after two builders are merged. The member
+/// is set if it was set in either of the builders. It also combines the states
+/// of the members of a group to check if any of them is set.
+pub trait MergeState<Other> {
+    type Output: crate::state::MemberState;
+}
 
-impl<T> Set<T> {
-    pub fn new(value: T) -> Self {
-        Self(value)
-    }
+impl<Other> MergeState<Other> for crate::state::Set {
+    type Output = crate::state::Set;
+}
 
-    pub fn into_inner(self) -> T {
-        self.0
-    }
+impl<Other: crate::state::MemberState> MergeState<Other> for crate::state::Unset {
+    type Output = Other;
 }
 
-impl<T> IntoSet<T> for Set<T> {
-    fn into_set(self) -> Self {
-        self
+/// Creates the metadata of a member. It's a function, because the struct is
+/// `#[non_exhaustive]`, so it can't be constructed outside of this crate.
+pub const fn member_info(
+    name: &'static str,
+    type_name: &'static str,
+    required: bool,
+    has_default: bool,
+    docs: &'static str,
+) -> crate::MemberInfo {
+    crate::MemberInfo {
+        name,
+        type_name,
+        required,
+        has_default,
+        docs,
     }
 }
 
-pub trait IntoSet<T> {
-    fn into_set(self) -> Set<T>;
+pub use crate::validation::MemberErrors;
+
+#[cfg(feature = "clap")]
+pub use clap;
+
+#[cfg(feature = "serde")]
+pub use serde;
+
+#[cfg(feature = "schemars")]
+pub use schemars;
+
+#[cfg(any(feature = "serde_json", feature = "schemars"))]
+pub use serde_json;
+
+/// Evaluates the predicates of the `cfg_attr` attributes, which proc macros
+/// can't do on their own, and invokes the attribute macro once again passing
+/// the values of the predicates to it in the `__cfgs(...)` parameter.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __eval_cfg_callback {
+    (
+        { $($results:tt)* }
+        ( ($($predicate:tt)*) $($predicates:tt)* )
+        $($rest:tt)*
+    ) => {
+        #[cfg($($predicate)*)]
+        $crate::__eval_cfg_callback! {
+            { $($results)* true, }
+            ( $($predicates)* )
+            $($rest)*
+        }
+
+        #[cfg(not($($predicate)*))]
+        $crate::__eval_cfg_callback! {
+            { $($results)* false, }
+            ( $($predicates)* )
+            $($rest)*
+        }
+    };
+
+    (
+        { $($results:tt)* }
+        ()
+        ( $($macro_path:tt)* )
+        ( $($params:tt)* )
+        $($item:tt)*
+    ) => {
+        #[$($macro_path)*(__cfgs($($results)*), $($params)*)]
+        $($item)*
+    };
 }
@@ -0,0 +1,100 @@
+//! Types and traits that describe the type state of the generated builders.
+//!
+//! Every builder has a module named after it in snake case, e.g. `request_builder`
+//! for `RequestBuilder`. The module contains the builder's state trait named
+//! `State` and the state types. The trait has an associated type for each member, which is either [`Set`] or [`Unset`]
+//! depending on whether the member was already set. The setters are available
+//! only if the member's state implements [`IsUnset`], and the finishing function
+//! is available only if the states of all required members implement [`IsSet`].
+//!
+//! These traits can be used to write functions generic over the state of the
+//! builder. For example, the following function accepts a builder where `url`
+//! is already set and `retries` is not set yet:
+//!
+//! ```
+//! use bon::builder;
+//! use bon::state::{IsSet, IsUnset};
+//!
+//! #[builder]
+//! struct Request {
+//!     url: String,
+//!     retries: Option<u32>,
+//! }
+//!
+//! fn build_with_retries<S>(builder: RequestBuilder<S>) -> Request
+//! where
+//!     S: request_builder::State,
+//!     S::Url: IsSet,
+//!     S::Retries: IsUnset,
+//! {
+//!     builder.retries(3).build()
+//! }
+//!
+//! let request = build_with_retries(Request::builder().url("https://example.com"));
+//!
+//! assert_eq!(request.retries, Some(3));
+//! ```
+
+use crate::private::Sealed;
+
+/// Marker type for the state of a member that was set.
+#[derive(Debug)]
+pub enum Set {}
+
+/// Marker type for the state of a member that wasn't set yet.
+#[derive(Debug)]
+pub enum Unset {}
+
+/// Common trait for the [`Set`] and [`Unset`] member states. It's implemented
+/// only for them and can't be implemented outside of this crate.
+pub trait MemberState: Sealed {
+    #[doc(hidden)]
+    const IS_SET: bool;
+}
+
+/// Implemented only for the [`Set`] state of a member.
+pub trait IsSet: MemberState {}
+
+/// Implemented only for the [`Unset`] state of a member.
+pub trait IsUnset: MemberState {}
+
+impl Sealed for Set {}
+impl Sealed for Unset {}
+
+impl MemberState for Set {
+    const IS_SET: bool = true;
+}
+
+impl MemberState for Unset {
+    const IS_SET: bool = false;
+}
+
+impl IsSet for Set {}
+impl IsUnset for Unset {}
+
+/// Implemented for the [`Set`] state of the member represented by the marker
+/// type `Member`. The generated builders require it instead of [`IsSet`] to
+/// report which member wasn't set if the state is [`Unset`].
+///
+/// The marker types are placed in the `members` child module of the builder's
+/// state module, and they are named after the members.
+#[diagnostic::on_unimplemented(
+    message = "the member `{Member}` was not set",
+    label = "the member `{Member}` must be set before calling this method"
+)]
+pub trait IsSetMember<Member: ?Sized> {}
+
+/// Implemented for the combined [`Set`] state of the members of the group
+/// represented by the marker type `Group`. The generated builders require it
+/// instead of [`IsSet`] to report which group has none of its members set.
+///
+/// The marker types are placed in the `groups` child module of the builder's
+/// state module, and they are named after the groups.
+#[diagnostic::on_unimplemented(
+    message = "none of the members of the group `{Group}` were set",
+    label = "at least one of the members of the group `{Group}` must be set before calling this method"
+)]
+pub trait IsSetGroup<Group: ?Sized> {}
+
+impl<T: IsSet, Member: ?Sized> IsSetMember<Member> for T {}
+impl<T: IsSet, Group: ?Sized> IsSetGroup<Group> for T {}
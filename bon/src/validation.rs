@@ -0,0 +1,140 @@
+use std::error::Error;
+use std::fmt;
+
+/// Boxed error returned by the validation function of a member.
+pub type BoxError = Box<dyn Error + Send + Sync>;
+
+/// Error of a member that didn't pass its `#[builder(validate = ...)]` function,
+/// or whose `#[builder(env = ...)]` variable has an invalid value.
+#[derive(Debug)]
+pub struct MemberError {
+    member: &'static str,
+    error: BoxError,
+}
+
+impl MemberError {
+    /// Name of the member as it appears in the builder API.
+    pub fn member(&self) -> &'static str {
+        self.member
+    }
+
+    /// Error returned by the validation function of the member.
+    pub fn error(&self) -> &(dyn Error + Send + Sync + 'static) {
+        &*self.error
+    }
+
+    /// Converts into the error returned by the validation function.
+    pub fn into_error(self) -> BoxError {
+        self.error
+    }
+}
+
+impl fmt::Display for MemberError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid value of `{}`: {}", self.member, self.error)
+    }
+}
+
+impl Error for MemberError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&*self.error)
+    }
+}
+
+/// Errors of all members that didn't pass their validation functions. The
+/// finishing function returns it if any of the members configured with
+/// `#[builder(validate = ...)]` is invalid, or the environment variable of a
+/// member configured with `#[builder(env = ...)]` can't be parsed. It's never empty.
+#[derive(Debug)]
+pub struct ValidationErrors {
+    errors: Vec<MemberError>,
+}
+
+impl ValidationErrors {
+    /// Errors of the invalid members in the order of their declaration.
+    pub fn errors(&self) -> &[MemberError] {
+        &self.errors
+    }
+
+    /// Converts into the errors of the invalid members.
+    pub fn into_errors(self) -> Vec<MemberError> {
+        self.errors
+    }
+}
+
+impl fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, error) in self.errors.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{error}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ValidationErrors {}
+
+impl IntoIterator for ValidationErrors {
+    type Item = MemberError;
+    type IntoIter = std::vec::IntoIter<MemberError>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.errors.into_iter()
+    }
+}
+
+/// Collects the errors of the member validation functions in the finishing
+/// function. It's re-exported from the private module for the generated code.
+#[derive(Debug, Default)]
+pub struct MemberErrors {
+    errors: Vec<MemberError>,
+}
+
+impl MemberErrors {
+    /// Records the error of the member if its validation function failed.
+    pub fn check<E: Into<BoxError>>(&mut self, member: &'static str, result: Result<(), E>) {
+        if let Err(error) = result {
+            self.errors.push(MemberError {
+                member,
+                error: error.into(),
+            });
+        }
+    }
+
+    /// Reads the value of a member that wasn't set from the environment variable.
+    /// Returns `None` if the variable isn't set. If its value can't be parsed,
+    /// then the error is recorded for the member, and `None` is returned.
+    pub fn env_var<T>(&mut self, member: &'static str, name: &str) -> Option<T>
+    where
+        T: std::str::FromStr,
+        T::Err: fmt::Display,
+    {
+        let result = match std::env::var(name) {
+            Ok(value) => value.parse().map_err(|err: T::Err| err.to_string()),
+            Err(std::env::VarError::NotPresent) => return None,
+            Err(err @ std::env::VarError::NotUnicode(_)) => Err(err.to_string()),
+        };
+
+        match result {
+            Ok(value) => Some(value),
+            Err(err) => {
+                let error = format!("invalid value of the environment variable `{name}`: {err}");
+                self.check(member, Err(error));
+                None
+            }
+        }
+    }
+
+    /// Returns the errors of all members that failed the validation, if any.
+    pub fn finish(self) -> Result<(), ValidationErrors> {
+        if self.errors.is_empty() {
+            return Ok(());
+        }
+
+        Err(ValidationErrors {
+            errors: self.errors,
+        })
+    }
+}
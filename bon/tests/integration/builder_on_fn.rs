@@ -162,6 +162,35 @@ fn unsafe_func() {
     unsafe { builder.call() };
 }
 
+#[test]
+#[allow(unsafe_code)]
+fn unsafe_func_safety_docs() {
+    /// Docs on the function.
+    ///
+    /// # Safety
+    ///
+    /// The `arg` must be `true`, for example:
+    ///
+    /// ```
+    /// # let arg = true;
+    /// assert!(arg);
+    /// ```
+    ///
+    /// # Examples
+    ///
+    /// There are none.
+    #[builder(closure_fn = with_sut)]
+    unsafe fn sut(arg: bool) -> bool {
+        arg
+    }
+
+    // The finishing functions are `unsafe` and carry over the `# Safety`
+    // section. The contents of the section are checked by the doc tests of
+    // `parse_unchecked` in the `e2e-tests` crate.
+    assert!(unsafe { sut().arg(true).call() });
+    assert!(unsafe { with_sut(|b| b.arg(true)) });
+}
+
 #[test]
 fn impl_traits() {
     #[builder]
@@ -185,6 +214,38 @@ fn impl_traits() {
     assert_eq!(vec, [1, 2, 3]);
 }
 
+#[test]
+fn nested_impl_traits() {
+    #[builder]
+    fn sut(
+        name: Option<impl Into<String>>,
+        tags: Vec<impl AsRef<str>>,
+        sizes: &[impl Into<u64> + Copy],
+    ) -> String {
+        let name = name.map(Into::into).unwrap_or_default();
+        let tags: Vec<_> = tags.iter().map(AsRef::as_ref).collect();
+        let size: u64 = sizes.iter().copied().map(Into::into).sum();
+
+        format!("{name}:{}:{size}", tags.join(","))
+    }
+
+    let actual = sut()
+        .name("bon")
+        .tags(vec!["a", "b"])
+        .sizes(&[1_u8, 2])
+        .call();
+
+    assert_eq!(actual, "bon:a,b:3");
+
+    let actual = sut()
+        .tags(vec![String::from("c")])
+        .sizes(&[3_u32])
+        .maybe_name(None::<String>)
+        .call();
+
+    assert_eq!(actual, ":c:3");
+}
+
 #[test]
 fn constructor() {
     struct Counter {
@@ -236,6 +297,301 @@ fn receiver() {
     assert_eq!(counter.val, 1);
 }
 
+#[test]
+fn derive_clone() {
+    struct Counter {
+        val: u32,
+    }
+
+    #[bon]
+    impl Counter {
+        #[builder(derive(Clone))]
+        fn add(&self, step: u32, times: Option<u32>) -> u32 {
+            self.val + step * times.unwrap_or(1)
+        }
+    }
+
+    let counter = Counter { val: 1 };
+    let builder = counter.add().step(2);
+
+    assert_eq!(builder.clone().times(3).call(), 7);
+    assert_eq!(builder.call(), 3);
+}
+
+#[test]
+fn derive_partial_eq() {
+    #[derive(PartialEq)]
+    struct Counter {
+        val: u32,
+    }
+
+    #[bon]
+    impl Counter {
+        #[builder(derive(PartialEq))]
+        fn add(&self, step: u32, times: Option<u32>) -> u32 {
+            self.val + step * times.unwrap_or(1)
+        }
+    }
+
+    let first = Counter { val: 1 };
+    let second = Counter { val: 2 };
+
+    assert!(first.add().step(2) == first.add().step(2));
+    assert!(first.add().step(2) != first.add().step(3));
+    assert!(first.add().step(2) != second.add().step(2));
+    assert!(first.add().times(1) != first.add().times(2));
+}
+
+#[test]
+fn requires_attr() {
+    #[builder]
+    fn retry(
+        attempts: Option<u32>,
+        #[builder(requires = attempts)] backoff_ms: Option<u64>,
+        #[builder(start_fn)] name: &str,
+    ) -> String {
+        format!("{name}: {attempts:?} {backoff_ms:?}")
+    }
+
+    assert_eq!(retry("a").call(), "a: None None");
+    assert_eq!(
+        retry("b").attempts(3).backoff_ms(100).call(),
+        "b: Some(3) Some(100)"
+    );
+}
+
+#[test]
+fn conflicts_with_attr() {
+    #[builder]
+    fn open(#[builder(conflicts_with = url)] path: Option<&str>, url: Option<&str>) -> String {
+        path.or(url).unwrap_or_default().to_owned()
+    }
+
+    assert_eq!(open().path("config.toml").call(), "config.toml");
+    assert_eq!(
+        open().url("https://example.com").call(),
+        "https://example.com"
+    );
+    assert_eq!(open().call(), "");
+}
+
+#[test]
+fn group_attr() {
+    #[builder]
+    fn contact(
+        #[builder(start_fn)] name: &str,
+        #[builder(group = channel)] email: Option<&str>,
+        #[builder(group = channel)] phone: Option<&str>,
+    ) -> String {
+        format!("{name}: {}", email.or(phone).unwrap_or_default())
+    }
+
+    assert_eq!(
+        contact("a").email("a@example.com").call(),
+        "a: a@example.com"
+    );
+    assert_eq!(contact("b").phone("123").call(), "b: 123");
+    assert_eq!(
+        contact("c").phone("456").email("c@example.com").call(),
+        "c: c@example.com"
+    );
+}
+
+#[test]
+fn section_attr() {
+    struct Client {
+        base_url: &'static str,
+    }
+
+    #[bon]
+    impl Client {
+        #[builder]
+        fn get(
+            &self,
+            path: &str,
+            #[builder(section = retry)] attempts: Option<u32>,
+            #[builder(section = retry)] backoff_ms: Option<u64>,
+        ) -> String {
+            format!("{}{path} ({attempts:?}, {backoff_ms:?})", self.base_url)
+        }
+    }
+
+    let client = Client {
+        base_url: "https://example.com",
+    };
+
+    let actual = client
+        .get()
+        .retry(|retry| retry.backoff_ms(100).attempts(3))
+        .path("/users")
+        .call();
+
+    assert_eq!(actual, "https://example.com/users (Some(3), Some(100))");
+    assert_eq!(
+        client.get().path("/").call(),
+        "https://example.com/ (None, None)"
+    );
+}
+
+#[test]
+fn nested_attr() {
+    #[builder]
+    #[derive(Debug, PartialEq)]
+    struct Point {
+        x: i32,
+        #[builder(default)]
+        y: i32,
+    }
+
+    #[builder]
+    fn distance(#[builder(nested)] from: Point, #[builder(nested)] to: Option<Point>) -> i32 {
+        let to = to.unwrap_or(Point { x: 0, y: 0 });
+        (from.x - to.x).abs() + (from.y - to.y).abs()
+    }
+
+    let actual = distance()
+        .from(|from| from.x(3).y(4))
+        .to(|to| to.x(1))
+        .call();
+
+    assert_eq!(actual, 6);
+
+    let actual = distance().from(|from| from.x(-2)).call();
+
+    assert_eq!(actual, 2);
+}
+
+#[test]
+fn flatten_attr() {
+    #[builder]
+    #[derive(Debug, PartialEq)]
+    struct Retry {
+        attempts: u32,
+
+        #[builder(default = 100)]
+        backoff_ms: u64,
+    }
+
+    #[builder]
+    fn fetch(
+        url: &str,
+        #[builder(flatten)] retry: Retry,
+        #[builder(flatten(prefix = fallback))] fallback_retry: Retry,
+    ) -> (String, Retry, Retry) {
+        (url.to_owned(), retry, fallback_retry)
+    }
+
+    let (url, retry, fallback_retry) = fetch()
+        .url("/")
+        .attempts(3)
+        .fallback_attempts(1)
+        .fallback_backoff_ms(1_000)
+        .call();
+
+    assert_eq!(url, "/");
+    assert_eq!(
+        retry,
+        Retry {
+            attempts: 3,
+            backoff_ms: 100,
+        }
+    );
+    assert_eq!(
+        fallback_retry,
+        Retry {
+            attempts: 1,
+            backoff_ms: 1_000,
+        }
+    );
+}
+
+#[test]
+fn finish_fn_map() {
+    #[builder(finish_fn(map = |sum| -> Box<u32> { Box::new(sum) }))]
+    fn sum(x: u32, y: u32) -> u32 {
+        x + y
+    }
+
+    let actual: Box<u32> = sum().x(1).y(2).call();
+
+    assert_eq!(*actual, 3);
+}
+
+#[test]
+fn pre_build_attr() {
+    #[builder(pre_build = |volume: &mut u8, _: &mut bool| *volume = (*volume).min(100))]
+    fn play(volume: u8, #[builder(default)] muted: bool) -> (u8, bool) {
+        (volume, muted)
+    }
+
+    assert_eq!(play().volume(200).call(), (100, false));
+    assert_eq!(play().volume(30).muted(true).call(), (30, true));
+}
+
+#[test]
+fn validate_member_attr() {
+    #[builder]
+    fn ratio(
+        #[builder(validate = |value: &u32| if *value == 0 { Err("zero") } else { Ok(()) })]
+        denominator: u32,
+        #[builder(finish_fn, validate = |value: &u32| if *value > 100 { Err("too big") } else { Ok(()) })]
+        numerator: u32,
+    ) -> u32 {
+        numerator / denominator
+    }
+
+    assert_eq!(ratio().denominator(2).call(10).unwrap(), 5);
+
+    let errors = ratio().denominator(0).call(200).unwrap_err();
+    let members: Vec<_> = errors.into_iter().map(|error| error.member()).collect();
+
+    assert_eq!(members, ["denominator", "numerator"]);
+}
+
+#[test]
+fn finish_fn_unwrap() {
+    #[builder(
+        finish_fn(name = try_call, unwrap = call),
+        validate = |x: &u32| if *x > 10 { Err("too big") } else { Ok(()) }
+    )]
+    fn double(x: u32) -> u32 {
+        x * 2
+    }
+
+    assert_eq!(double().x(2).call(), 4);
+    assert_eq!(
+        double().x(20).try_call().unwrap_err().to_string(),
+        "too big"
+    );
+}
+
+#[test]
+fn check_runtime() {
+    struct Counter {
+        step: u32,
+    }
+
+    #[bon]
+    impl Counter {
+        #[builder(check = "runtime", track_caller)]
+        fn advance(&self, from: &u32, #[builder(finish_fn)] times: u32) -> u32 {
+            from + self.step * times
+        }
+    }
+
+    let counter = Counter { step: 2 };
+    let from = 1;
+
+    assert_eq!(counter.advance().from(&from).call(3), 7);
+
+    let panic = std::panic::catch_unwind(|| counter.advance().call(3)).unwrap_err();
+
+    assert_eq!(
+        panic.downcast_ref::<String>().map(String::as_str),
+        Some("the required member `from` of `CounterAdvanceBuilder` wasn't set")
+    );
+}
+
 #[test]
 fn receiver_with_lifetimes() {
     struct Sut<'a, 'b> {
@@ -299,6 +655,101 @@ fn receiver_is_non_default() {
     assert_eq!(sut.method().call(), "blackjack");
 }
 
+#[test]
+fn receiver_is_mut_ref() {
+    #[derive(Debug, Default, PartialEq)]
+    struct Window {
+        title: String,
+        width: u32,
+        height: u32,
+        resizable: bool,
+    }
+
+    #[bon]
+    impl Window {
+        #[builder]
+        fn configure(
+            &mut self,
+            title: Option<&str>,
+            width: Option<u32>,
+            height: Option<u32>,
+            #[builder(flag)] resizable: bool,
+        ) -> &mut Self {
+            if let Some(title) = title {
+                self.title = title.to_owned();
+            }
+            self.width = width.unwrap_or(self.width);
+            self.height = height.unwrap_or(self.height);
+            self.resizable = resizable;
+            self
+        }
+    }
+
+    let mut window = Window::default();
+
+    let builder = window.configure().title("bon").width(800);
+
+    // The builder holds the mutable borrow until the method is called
+    builder.resizable().call().height = 600;
+
+    assert_eq!(
+        window,
+        Window {
+            title: "bon".to_owned(),
+            width: 800,
+            height: 600,
+            resizable: true,
+        }
+    );
+
+    window.configure().height(300).call();
+
+    assert_eq!((window.width, window.height), (800, 300));
+    assert!(!window.resizable);
+}
+
+#[test]
+fn receiver_is_smart_pointer() {
+    use std::pin::Pin;
+    use std::rc::Rc;
+    use std::sync::Arc;
+
+    struct Sut(u32);
+
+    #[bon]
+    impl Sut {
+        #[builder]
+        fn arc(self: Arc<Self>, diff: u32) -> u32 {
+            self.0 + diff
+        }
+
+        #[builder]
+        fn rc(self: Rc<Self>, diff: u32) -> u32 {
+            self.0 + diff
+        }
+
+        #[builder]
+        fn boxed(mut self: Box<Self>, diff: u32) -> Box<Self> {
+            self.0 += diff;
+            self
+        }
+
+        #[builder]
+        fn pinned(mut self: Pin<&mut Self>, diff: u32) -> u32 {
+            self.0 += diff;
+            self.0
+        }
+    }
+
+    assert_eq!(Arc::new(Sut(1)).arc().diff(2).call(), 3);
+    assert_eq!(Rc::new(Sut(1)).rc().diff(2).call(), 3);
+    assert_eq!(Box::new(Sut(1)).boxed().diff(2).call().0, 3);
+
+    let mut sut = Sut(1);
+    assert_eq!(Pin::new(&mut sut).pinned().diff(2).call(), 3);
+    assert_eq!(sut.0, 3);
+}
+
 #[test]
 fn impl_block_ty_contains_a_reference() {
     struct Sut<T>(T);
@@ -339,6 +790,48 @@ fn impl_block_with_self_in_const_generics() {
     assert_eq!(Sut::<42>.method().call(), 42);
 }
 
+#[test]
+fn const_generics() {
+    #[builder]
+    fn sum<const N: usize>(
+        values: [u32; N],
+        #[builder(default = [1; N])] weights: [u32; N],
+    ) -> u32 {
+        values
+            .iter()
+            .zip(weights)
+            .map(|(value, weight)| value * weight)
+            .sum()
+    }
+
+    assert_eq!(sum().values([1, 2, 3]).call(), 6);
+    assert_eq!(sum().values([1, 2]).weights([2, 3]).call(), 8);
+
+    struct Buf<const N: usize>([u8; N]);
+
+    #[bon]
+    impl<const N: usize> Buf<N> {
+        #[builder]
+        fn new(#[builder(default = [0; N])] data: [u8; N]) -> Self {
+            Self(data)
+        }
+
+        #[builder]
+        fn concat<const M: usize>(&self, tail: [u8; M]) -> Vec<u8> {
+            [self.0.as_slice(), tail.as_slice()].concat()
+        }
+    }
+
+    let buf = Buf::<2>::builder().build();
+
+    assert_eq!(buf.concat().tail([1]).call(), [0, 0, 1]);
+
+    let buf = Buf::builder().data([1, 2, 3]).build();
+    let builder: BufConcatBuilder<'_, 3, 1, _> = buf.concat().tail([4]);
+
+    assert_eq!(builder.call(), [1, 2, 3, 4]);
+}
+
 #[test]
 fn generics_with_lifetimes() {
     #[builder]
@@ -372,60 +865,774 @@ fn raw_identifiers() {
     fn sut() {}
 
     let _: r#type = sut();
+
+    #[builder]
+    fn keywords(
+        r#async: Option<u32>,
+        #[builder(flag)] r#loop: bool,
+        #[builder(push)] r#in: Vec<u32>,
+        _type: u32,
+        _self: u32,
+    ) -> (Option<u32>, bool, Vec<u32>, u32, u32) {
+        (r#async, r#loop, r#in, _type, _self)
+    }
+
+    let actual = keywords()
+        .maybe_async(Some(1))
+        .r#loop()
+        .push_in(2)
+        .r#type(3)
+        ._self(4)
+        .call();
+
+    assert_eq!(actual, (Some(1), true, vec![2], 3, 4));
 }
 
-// This is based on the issue https://github.com/elastio/bon/issues/16
 #[test]
-fn self_only_generic_param() {
-    struct Sut<'a, 'b: 'a, T, U> {
-        buf: Vec<T>,
-        bar: Option<U>,
-        str: &'a str,
-        other_ref: &'b (),
+fn custom_finish_fn() {
+    #[builder(finish_fn = "send")]
+    fn get(path: &str) -> String {
+        format!("GET {path}")
     }
 
+    assert_eq!(get().path("/users").send(), "GET /users");
+
+    struct Client;
+
     #[bon::bon]
-    impl<T, U> Sut<'_, '_, T, U> {
-        #[builder]
-        fn new() -> Self {
-            Self {
-                buf: vec![],
-                bar: None,
-                str: "littlepip",
-                other_ref: &(),
-            }
+    impl Client {
+        #[builder(finish_fn = "send")]
+        fn post(&self, path: &str) -> String {
+            format!("POST {path}")
+        }
+
+        #[builder(finish_fn = connect)]
+        fn new(_host: &str) -> Self {
+            Self
         }
     }
 
-    // Make sure `new` method is hidden
-    Sut::<u32, std::convert::Infallible>::__orig_new();
+    let client = Client::builder().host("localhost").connect();
 
-    // Make sure the builder type name matches the type of builder when
-    // `#[builder]` is placed on top of a struct
-    let _: SutBuilder<'_, '_, u32, std::convert::Infallible> = Sut::builder();
+    assert_eq!(client.post().path("/users").send(), "POST /users");
+}
 
-    let actual = Sut::<u32, std::convert::Infallible>::builder().build();
+#[test]
+fn custom_builder_type() {
+    #[builder(builder_type = "GetRequestBuilder")]
+    fn get(path: &str) -> String {
+        format!("GET {path}")
+    }
 
-    let empty: [u32; 0] = [];
+    struct Client;
 
-    assert_eq!(actual.buf, empty);
-    assert!(actual.bar.is_none());
-    assert_eq!(actual.str, "littlepip");
-    let () = actual.other_ref;
+    #[bon::bon]
+    impl Client {
+        #[builder(builder_type = PostRequestBuilder)]
+        fn post(&self, path: &str) -> String {
+            format!("POST {path}")
+        }
+    }
+
+    let builder: GetRequestBuilder<'_, _> = get().path("/users");
+    assert_eq!(builder.call(), "GET /users");
+
+    let builder: PostRequestBuilder<'_, '_, _> = Client.post().path("/users");
+    assert_eq!(builder.call(), "POST /users");
 }
 
 #[test]
-fn mut_fn_params() {
-    #[builder]
-    fn sut(mut arg1: u32, mut arg2: u32) -> (u32, u32) {
-        arg1 += 1;
-        arg2 += 2;
+fn custom_start_fn() {
+    #[builder(start_fn = with_defaults)]
+    fn connect(host: &str, port: Option<u16>) -> String {
+        format!("{host}:{}", port.unwrap_or(80))
+    }
+
+    assert_eq!(with_defaults().host("localhost").call(), "localhost:80");
+
+    struct Client;
+
+    #[bon::bon]
+    impl Client {
+        #[builder(start_fn(name = "init", vis = ""))]
+        fn new() -> Self {
+            Self
+        }
+
+        #[builder(start_fn = get_with, owned)]
+        fn get(self, path: &str) -> String {
+            format!("GET {path}")
+        }
+    }
+
+    let response = Client::init().build().get_with().path("/users").call();
+    assert_eq!(response, "GET /users");
+
+    let response = Client::init()
+        .build()
+        .get_with_owned()
+        .path("/users")
+        .call();
+    assert_eq!(response, "GET /users");
+}
+
+#[test]
+fn custom_vis() {
+    mod private {
+        use bon::{bon, builder};
+
+        // The positional function and the builder have different visibility
+        #[builder(vis = "pub(super)", expose_positional_fn(name = sum, vis = "pub(crate)"))]
+        fn sum_builder(a: u32, b: u32) -> u32 {
+            a + b
+        }
+
+        pub(crate) struct Client;
+
+        #[bon]
+        impl Client {
+            #[builder(
+                builder_type(name = GetRequest, vis = "pub(crate)"),
+                finish_fn(name = send, vis = "pub(super)"),
+            )]
+            pub(crate) fn get(&self, path: &str) -> String {
+                format!("GET {path}")
+            }
+        }
+    }
+
+    assert_eq!(private::sum(1, 2), 3);
+    assert_eq!(private::sum_builder().a(1).b(2).call(), 3);
+
+    let builder: private::GetRequest<'_, '_, _> = private::Client.get().path("/users");
+    assert_eq!(builder.send(), "GET /users");
+}
+
+#[test]
+fn setter_prefix() {
+    #[builder(setter_prefix = with_)]
+    fn sut(name: &str, age: Option<u32>) -> String {
+        format!("{name} {age:?}")
+    }
+
+    assert_eq!(sut().with_name("Bon").with_age(24).call(), "Bon Some(24)");
+
+    struct Counter;
+
+    #[bon::bon]
+    impl Counter {
+        #[builder(setter_prefix = set_)]
+        fn new(initial: Option<u32>) -> u32 {
+            initial.unwrap_or_default()
+        }
+    }
+
+    assert_eq!(Counter::builder().set_maybe_initial(None).build(), 0);
+}
+
+#[test]
+fn member_rename() {
+    // The `call` setter would conflict with the finishing function
+    #[builder]
+    fn sut(
+        #[builder(name = "timeout")] timeout_secs: u64,
+        #[builder(name = callback)] call: Option<fn() -> u32>,
+    ) -> u64 {
+        timeout_secs + u64::from(call.map(|call| call()).unwrap_or_default())
+    }
+
+    assert_eq!(sut().timeout(30).callback(|| 12).call(), 42);
+
+    struct Client;
+
+    #[bon::bon]
+    impl Client {
+        #[builder]
+        fn new(#[builder(name = "timeout")] _timeout_secs: Option<u64>) -> Self {
+            Self
+        }
+    }
+
+    let _ = Client::builder().maybe_timeout(Some(30)).build();
+}
+
+#[test]
+fn member_rename_pattern() {
+    struct Wrapper(u32);
+
+    #[builder]
+    fn sum(
+        #[builder(name = point)] (x, y): (u32, u32),
+        #[builder(name = wrapper)] Wrapper(inner): Wrapper,
+        #[builder(name = pair)] &(first, _): &(u32, u32),
+    ) -> u32 {
+        x + y + inner + first
+    }
+
+    assert_eq!(
+        sum().point((1, 2)).wrapper(Wrapper(3)).pair(&(4, 5)).call(),
+        10
+    );
+
+    struct Scale(u32);
+
+    #[bon::bon]
+    impl Scale {
+        #[builder]
+        fn apply(&self, #[builder(name = point)] (x, y): (u32, u32)) -> (u32, u32) {
+            (x * self.0, y * self.0)
+        }
+    }
+
+    assert_eq!(Scale(2).apply().point((1, 2)).call(), (2, 4));
+}
+
+#[test]
+fn default_expr_with_self() {
+    struct Client {
+        timeout_secs: u64,
+    }
+
+    #[bon::bon]
+    impl Client {
+        const DEFAULT_TIMEOUT_SECS: u64 = 30;
+
+        fn default_timeout_secs() -> u64 {
+            Self::DEFAULT_TIMEOUT_SECS
+        }
+
+        #[builder]
+        fn new(#[builder(default = Self::default_timeout_secs())] timeout_secs: u64) -> Self {
+            Self { timeout_secs }
+        }
+
+        #[builder]
+        fn timeout_secs(
+            &self,
+            #[builder(default = Self::DEFAULT_TIMEOUT_SECS)] extra_secs: u64,
+        ) -> u64 {
+            self.timeout_secs + extra_secs
+        }
+    }
+
+    let client = Client::builder().build();
+
+    assert_eq!(client.timeout_secs().call(), 60);
+}
+
+#[test]
+fn default_expr_with_preceding_members() {
+    #[builder(owned)]
+    fn sut(name: &str, #[builder(default = format!("Hello, {name}!"))] greeting: String) -> String {
+        format!("{name}: {greeting}")
+    }
+
+    assert_eq!(sut().name("Bon").call(), "Bon: Hello, Bon!");
+    assert_eq!(sut_owned().name("Bon").call(), "Bon: Hello, Bon!");
+    assert_eq!(sut().name("Bon").greeting("Hi!").call(), "Bon: Hi!");
+}
+
+#[tokio::test]
+async fn validate() {
+    #[derive(Debug, PartialEq)]
+    struct ZeroDivisor;
+
+    fn check_divisor(_: &u32, divisor: &u32) -> Result<(), ZeroDivisor> {
+        if *divisor == 0 {
+            return Err(ZeroDivisor);
+        }
+        Ok(())
+    }
+
+    #[builder(validate(with = check_divisor, error = ZeroDivisor), owned)]
+    async fn div(dividend: u32, divisor: u32) -> u32 {
+        std::future::ready(dividend / divisor).await
+    }
+
+    assert_eq!(div().dividend(6).divisor(3).call().await, Ok(2));
+    assert_eq!(div().dividend(6).divisor(0).call().await, Err(ZeroDivisor));
+    assert_eq!(
+        div_owned().dividend(6).divisor(0).call().await,
+        Err(ZeroDivisor)
+    );
+
+    struct Greeter;
+
+    #[bon::bon]
+    impl Greeter {
+        #[builder(validate = |name: &&str| if name.is_empty() { Err("empty name") } else { Ok(()) })]
+        fn greet(&self, name: &str) {
+            drop(format!("Hello, {name}!"));
+        }
+    }
+
+    Greeter.greet().name("Bon").call().unwrap();
+
+    let err = Greeter.greet().name("").call().unwrap_err();
+
+    assert_eq!(err.to_string(), "empty name");
+}
+
+#[tokio::test]
+async fn validate_async() {
+    async fn check_user(id: &u32) -> Result<(), String> {
+        tokio::task::yield_now().await;
+
+        if *id == 0 {
+            return Err(format!("user {id} doesn't exist"));
+        }
+        Ok(())
+    }
+
+    #[builder(validate(async_with = check_user))]
+    async fn fetch_user(id: u32) -> String {
+        tokio::task::yield_now().await;
+        format!("user {id}")
+    }
+
+    assert_eq!(fetch_user().id(1).call().await.unwrap(), "user 1");
+
+    let err = fetch_user().id(0).call().await.unwrap_err();
+
+    assert_eq!(err.to_string(), "user 0 doesn't exist");
+}
+
+#[test]
+fn start_fn_members() {
+    #[builder(owned)]
+    fn greet(#[builder(start_fn)] name: &str, greeting: Option<&str>) -> String {
+        format!("{}, {name}!", greeting.unwrap_or("Hello"))
+    }
+
+    assert_eq!(greet("Bon").call(), "Hello, Bon!");
+    assert_eq!(greet_owned("Bon").greeting("Hi").call(), "Hi, Bon!");
+
+    struct Counter {
+        value: u32,
+    }
+
+    #[bon::bon]
+    impl Counter {
+        #[builder]
+        fn new(#[builder(start_fn)] value: u32) -> Self {
+            Self { value }
+        }
+
+        #[builder]
+        fn add(&mut self, #[builder(start_fn)] diff: u32, times: Option<u32>) {
+            self.value += diff * times.unwrap_or(1);
+        }
+    }
+
+    let mut counter = Counter::builder(1).build();
+
+    counter.add(2).times(3).call();
+    counter.add(1).call();
+
+    assert_eq!(counter.value, 8);
+}
+
+#[test]
+fn finish_fn_members() {
+    struct Connection {
+        queries: Vec<String>,
+    }
+
+    #[builder(finish_fn = execute, closure_fn)]
+    fn query(
+        #[builder(start_fn)] table: &str,
+        #[builder(finish_fn)] conn: &mut Connection,
+        limit: Option<u32>,
+        #[builder(finish_fn)] comment: &str,
+    ) -> usize {
+        let limit = limit
+            .map(|limit| format!(" LIMIT {limit}"))
+            .unwrap_or_default();
+        conn.queries
+            .push(format!("SELECT * FROM {table}{limit} -- {comment}"));
+        conn.queries.len()
+    }
+
+    let mut conn = Connection { queries: vec![] };
+
+    assert_eq!(query("users").limit(10).execute(&mut conn, "first"), 1);
+    assert_eq!(
+        query_with("posts", &mut conn, "second", |builder| builder),
+        2
+    );
+
+    assert_eq!(
+        conn.queries,
+        [
+            "SELECT * FROM users LIMIT 10 -- first",
+            "SELECT * FROM posts -- second",
+        ]
+    );
+
+    trait Sum {
+        fn call(self, extra: u32) -> u32;
+    }
+
+    #[builder(implements = Sum)]
+    fn sum(x: u32, #[builder(finish_fn)] extra: u32) -> u32 {
+        x + extra
+    }
+
+    fn call_sum(builder: impl Sum) -> u32 {
+        builder.call(2)
+    }
+
+    assert_eq!(sum().x(1).call(2), 3);
+    assert_eq!(call_sum(sum().x(1)), 3);
+
+    struct Counter {
+        value: u32,
+    }
+
+    #[bon::bon]
+    impl Counter {
+        #[builder]
+        fn add(&mut self, #[builder(finish_fn)] diff: u32, times: Option<u32>) {
+            self.value += diff * times.unwrap_or(1);
+        }
+    }
+
+    let mut counter = Counter { value: 0 };
+
+    counter.add().times(3).call(2);
+    counter.add().call(1);
+
+    assert_eq!(counter.value, 7);
+}
+
+#[test]
+fn skipped_members() {
+    #[builder]
+    fn greet(
+        name: &str,
+        #[builder(skip)] count: u32,
+        #[builder(skip = format!("Hello, {name}!"))] greeting: String,
+    ) -> String {
+        format!("{greeting} ({name}, {count})")
+    }
+
+    assert_eq!(greet().name("Bon").call(), "Hello, Bon! (Bon, 0)");
+
+    struct Counter {
+        value: u32,
+    }
+
+    #[bon::bon]
+    impl Counter {
+        const STEP: u32 = 2;
+
+        #[builder]
+        fn new(#[builder(skip = Self::STEP)] value: u32) -> Self {
+            Self { value }
+        }
+    }
+
+    assert_eq!(Counter::builder().build().value, 2);
+}
+
+#[test]
+fn field_members() {
+    #[builder]
+    fn sum(#[builder(field = vec![1])] values: Vec<u32>, scale: Option<u32>) -> u32 {
+        values.iter().sum::<u32>() * scale.unwrap_or(1)
+    }
+
+    impl<State: sum_builder::State> SumBuilder<State> {
+        fn value(mut self, value: u32) -> Self {
+            self.__private_impl.values.push(value);
+            self
+        }
+    }
+
+    assert_eq!(sum().call(), 1);
+    assert_eq!(sum().value(2).scale(2).value(3).call(), 12);
+}
+
+#[test]
+fn push_attr() {
+    #[builder]
+    fn sum(#[builder(push = value)] values: Vec<u32>) -> u32 {
+        values.iter().sum()
+    }
+
+    assert_eq!(sum().value(1).value(2).call(), 3);
+    assert_eq!(sum().values(vec![1, 2]).value(3).call(), 6);
+}
+
+#[test]
+fn required_attr() {
+    #[builder]
+    fn sut(#[builder(required)] limit: Option<u32>, offset: Option<u32>) -> String {
+        format!("{limit:?}:{offset:?}")
+    }
+
+    assert_eq!(sut().limit(Some(10)).call(), "Some(10):None");
+    assert_eq!(sut().limit(None).offset(5).call(), "None:Some(5)");
+}
+
+#[test]
+fn flag_attr() {
+    #[builder]
+    fn sut(#[builder(flag)] verbose: bool, #[builder(flag)] dry_run: bool) -> (bool, bool) {
+        (verbose, dry_run)
+    }
+
+    assert_eq!(sut().call(), (false, false));
+    assert_eq!(sut().verbose().call(), (true, false));
+    assert_eq!(sut().with_verbose(false).dry_run().call(), (false, true));
+}
+
+#[test]
+fn cow_into() {
+    use std::borrow::Cow;
+
+    #[builder]
+    fn sut<'a>(name: Cow<'a, str>, bytes: Option<Cow<'a, [u8]>>) -> (Cow<'a, str>, usize) {
+        (name, bytes.map_or(0, |bytes| bytes.len()))
+    }
+
+    let name = String::from("borrowed");
+
+    let (actual, _) = sut().name(name.as_str()).call();
+    assert!(matches!(actual, Cow::Borrowed("borrowed")));
+
+    let (actual, len) = sut().name(String::from("owned")).bytes(vec![1, 2]).call();
+    assert!(matches!(actual, Cow::Owned(_)));
+    assert_eq!(len, 2);
+
+    let (_, len) = sut().name("static").bytes([1_u8].as_slice()).call();
+    assert_eq!(len, 1);
+}
+
+// This is based on the issue https://github.com/elastio/bon/issues/16
+#[test]
+fn self_only_generic_param() {
+    struct Sut<'a, 'b: 'a, T, U> {
+        buf: Vec<T>,
+        bar: Option<U>,
+        str: &'a str,
+        other_ref: &'b (),
+    }
+
+    #[bon::bon]
+    impl<T, U> Sut<'_, '_, T, U> {
+        #[builder]
+        fn new() -> Self {
+            Self {
+                buf: vec![],
+                bar: None,
+                str: "littlepip",
+                other_ref: &(),
+            }
+        }
+    }
+
+    // Make sure `new` method is hidden
+    Sut::<u32, std::convert::Infallible>::__orig_new();
+
+    // Make sure the builder type name matches the type of builder when
+    // `#[builder]` is placed on top of a struct
+    let _: SutBuilder<'_, '_, u32, std::convert::Infallible> = Sut::builder();
+
+    let actual = Sut::<u32, std::convert::Infallible>::builder().build();
+
+    let empty: [u32; 0] = [];
+
+    assert_eq!(actual.buf, empty);
+    assert!(actual.bar.is_none());
+    assert_eq!(actual.str, "littlepip");
+    let () = actual.other_ref;
+}
+
+#[test]
+fn generic_over_state() {
+    use bon::state::{IsSet, IsUnset};
+
+    #[builder]
+    fn connect(host: &str, port: Option<u16>) -> String {
+        format!("{host}:{}", port.unwrap_or(80))
+    }
+
+    fn with_port<S>(builder: ConnectBuilder<'_, S>) -> String
+    where
+        S: connect_builder::State,
+        S::Host: IsSet,
+        S::Port: IsUnset,
+    {
+        builder.port(8080).call()
+    }
+
+    assert_eq!(with_port(connect().host("localhost")), "localhost:8080");
+
+    struct Client {
+        base_url: String,
+    }
+
+    #[bon]
+    impl Client {
+        #[builder]
+        fn get(&self, path: &str, retries: Option<u32>) -> String {
+            format!("{}{path} ({} retries)", self.base_url, retries.unwrap_or(0))
+        }
+    }
+
+    fn send<S>(builder: ClientGetBuilder<'_, '_, S>) -> String
+    where
+        S: client_get_builder::State,
+        S::Path: IsSet,
+    {
+        builder.call()
+    }
+
+    let client = Client {
+        base_url: "https://example.com".to_owned(),
+    };
+
+    assert_eq!(
+        send(client.get().path("/users").retries(2)),
+        "https://example.com/users (2 retries)"
+    );
+    assert_eq!(
+        send(client.get().path("/posts")),
+        "https://example.com/posts (0 retries)"
+    );
+}
+
+#[test]
+fn merge_attr() {
+    #[builder(merge)]
+    fn connect(host: &str, port: Option<u16>, retries: Option<u32>) -> String {
+        format!("{host}:{} ({})", port.unwrap_or(80), retries.unwrap_or(0))
+    }
+
+    let actual = connect()
+        .port(443)
+        .retries(1)
+        .merge(connect().host("localhost").retries(2))
+        .call();
+
+    assert_eq!(actual, "localhost:443 (2)");
+
+    struct Client {
+        base_url: &'static str,
+    }
+
+    #[bon]
+    impl Client {
+        #[builder(merge)]
+        fn get(&self, path: &str, retries: Option<u32>) -> String {
+            format!("{}{path} ({})", self.base_url, retries.unwrap_or(0))
+        }
+    }
+
+    let first = Client {
+        base_url: "https://first.com",
+    };
+    let second = Client {
+        base_url: "https://second.com",
+    };
+
+    let actual = first
+        .get()
+        .retries(3)
+        .merge(second.get().path("/users"))
+        .call();
+
+    assert_eq!(actual, "https://first.com/users (3)");
+}
+
+#[test]
+fn finish_fn_clone_attr() {
+    #[builder(finish_fn_clone)]
+    fn connect(host: &str, port: Option<u16>, #[builder(finish_fn)] id: u32) -> String {
+        format!("{id}: {host}:{}", port.unwrap_or(80))
+    }
+
+    let template = connect().host("localhost").port(443);
+
+    let actual: Vec<_> = (1..=2).map(|id| template.call_clone(id)).collect();
+
+    assert_eq!(actual, ["1: localhost:443", "2: localhost:443"]);
+    assert_eq!(template.call(3), "3: localhost:443");
+
+    struct Client {
+        base_url: &'static str,
+    }
+
+    #[bon]
+    impl Client {
+        #[builder(finish_fn_clone)]
+        fn get(&self, path: &str, retries: Option<u32>) -> String {
+            format!("{}{path} ({})", self.base_url, retries.unwrap_or(0))
+        }
+    }
+
+    let client = Client {
+        base_url: "https://example.com",
+    };
+
+    let template = client.get().path("/users");
+
+    assert_eq!(template.call_clone(), "https://example.com/users (0)");
+    assert_eq!(template.retries(2).call(), "https://example.com/users (2)");
+}
+
+#[test]
+fn mut_fn_params() {
+    #[builder]
+    fn sut(mut arg1: u32, mut arg2: u32) -> (u32, u32) {
+        arg1 += 1;
+        arg2 += 2;
 
         (arg1, arg2)
     }
 
     let actual = sut().arg1(1).arg2(2).call();
     assert_eq!(actual, (2, 4));
+
+    #[builder]
+    fn positional(
+        #[builder(start_fn)] mut start: u32,
+        #[builder(finish_fn)] mut finish: u32,
+        mut generic: impl Iterator<Item = u32>,
+        mut optional: Option<u32>,
+    ) -> (u32, u32, u32, u32) {
+        start += 1;
+        finish += 2;
+        optional = optional.map(|value| value + 4);
+
+        (
+            start,
+            finish,
+            generic.next().unwrap_or_default(),
+            optional.unwrap_or_default(),
+        )
+    }
+
+    let actual = positional(1)
+        .generic([1, 2].into_iter())
+        .optional(4)
+        .call(2);
+    assert_eq!(actual, (2, 4, 1, 8));
+
+    struct Counter(u32);
+
+    #[bon::bon]
+    impl Counter {
+        #[builder]
+        fn add(mut self, mut diff: u32) -> u32 {
+            diff += 1;
+            self.0 += diff;
+            self.0
+        }
+    }
+
+    assert_eq!(Counter(1).add().diff(1).call(), 3);
 }
 
 // This is based on the issue https://github.com/elastio/bon/issues/12
@@ -438,3 +1645,396 @@ fn types_not_implementing_default() {
 
     test().call();
 }
+
+#[tokio::test]
+async fn implements_trait() {
+    trait Request {
+        async fn send(self) -> String;
+    }
+
+    #[builder(implements = Request, finish_fn = send)]
+    async fn request(path: &str, body: Option<String>) -> String {
+        tokio::task::yield_now().await;
+        format!("{path}:{body:?}")
+    }
+
+    async fn send(builder: impl Request) -> String {
+        builder.send().await
+    }
+
+    let actual = send(request().path("/users")).await;
+    assert_eq!(actual, "/users:None");
+}
+
+#[test]
+fn closure_fn() {
+    #[builder(closure_fn)]
+    fn sut<T: Into<u32>>(arg1: &str, arg2: Option<T>) -> String {
+        format!("{arg1}:{:?}", arg2.map(Into::into))
+    }
+
+    assert_eq!(sut_with(|b| b.arg1("a").arg2(42_u8)), "a:Some(42)");
+    assert_eq!(sut_with::<u32, _>(|b| b.arg1("a")), "a:None");
+
+    #[builder(closure_fn)]
+    fn first<'a, T>(items: &'a [T], fallback: &'a T) -> &'a T {
+        items.first().unwrap_or(fallback)
+    }
+
+    assert_eq!(*first_with(|b| b.items(&[1, 2]).fallback(&3)), 1);
+    assert_eq!(*first_with(|b| b.items(&[]).fallback(&3)), 3);
+
+    struct Counter {
+        val: u32,
+    }
+
+    #[bon]
+    impl Counter {
+        #[builder(closure_fn = with)]
+        fn new(initial: Option<u32>) -> Self {
+            Self {
+                val: initial.unwrap_or_default(),
+            }
+        }
+
+        #[builder(closure_fn(name = incremented, vis = "pub(crate)"))]
+        fn increment(&mut self, diff: u32) -> &mut Self {
+            self.val += diff;
+            self
+        }
+    }
+
+    let mut counter = Counter::with(|b| b.initial(3));
+    assert_eq!(counter.incremented(|b| b.diff(2)).val, 5);
+}
+
+#[test]
+fn owned() {
+    #[builder(owned)]
+    fn sut<'a, T: Copy>(
+        str: &str,
+        bytes: &'a [u8],
+        opt_str: Option<&'a str>,
+        #[builder(default)] default_str: &str,
+        generic: &[T],
+        other: u32,
+    ) -> String {
+        format!(
+            "{str}:{bytes:?}:{opt_str:?}:{default_str}:{}:{other}",
+            generic.len()
+        )
+    }
+
+    fn assert_static<T: Send + 'static>(value: T) -> T {
+        value
+    }
+
+    let builder = assert_static(
+        sut_owned()
+            .str(String::from("str"))
+            .bytes([1_u8, 2].as_slice())
+            .opt_str("opt")
+            .generic(vec![true]),
+    );
+
+    let builder: OwnedSutBuilder<bool, _> = builder;
+
+    assert_eq!(builder.other(3).call(), "str:[1, 2]:Some(\"opt\")::1:3");
+
+    // The original builder is still available
+    assert_eq!(
+        sut().str("a").bytes(&[]).generic(&[1]).other(1).call(),
+        "a:[]:None::1:1"
+    );
+
+    struct Counter {
+        val: String,
+    }
+
+    #[bon]
+    impl Counter {
+        #[builder(owned)]
+        fn new(val: &str) -> Self {
+            Self {
+                val: val.to_owned(),
+            }
+        }
+
+        #[builder(owned)]
+        fn append(self, suffix: Option<&str>) -> Self {
+            Self {
+                val: format!("{}{}", self.val, suffix.unwrap_or_default()),
+            }
+        }
+    }
+
+    let counter = Counter::builder_owned().val("a").build();
+    let counter = counter.append_owned().suffix("b").call();
+
+    assert_eq!(counter.val, "ab");
+
+    // `&mut` references are not replaced with owned types
+    #[builder(owned)]
+    fn push(target: &mut Vec<String>, item: &str) {
+        target.push(item.to_owned());
+    }
+
+    let mut items = vec![];
+    push_owned().target(&mut items).item("a").call();
+
+    assert_eq!(items, ["a"]);
+
+    // The members that share a lifetime with the return type stay borrowed
+    #[builder(owned)]
+    fn parse<'a>(input: &str, fallback: &'a str) -> Option<&'a str> {
+        input.is_empty().then_some(fallback)
+    }
+
+    assert_eq!(
+        parse_owned().input("").fallback("none").call(),
+        Some("none")
+    );
+
+    #[builder(owned)]
+    fn find<'a>(items: &'a [u32], skip: &[u32]) -> Option<&'a u32> {
+        items.iter().find(|item| !skip.contains(item))
+    }
+
+    assert_eq!(find_owned().items(&[1, 2]).skip(vec![1]).call(), Some(&2));
+}
+
+#[test]
+fn trait_provided_methods() {
+    #[bon]
+    trait Greeter<T = u32>: Sized {
+        fn name(&self) -> String;
+
+        #[builder]
+        fn greet(&self, greeting: &str, times: Option<T>) -> String
+        where
+            T: Into<usize>,
+        {
+            let times = times.map(Into::into).unwrap_or(1);
+            format!("{greeting}, {}!", self.name()).repeat(times)
+        }
+
+        #[builder]
+        fn create(name: &str) -> Self
+        where
+            Self: From<String>,
+        {
+            Self::from(name.to_owned())
+        }
+    }
+
+    struct User(String);
+
+    impl From<String> for User {
+        fn from(name: String) -> Self {
+            Self(name)
+        }
+    }
+
+    impl Greeter<u8> for User {
+        fn name(&self) -> String {
+            self.0.clone()
+        }
+    }
+
+    let user = User::create().name("Bon").call();
+
+    assert_eq!(user.greet().greeting("Hi").call(), "Hi, Bon!");
+    assert_eq!(
+        user.greet().greeting("Hi").times(2).call(),
+        "Hi, Bon!Hi, Bon!"
+    );
+
+    let _: GreeterGreetBuilder<'_, '_, u8, User, _> = user.greet();
+}
+
+#[test]
+fn cfg_members() {
+    #[builder]
+    fn sut(
+        level: u32,
+        #[cfg(any())] tls: NonExistentType,
+        #[cfg(not(any()))] timeout: Option<u32>,
+    ) -> (u32, Option<u32>) {
+        (level, timeout)
+    }
+
+    struct Sut;
+
+    #[bon]
+    impl Sut {
+        #[builder]
+        fn method(
+            &self,
+            #[cfg(any())] tls: NonExistentType,
+            #[cfg(not(any()))] timeout: u32,
+        ) -> u32 {
+            timeout
+        }
+    }
+
+    assert_eq!(sut().level(1).timeout(2).call(), (1, Some(2)));
+    assert_eq!(Sut.method().timeout(3).call(), 3);
+}
+
+#[test]
+fn cfg_attr_members() {
+    #[builder]
+    fn sut(
+        #[cfg_attr(not(any()), builder(default = 5))] level: u32,
+        #[cfg_attr(any(), builder(start_fn))] timeout: u32,
+    ) -> (u32, u32) {
+        (level, timeout)
+    }
+
+    struct Sut;
+
+    #[bon]
+    impl Sut {
+        #[cfg_attr(not(any()), builder)]
+        fn method(&self, #[cfg_attr(not(any()), builder(default = 3))] level: u32) -> u32 {
+            level
+        }
+
+        #[cfg_attr(any(), builder)]
+        fn plain(&self, level: u32) -> u32 {
+            level
+        }
+    }
+
+    assert_eq!(sut().timeout(2).call(), (5, 2));
+    assert_eq!(Sut.method().call(), 3);
+    assert_eq!(Sut.plain(4), 4);
+}
+
+#[test]
+fn other_attrs() {
+    #[inline]
+    #[builder(expose_positional_fn = positional, closure_fn)]
+    #[must_use]
+    #[track_caller]
+    fn sut(level: u32) -> u32 {
+        level
+    }
+
+    #[builder]
+    #[deprecated = "use `sut` instead"]
+    fn old(level: u32) -> u32 {
+        level
+    }
+
+    struct Sut;
+
+    #[bon]
+    impl Sut {
+        #[inline(always)]
+        #[builder]
+        #[must_use]
+        fn method(&self, level: u32) -> u32 {
+            level
+        }
+    }
+
+    assert_eq!(sut().level(1).call(), 1);
+    assert_eq!(sut_with(|builder| builder.level(2)), 2);
+    assert_eq!(positional(3), 3);
+    assert_eq!(Sut.method().level(4).call(), 4);
+
+    #[allow(deprecated)]
+    let old = old().level(5).call();
+    assert_eq!(old, 5);
+}
+
+/// The output of `#[async_trait]` placed above `#[bon]`, which desugars the
+/// `async fn` before the builder is generated for it
+#[tokio::test]
+async fn async_trait_desugared() {
+    use std::future::Future;
+    use std::pin::Pin;
+
+    #[bon]
+    trait Greeter {
+        fn name(&self) -> String;
+
+        #[must_use]
+        #[allow(clippy::type_complexity, clippy::type_repetition_in_bounds)]
+        #[builder]
+        fn greet<'life0, 'async_trait>(
+            &'life0 self,
+            greeting: &'static str,
+        ) -> Pin<Box<dyn Future<Output = String> + Send + 'async_trait>>
+        where
+            'life0: 'async_trait,
+            Self: Sync + 'async_trait,
+        {
+            Box::pin(async move { format!("{greeting}, {}!", self.name()) })
+        }
+    }
+
+    struct User;
+
+    impl Greeter for User {
+        fn name(&self) -> String {
+            "Bon".to_owned()
+        }
+    }
+
+    assert_eq!(User.greet().greeting("Hi").call().await, "Hi, Bon!");
+}
+
+mod facade {
+    pub(crate) use bon as bon_reexport;
+}
+
+#[tokio::test]
+async fn async_trait() {
+    #[async_trait::async_trait]
+    #[bon]
+    trait Above {
+        #[builder]
+        async fn greet(&self, greeting: &'static str) -> String {
+            format!("{greeting}, above!")
+        }
+    }
+
+    #[bon]
+    #[async_trait::async_trait]
+    trait Below {
+        #[builder]
+        async fn greet(&self, greeting: &'static str) -> String {
+            format!("{greeting}, below!")
+        }
+    }
+
+    #[facade::bon_reexport::bon(crate = crate::builder_on_fn::facade::bon_reexport)]
+    #[async_trait::async_trait]
+    trait Reexported {
+        #[builder]
+        async fn greet(&self, greeting: &'static str) -> String {
+            format!("{greeting}, reexported!")
+        }
+    }
+
+    struct User;
+
+    impl Above for User {}
+    impl Below for User {}
+    impl Reexported for User {}
+
+    assert_eq!(
+        Above::greet(&User).greeting("Hi").call().await,
+        "Hi, above!"
+    );
+    assert_eq!(
+        Below::greet(&User).greeting("Hi").call().await,
+        "Hi, below!"
+    );
+    assert_eq!(
+        Reexported::greet(&User).greeting("Hi").call().await,
+        "Hi, reexported!"
+    );
+}
@@ -49,3 +49,51 @@ fn simple() {
 
     assert_eq!(positional("arg1".to_owned()), "arg1");
 }
+
+#[test]
+fn keeps_original_name() {
+    #[builder(expose_positional_fn)]
+    fn sut(arg1: bool, arg2: u32) -> (bool, u32) {
+        (arg1, arg2)
+    }
+
+    assert_eq!(sut(true, 42), (true, 42));
+    assert_eq!(sut_builder().arg1(true).arg2(42).call(), (true, 42));
+
+    struct Sut;
+
+    #[bon]
+    impl Sut {
+        #[builder(expose_positional_fn(vis = "pub(crate)"), start_fn = with_builder)]
+        fn method(&self, arg: u32) -> u32 {
+            arg
+        }
+    }
+
+    assert_eq!(Sut.method(1), 1);
+    assert_eq!(Sut.with_builder().arg(1).call(), 1);
+}
+
+#[test]
+fn renamed() {
+    struct Sut(u32);
+
+    #[bon]
+    impl Sut {
+        #[builder(expose_positional_fn = "new_unchecked")]
+        fn new(value: u32) -> Self {
+            Self(value)
+        }
+
+        #[builder(expose_positional_fn = "with_value_unchecked")]
+        fn with_value(value: u32) -> Self {
+            Self(value)
+        }
+    }
+
+    assert_eq!(Sut::new_unchecked(1).0, 1);
+    assert_eq!(Sut::builder().value(1).build().0, 1);
+
+    assert_eq!(Sut::with_value_unchecked(2).0, 2);
+    assert_eq!(Sut::with_value().value(2).call().0, 2);
+}
@@ -67,6 +67,2010 @@ fn smoke() {
     expected.assert_debug_eq(&actual);
 }
 
+#[test]
+fn default_attr() {
+    #[builder]
+    #[derive(Debug, PartialEq)]
+    struct Sut {
+        #[builder(default)]
+        retries: u32,
+
+        #[builder(default)]
+        tags: Vec<String>,
+    }
+
+    assert_eq!(
+        Sut::builder().build(),
+        Sut {
+            retries: 0,
+            tags: vec![]
+        }
+    );
+
+    let actual = Sut::builder().retries(3).maybe_tags(None).build();
+
+    assert_eq!(
+        actual,
+        Sut {
+            retries: 3,
+            tags: vec![]
+        }
+    );
+
+    let actual = Sut::builder()
+        .maybe_retries(Some(5))
+        .tags(vec!["tag".to_owned()])
+        .build();
+
+    assert_eq!(
+        actual,
+        Sut {
+            retries: 5,
+            tags: vec!["tag".to_owned()]
+        }
+    );
+}
+
+#[test]
+fn default_expr_with_generics_and_self() {
+    use std::time::Duration;
+
+    #[builder]
+    struct Sut<T: Default> {
+        #[builder(default = Duration::from_secs(30))]
+        timeout: Duration,
+
+        #[builder(default = Self::DEFAULT_RETRIES)]
+        retries: u32,
+
+        #[builder(default = T::default())]
+        value: T,
+    }
+
+    impl<T: Default> Sut<T> {
+        const DEFAULT_RETRIES: u32 = 3;
+    }
+
+    let actual = Sut::<String>::builder().build();
+
+    assert_eq!(actual.timeout, Duration::from_secs(30));
+    assert_eq!(actual.retries, 3);
+    assert_eq!(actual.value, "");
+
+    let actual = Sut::builder().retries(5).value(42_u32).build();
+
+    assert_eq!(actual.retries, 5);
+    assert_eq!(actual.value, 42);
+}
+
+#[test]
+fn default_expr_with_preceding_members() {
+    #[builder]
+    struct Sut {
+        base_url: String,
+
+        #[builder(default = format!("{base_url}/api"))]
+        api_url: String,
+
+        #[builder(default = api_url.len())]
+        api_url_len: usize,
+    }
+
+    let actual = Sut::builder().base_url("https://bon.rs").build();
+
+    assert_eq!(actual.base_url, "https://bon.rs");
+    assert_eq!(actual.api_url, "https://bon.rs/api");
+    assert_eq!(actual.api_url_len, 18);
+
+    let actual = Sut::builder()
+        .base_url("https://bon.rs")
+        .api_url("https://api.bon.rs")
+        .build();
+
+    assert_eq!(actual.api_url, "https://api.bon.rs");
+    assert_eq!(actual.api_url_len, 18);
+}
+
+#[test]
+fn validate() {
+    #[builder(validate = check_range)]
+    #[derive(Debug)]
+    struct Range {
+        min: u32,
+        max: u32,
+    }
+
+    fn check_range(min: &u32, max: &u32) -> Result<(), String> {
+        if min > max {
+            return Err(format!("min ({min}) must not exceed max ({max})"));
+        }
+        Ok(())
+    }
+
+    let range = Range::builder().min(1).max(2).build().unwrap();
+
+    assert_eq!((range.min, range.max), (1, 2));
+
+    let err = Range::builder().min(3).max(2).build().unwrap_err();
+
+    assert_eq!(err.to_string(), "min (3) must not exceed max (2)");
+
+    #[derive(Debug, PartialEq)]
+    struct EmptyName;
+
+    #[builder(validate(
+        with = |name: &String, _: &Option<u32>| if name.is_empty() { Err(EmptyName) } else { Ok(()) },
+        error = EmptyName,
+    ))]
+    struct User {
+        name: String,
+        age: Option<u32>,
+    }
+
+    let user = User::builder().name("Bon").build().unwrap();
+
+    assert_eq!(user.name, "Bon");
+    assert_eq!(user.age, None);
+
+    let result: Result<User, EmptyName> = User::builder().name("").age(24).build();
+
+    assert_eq!(result.err(), Some(EmptyName));
+}
+
+#[test]
+fn try_into_attr() -> Result<(), std::num::TryFromIntError> {
+    use std::num::NonZeroU16;
+
+    #[builder]
+    struct Sut {
+        #[builder(try_into)]
+        port: u16,
+
+        #[builder(try_into)]
+        backlog: Option<NonZeroU16>,
+
+        #[builder(try_into, default = NonZeroU16::MIN)]
+        workers: NonZeroU16,
+    }
+
+    let actual = Sut::builder().try_port(8080_u32)?.try_backlog(128)?.build();
+
+    assert_eq!(actual.port, 8080);
+    assert_eq!(actual.backlog.map(NonZeroU16::get), Some(128));
+    assert_eq!(actual.workers.get(), 1);
+
+    let actual = Sut::builder().port(80).try_workers(4)?.build();
+
+    assert_eq!(actual.workers.get(), 4);
+
+    assert!(Sut::builder().try_port(-1_i32).is_err());
+    assert!(Sut::builder().port(80).try_workers(0).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn with_attr() {
+    use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+
+    #[builder]
+    struct Sut {
+        #[builder(with = |addr: &str| addr.parse().unwrap())]
+        ip: IpAddr,
+
+        #[builder(with = |ip: [u8; 4], port: u16| SocketAddr::from((ip, port)))]
+        proxy: Option<SocketAddr>,
+
+        #[builder(with = |(min, max): (u32, u32)| min..max, default = 0..10)]
+        range: std::ops::Range<u32>,
+    }
+
+    let actual = Sut::builder()
+        .ip("127.0.0.1")
+        .proxy([10, 0, 0, 1], 8080)
+        .range((1, 5))
+        .build();
+
+    assert_eq!(actual.ip, IpAddr::V4(Ipv4Addr::LOCALHOST));
+    assert_eq!(actual.proxy, Some(SocketAddr::from(([10, 0, 0, 1], 8080))));
+    assert_eq!(actual.range, 1..5);
+
+    let actual = Sut::builder()
+        .ip("::1")
+        .maybe_proxy(Some(([10, 0, 0, 2], 80)))
+        .maybe_range(None)
+        .build();
+
+    assert_eq!(actual.proxy, Some(SocketAddr::from(([10, 0, 0, 2], 80))));
+    assert_eq!(actual.range, 0..10);
+}
+
+#[test]
+fn on_into_false() {
+    type UserId = u64;
+
+    #[builder(on(UserId, into = false), on(String, into = false))]
+    struct Sut {
+        id: UserId,
+        name: Option<String>,
+
+        #[builder(into)]
+        email: String,
+
+        level: u32,
+    }
+
+    let actual = Sut::builder()
+        .id(1)
+        .name("name".to_owned())
+        .email("email")
+        .level(2)
+        .build();
+
+    assert_eq!(actual.id, 1);
+    assert_eq!(actual.name.as_deref(), Some("name"));
+    assert_eq!(actual.email, "email");
+    assert_eq!(actual.level, 2);
+}
+
+#[test]
+fn on_type_pattern() {
+    use std::borrow::Cow;
+
+    #[builder(on(u32, into), on(Cow<'_, _>, into), on(Vec<_>, into))]
+    struct Sut<'a> {
+        id: u32,
+        retries: Option<u32>,
+
+        #[builder(into = false)]
+        version: u32,
+
+        name: Cow<'a, str>,
+        bytes: Cow<'a, [u8]>,
+        tags: Vec<&'a str>,
+
+        other: u8,
+    }
+
+    let actual = Sut::builder()
+        .id(true)
+        .retries(3_u8)
+        .version(2)
+        .name("name")
+        .bytes(vec![1, 2])
+        .tags(["tag"])
+        .other(1)
+        .build();
+
+    assert_eq!(actual.id, 1);
+    assert_eq!(actual.retries, Some(3));
+    assert_eq!(actual.version, 2);
+    assert_eq!(actual.name, "name");
+    assert_eq!(actual.bytes.as_ref(), [1, 2]);
+    assert_eq!(actual.tags, ["tag"]);
+    assert_eq!(actual.other, 1);
+}
+
+#[test]
+fn derive_builder() {
+    /// Docs on the struct.
+    #[derive(bon::Builder, Debug, PartialEq)]
+    #[builder(finish_fn = assemble)]
+    #[builder(closure_fn)]
+    struct Sut<T: Default> {
+        /// Docs on the field.
+        name: String,
+
+        #[builder(default)]
+        value: T,
+
+        level: Option<u32>,
+    }
+
+    let actual = Sut::builder().name("Bon").level(3).assemble();
+
+    assert_eq!(
+        actual,
+        Sut {
+            name: "Bon".to_owned(),
+            value: 0_u32,
+            level: Some(3)
+        }
+    );
+
+    let actual = Sut::builder_with(|builder| builder.name("Bon").value("value"));
+
+    assert_eq!(actual.value, "value");
+    assert_eq!(actual.level, None);
+}
+
+#[test]
+fn start_fn_members() {
+    #[builder(closure_fn, default_from = Default)]
+    #[derive(Debug, Default)]
+    struct Client {
+        #[builder(start_fn)]
+        api_key: String,
+
+        #[builder(start_fn)]
+        region: u32,
+
+        timeout_secs: Option<u64>,
+    }
+
+    let client = Client::builder("key", 1).timeout_secs(30).build();
+
+    assert_eq!(client.api_key, "key");
+    assert_eq!(client.region, 1);
+    assert_eq!(client.timeout_secs, Some(30));
+
+    let client = Client::builder_with(String::from("key2"), 2, |builder| builder);
+
+    assert_eq!(client.api_key, "key2");
+    assert_eq!(client.region, 2);
+    assert_eq!(client.timeout_secs, None);
+}
+
+#[test]
+fn finish_fn_members() {
+    #[builder(default_from = Default)]
+    #[derive(Debug, Default)]
+    struct Request {
+        path: String,
+
+        #[builder(finish_fn)]
+        body: Vec<u8>,
+
+        #[builder(finish_fn)]
+        retries: u32,
+    }
+
+    let request = Request::builder()
+        .path("/users")
+        .build(b"payload".to_vec(), 3);
+
+    assert_eq!(request.path, "/users");
+    assert_eq!(request.body, b"payload");
+    assert_eq!(request.retries, 3);
+}
+
+#[test]
+fn skipped_members() {
+    #[builder(default_from = Default)]
+    #[derive(Debug, Default)]
+    struct User {
+        name: String,
+
+        #[builder(skip)]
+        tags: Vec<String>,
+
+        #[builder(skip = name.to_lowercase())]
+        handle: String,
+
+        #[builder(skip = Self::MAX_LOGINS)]
+        max_logins: u32,
+    }
+
+    impl User {
+        const MAX_LOGINS: u32 = 3;
+    }
+
+    let user = User::builder().name("Bon").build();
+
+    assert_eq!(user.name, "Bon");
+    assert_eq!(user.tags, Vec::<String>::new());
+    assert_eq!(user.handle, "bon");
+    assert_eq!(user.max_logins, 3);
+}
+
+#[test]
+fn field_members() {
+    #[builder(default_from = Default)]
+    #[derive(Debug, Default)]
+    struct Command {
+        program: String,
+
+        #[builder(field)]
+        args: Vec<String>,
+
+        #[builder(field = Self::DEFAULT_ENV.to_owned())]
+        env: Vec<(String, String)>,
+    }
+
+    impl Command {
+        const DEFAULT_ENV: &'static [(String, String)] = &[];
+    }
+
+    impl<State: command_builder::State> CommandBuilder<State> {
+        fn arg(mut self, arg: impl Into<String>) -> Self {
+            self.__private_impl.args.push(arg.into());
+            self
+        }
+
+        fn env(mut self, key: &str, value: &str) -> Self {
+            self.__private_impl
+                .env
+                .push((key.to_owned(), value.to_owned()));
+            self
+        }
+    }
+
+    let command = Command::builder()
+        .arg("--verbose")
+        .program("cargo")
+        .env("RUST_LOG", "debug")
+        .arg("build")
+        .build();
+
+    assert_eq!(command.program, "cargo");
+    assert_eq!(command.args, ["--verbose", "build"]);
+    assert_eq!(command.env, [("RUST_LOG".to_owned(), "debug".to_owned())]);
+}
+
+#[test]
+fn push_attr() {
+    #[builder]
+    struct Command {
+        #[builder(push)]
+        args: Vec<String>,
+
+        #[builder(push = env_var)]
+        env: Option<Vec<(String, String)>>,
+
+        #[builder(default, push)]
+        retries: Vec<u32>,
+    }
+
+    let command = Command::builder()
+        .push_args("build")
+        .env_var(("RUST_LOG".to_owned(), "debug".to_owned()))
+        .push_args(String::from("--release"))
+        .build();
+
+    assert_eq!(command.args, ["build", "--release"]);
+    assert_eq!(
+        command.env.unwrap(),
+        [("RUST_LOG".to_owned(), "debug".to_owned())]
+    );
+    assert_eq!(command.retries, Vec::<u32>::new());
+
+    let command = Command::builder()
+        .args(vec!["run".to_owned()])
+        .push_args("--quiet")
+        .push_retries(1)
+        .push_retries(2)
+        .build();
+
+    assert_eq!(command.args, ["run", "--quiet"]);
+    assert_eq!(command.env, None);
+    assert_eq!(command.retries, [1, 2]);
+
+    // The state doesn't change once the member is set, so the setter can be
+    // called in a loop
+    let mut builder = Command::builder().push_args("test");
+
+    for arg in ["--all", "--quiet"] {
+        builder = builder.push_args(arg);
+    }
+
+    assert_eq!(builder.build().args, ["test", "--all", "--quiet"]);
+}
+
+#[test]
+fn insert_attr() {
+    use std::collections::{BTreeMap, HashMap};
+
+    #[builder]
+    struct Request {
+        #[builder(insert = header)]
+        headers: BTreeMap<String, String>,
+
+        #[builder(insert)]
+        query: Option<HashMap<String, u32>>,
+    }
+
+    let request = Request::builder()
+        .header("Accept", "text/html")
+        .insert_query("page", 2)
+        .header("Accept", "application/json")
+        .header(String::from("Host"), "example.com")
+        .build();
+
+    assert_eq!(
+        request.headers.into_iter().collect::<Vec<_>>(),
+        [
+            ("Accept".to_owned(), "application/json".to_owned()),
+            ("Host".to_owned(), "example.com".to_owned()),
+        ]
+    );
+    assert_eq!(request.query.unwrap()["page"], 2);
+
+    let request = Request::builder()
+        .headers(BTreeMap::from([("Host".to_owned(), "a.com".to_owned())]))
+        .header("Accept", "text/html")
+        .build();
+
+    assert_eq!(request.headers.len(), 2);
+    assert_eq!(request.query, None);
+}
+
+#[test]
+fn extend_attr() {
+    use std::collections::BTreeSet;
+
+    #[builder]
+    struct Document {
+        #[builder(extend, push)]
+        lines: Vec<String>,
+
+        #[builder(extend = text)]
+        body: String,
+
+        #[builder(extend)]
+        tags: Option<BTreeSet<u32>>,
+    }
+
+    let doc = Document::builder()
+        .extend_lines(["a".to_owned(), "b".to_owned()])
+        .push_lines("c")
+        .extend_lines(vec!["d".to_owned()])
+        .text(["Hello", ", "])
+        .text("world".chars())
+        .extend_tags([3, 1])
+        .extend_tags([2, 1].iter())
+        .build();
+
+    assert_eq!(doc.lines, ["a", "b", "c", "d"]);
+    assert_eq!(doc.body, "Hello, world");
+    assert_eq!(doc.tags.unwrap().into_iter().collect::<Vec<_>>(), [1, 2, 3]);
+
+    let doc = Document::builder().extend_lines([]).body("text").build();
+
+    assert_eq!(doc.lines, Vec::<String>::new());
+    assert_eq!(doc.body, "text");
+    assert_eq!(doc.tags, None);
+}
+
+#[test]
+fn derive_clone() {
+    #[builder(derive(Clone))]
+    struct Config<T> {
+        #[builder(start_fn)]
+        name: String,
+        level: T,
+        tags: Option<Vec<String>>,
+    }
+
+    let base = Config::builder("base").tags(vec!["a".to_owned()]);
+
+    let first = base.clone().level(1).build();
+    let second = base.level(2).build();
+
+    assert_eq!(first.name, "base");
+    assert_eq!(first.level, 1);
+    assert_eq!(first.tags, Some(vec!["a".to_owned()]));
+
+    assert_eq!(second.name, "base");
+    assert_eq!(second.level, 2);
+    assert_eq!(second.tags, Some(vec!["a".to_owned()]));
+}
+
+#[test]
+fn derive_debug() {
+    #[builder(derive(Debug))]
+    #[allow(dead_code)]
+    struct Config {
+        #[builder(start_fn)]
+        name: String,
+        level: u32,
+        tags: Option<Vec<String>>,
+        _timeout: Option<u32>,
+    }
+
+    let builder = Config::builder("base").tags(vec!["a".to_owned()]);
+
+    let expected = expect![[r#"
+        ConfigBuilder {
+            name: "base",
+            level: <unset>,
+            tags: Some(
+                [
+                    "a",
+                ],
+            ),
+            timeout: <unset>,
+        }"#]];
+
+    expected.assert_eq(&format!("{builder:#?}"));
+
+    let builder = builder.maybe_timeout(None).level(2);
+
+    let expected =
+        expect![[r#"ConfigBuilder { name: "base", level: 2, tags: Some(["a"]), timeout: None }"#]];
+
+    expected.assert_eq(&format!("{builder:?}"));
+}
+
+#[test]
+fn derive_partial_eq() {
+    // The type isn't stored in the builder, so it doesn't need to be `PartialEq`
+    #[derive(Default)]
+    struct Handle;
+
+    #[builder(derive(PartialEq, Eq))]
+    #[allow(dead_code)]
+    struct Config {
+        #[builder(start_fn)]
+        name: String,
+        level: u32,
+        tags: Option<Vec<String>>,
+        #[builder(skip)]
+        handle: Handle,
+    }
+
+    fn assert_eq_impl<T: Eq>(_: &T) {}
+
+    // The builders are in different states, because the setters are called
+    // in a different order, but they are still comparable
+    let from_cli = Config::builder("base").level(1).tags(vec!["a".to_owned()]);
+    let from_file = Config::builder("base").tags(vec!["a".to_owned()]).level(1);
+
+    assert_eq_impl(&from_cli);
+    assert!(from_cli == from_file);
+
+    let other_level = Config::builder("base").level(2).tags(vec!["a".to_owned()]);
+    let other_name = Config::builder("other").level(1).tags(vec!["a".to_owned()]);
+
+    assert!(from_cli != other_level);
+    assert!(from_cli != other_name);
+}
+
+#[test]
+fn requires_attr() {
+    #[builder]
+    #[derive(Debug, PartialEq)]
+    struct Connection {
+        host: Option<String>,
+
+        #[builder(requires = host)]
+        port: Option<u16>,
+
+        tls: Option<bool>,
+
+        #[builder(requires(host, tls), default, push)]
+        certs: Vec<&'static str>,
+    }
+
+    let actual = Connection::builder()
+        .host("localhost")
+        .port(443)
+        .tls(true)
+        .push_certs("ca.pem")
+        .build();
+
+    assert_eq!(
+        actual,
+        Connection {
+            host: Some("localhost".to_owned()),
+            port: Some(443),
+            tls: Some(true),
+            certs: vec!["ca.pem"],
+        }
+    );
+
+    // The requirement is satisfied regardless of the order of the setters
+    let actual = Connection::builder().tls(false).host("localhost").build();
+
+    assert_eq!(actual.port, None);
+    assert!(actual.certs.is_empty());
+}
+
+#[test]
+fn conflicts_with_attr() {
+    #[builder]
+    #[derive(Debug, PartialEq)]
+    struct Source {
+        path: Option<String>,
+
+        #[builder(conflicts_with = path)]
+        url: Option<String>,
+
+        #[builder(conflicts_with(path, url), default, push)]
+        inline: Vec<&'static str>,
+    }
+
+    let actual = Source::builder().path("config.toml").build();
+    assert_eq!(actual.path.as_deref(), Some("config.toml"));
+
+    let actual = Source::builder().url("https://example.com").build();
+    assert_eq!(actual.url.as_deref(), Some("https://example.com"));
+
+    let actual = Source::builder().push_inline("a").push_inline("b").build();
+    assert_eq!(
+        actual,
+        Source {
+            path: None,
+            url: None,
+            inline: vec!["a", "b"],
+        }
+    );
+}
+
+#[test]
+fn group_attr() {
+    #[builder]
+    #[derive(Debug, PartialEq)]
+    struct Input {
+        #[builder(group = source)]
+        path: Option<String>,
+
+        #[builder(group = source)]
+        url: Option<String>,
+
+        #[builder(group = source, default, push)]
+        lines: Vec<&'static str>,
+
+        // The members of a group may also be mutually exclusive
+        #[builder(group = "format", conflicts_with = yaml)]
+        json: Option<bool>,
+
+        #[builder(group = "format")]
+        yaml: Option<bool>,
+    }
+
+    let actual = Input::builder().path("input.json").json(true).build();
+
+    assert_eq!(
+        actual,
+        Input {
+            path: Some("input.json".to_owned()),
+            url: None,
+            lines: vec![],
+            json: Some(true),
+            yaml: None,
+        }
+    );
+
+    let actual = Input::builder()
+        .yaml(true)
+        .push_lines("a: 1")
+        .url("https://example.com")
+        .build();
+
+    assert_eq!(actual.lines, ["a: 1"]);
+    assert_eq!(actual.url.as_deref(), Some("https://example.com"));
+}
+
+#[test]
+fn section_attr() {
+    #[builder]
+    #[derive(Debug, PartialEq)]
+    struct Config<T> {
+        name: String,
+
+        /// Port of the HTTP server
+        #[builder(section = http)]
+        port: Option<u16>,
+
+        #[builder(section = http, default)]
+        keep_alive: bool,
+
+        #[builder(section = http, default, push)]
+        routes: Vec<&'static str>,
+
+        #[builder(section = "tls")]
+        cert: Option<T>,
+    }
+
+    let actual = Config::builder()
+        .http(|http| {
+            http.push_routes("/users")
+                .port(8080)
+                .keep_alive(true)
+                .push_routes("/posts")
+        })
+        .name("server")
+        .tls(|tls| tls.cert("cert.pem"))
+        .build();
+
+    assert_eq!(
+        actual,
+        Config {
+            name: "server".to_owned(),
+            port: Some(8080),
+            keep_alive: true,
+            routes: vec!["/users", "/posts"],
+            cert: Some("cert.pem"),
+        }
+    );
+
+    let actual = Config::<()>::builder()
+        .name("server")
+        .http(|http| http.maybe_port(None))
+        .build();
+
+    assert_eq!(actual.port, None);
+    assert!(!actual.keep_alive);
+    assert_eq!(actual.cert, None);
+}
+
+#[test]
+fn nested_attr() {
+    #[builder]
+    #[derive(Debug, PartialEq)]
+    struct Retry {
+        attempts: u32,
+        backoff_ms: Option<u64>,
+    }
+
+    #[builder]
+    #[derive(Debug, PartialEq)]
+    struct Client<'a> {
+        url: &'a str,
+
+        #[builder(nested)]
+        retry: Retry,
+
+        #[builder(nested)]
+        fallback: Option<Retry>,
+    }
+
+    let actual = Client::builder()
+        .retry(|retry| retry.attempts(3).backoff_ms(100))
+        .url("https://example.com")
+        .build();
+
+    assert_eq!(
+        actual,
+        Client {
+            url: "https://example.com",
+            retry: Retry {
+                attempts: 3,
+                backoff_ms: Some(100),
+            },
+            fallback: None,
+        }
+    );
+
+    let actual = Client::builder()
+        .url("https://example.com")
+        .retry(|retry| retry.attempts(1))
+        .fallback(|fallback| fallback.attempts(5))
+        .build();
+
+    assert_eq!(
+        actual.fallback,
+        Some(Retry {
+            attempts: 5,
+            backoff_ms: None,
+        })
+    );
+
+    let actual = Client::builder()
+        .url("https://example.com")
+        .retry(|retry| retry.attempts(1))
+        .maybe_fallback(Some(Retry::builder().attempts(2).build()))
+        .build();
+
+    assert_eq!(actual.fallback.map(|fallback| fallback.attempts), Some(2));
+}
+
+#[test]
+fn flatten_attr() {
+    mod net {
+        use bon::builder;
+
+        #[builder]
+        #[derive(Debug, PartialEq)]
+        pub(crate) struct Timeouts {
+            pub(crate) connect_ms: u64,
+
+            #[builder(default = 30_000)]
+            pub(crate) read_ms: u64,
+        }
+    }
+
+    // The struct is declared after the one that flattens it
+    #[builder]
+    #[derive(Debug, PartialEq)]
+    struct Client {
+        url: String,
+
+        #[builder(flatten)]
+        retry: Retry,
+
+        #[builder(flatten(prefix = fallback))]
+        fallback_retry: Retry,
+
+        #[builder(flatten)]
+        timeouts: net::Timeouts,
+    }
+
+    #[builder]
+    #[derive(Debug, PartialEq)]
+    struct Retry {
+        #[builder(validate = |attempts: &u32| if *attempts > 0 { Ok(()) } else { Err("zero") })]
+        attempts: u32,
+
+        backoff_ms: Option<u64>,
+    }
+
+    let actual = Client::builder()
+        .url("https://example.com")
+        .attempts(3)
+        .backoff_ms(100)
+        .fallback_attempts(1)
+        .connect_ms(500)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        actual,
+        Client {
+            url: "https://example.com".to_owned(),
+            retry: Retry {
+                attempts: 3,
+                backoff_ms: Some(100),
+            },
+            fallback_retry: Retry {
+                attempts: 1,
+                backoff_ms: None,
+            },
+            timeouts: net::Timeouts {
+                connect_ms: 500,
+                read_ms: 30_000,
+            },
+        }
+    );
+
+    let err = Client::builder()
+        .url("https://example.com")
+        .attempts(0)
+        .fallback_attempts(1)
+        .maybe_fallback_backoff_ms(Some(10))
+        .connect_ms(500)
+        .read_ms(1_000)
+        .build()
+        .unwrap_err();
+
+    assert_eq!(err.to_string(), "invalid value of `attempts`: zero");
+
+    let names: Vec<_> = ClientBuilder::<client_builder::Empty>::MEMBERS
+        .iter()
+        .map(|member| member.name)
+        .collect();
+
+    assert_eq!(
+        names,
+        [
+            "url",
+            "attempts",
+            "backoff_ms",
+            "fallback_attempts",
+            "fallback_backoff_ms",
+            "connect_ms",
+            "read_ms",
+        ]
+    );
+}
+
+#[test]
+fn extends_attr() {
+    #[builder]
+    #[derive(Debug, PartialEq)]
+    struct BaseConfig {
+        name: String,
+
+        #[builder(default = 4)]
+        workers: u32,
+    }
+
+    #[builder(extends = BaseConfig)]
+    #[derive(Debug, PartialEq)]
+    struct PluginConfig {
+        base: BaseConfig,
+        verbose: bool,
+    }
+
+    let actual = PluginConfig::builder()
+        .name("metrics")
+        .verbose(true)
+        .build();
+
+    assert_eq!(
+        actual,
+        PluginConfig {
+            base: BaseConfig {
+                name: "metrics".to_owned(),
+                workers: 4,
+            },
+            verbose: true,
+        }
+    );
+
+    let actual = PluginConfig::builder()
+        .name("logs")
+        .workers(1)
+        .verbose(false)
+        .build();
+
+    assert_eq!(actual.base.workers, 1);
+}
+
+#[test]
+fn finish_fn_map() {
+    use std::sync::Arc;
+
+    #[builder(finish_fn(map = |config| -> Arc<Config> { Arc::new(config) }))]
+    #[derive(Debug, PartialEq)]
+    struct Config {
+        name: &'static str,
+    }
+
+    let actual: Arc<Config> = Config::builder().name("server").build();
+
+    assert_eq!(*actual, Config { name: "server" });
+
+    #[builder(
+        finish_fn(name = finish, map = |config| -> Vec<Counter> { vec![config; 2] }),
+        validate(
+            with = |value: &u32| if *value > 0 { Ok(()) } else { Err("zero") },
+            error = String,
+        )
+    )]
+    #[derive(Debug, Clone, PartialEq)]
+    struct Counter {
+        value: u32,
+    }
+
+    let actual = Counter::builder().value(1).finish();
+
+    assert_eq!(actual, Ok(vec![Counter { value: 1 }, Counter { value: 1 }]));
+    assert_eq!(Counter::builder().value(0).finish(), Err("zero".to_owned()));
+}
+
+#[test]
+fn pre_build_attr() {
+    fn normalize(name: &mut String, retries: &mut Option<u32>) {
+        *name = name.trim().to_lowercase();
+        *retries = retries.map(|retries| retries.min(5));
+    }
+
+    #[builder(
+        pre_build = normalize,
+        validate(with = |name: &String, _: &Option<u32>| {
+            if name.is_empty() { Err("empty name") } else { Ok(()) }
+        })
+    )]
+    #[derive(Debug, PartialEq)]
+    struct Connection {
+        name: String,
+        retries: Option<u32>,
+    }
+
+    let actual = Connection::builder()
+        .name("  Primary ")
+        .retries(10)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        actual,
+        Connection {
+            name: "primary".to_owned(),
+            retries: Some(5),
+        }
+    );
+
+    let err = Connection::builder().name("   ").build().unwrap_err();
+
+    assert_eq!(err.to_string(), "empty name");
+}
+
+#[test]
+fn validate_member_attr() {
+    fn non_empty(value: &str) -> Result<(), &'static str> {
+        if value.is_empty() {
+            return Err("must not be empty");
+        }
+        Ok(())
+    }
+
+    #[builder]
+    #[derive(Debug)]
+    struct Config {
+        #[builder(validate = non_empty)]
+        name: String,
+
+        #[builder(validate = |port: &u16| if *port == 0 { Err("must not be zero") } else { Ok(()) })]
+        port: u16,
+
+        #[builder(name = host_name, validate = non_empty)]
+        host: String,
+    }
+
+    let actual = Config::builder()
+        .name("server")
+        .port(8080)
+        .host_name("localhost")
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        (actual.name.as_str(), actual.port, actual.host.as_str()),
+        ("server", 8080, "localhost")
+    );
+
+    let errors = Config::builder()
+        .name("")
+        .port(0)
+        .host_name("localhost")
+        .build()
+        .unwrap_err();
+
+    let actual: Vec<_> = errors
+        .errors()
+        .iter()
+        .map(|error| (error.member(), error.error().to_string()))
+        .collect();
+
+    assert_eq!(
+        actual,
+        [
+            ("name", "must not be empty".to_owned()),
+            ("port", "must not be zero".to_owned()),
+        ]
+    );
+
+    assert_eq!(
+        errors.to_string(),
+        "invalid value of `name`: must not be empty; \
+        invalid value of `port`: must not be zero"
+    );
+
+    // The validator of the whole builder runs only if all members are valid
+    #[builder(validate(with = |min: &u32, max: &u32| if min > max { Err("min > max") } else { Ok(()) }))]
+    #[derive(Debug)]
+    struct Range {
+        #[builder(validate = |min: &u32| if *min > 100 { Err("too big") } else { Ok(()) })]
+        min: u32,
+        max: u32,
+    }
+
+    let actual = Range::builder().min(10).max(50).build().unwrap();
+
+    assert_eq!((actual.min, actual.max), (10, 50));
+
+    let err = Range::builder().min(200).max(300).build().unwrap_err();
+
+    assert_eq!(err.to_string(), "invalid value of `min`: too big");
+
+    let err = Range::builder().min(50).max(10).build().unwrap_err();
+
+    assert_eq!(err.to_string(), "min > max");
+}
+
+#[test]
+fn finish_fn_unwrap() {
+    #[builder(finish_fn(name = try_build, unwrap = build))]
+    #[derive(Debug)]
+    struct Config {
+        #[builder(validate = |port: &u16| if *port == 0 { Err("must not be zero") } else { Ok(()) })]
+        port: u16,
+    }
+
+    assert_eq!(Config::builder().port(80).build().port, 80);
+    assert_eq!(Config::builder().port(80).try_build().unwrap().port, 80);
+
+    let panic = std::panic::catch_unwind(|| Config::builder().port(0).build()).unwrap_err();
+
+    assert_eq!(
+        panic.downcast_ref::<String>().map(String::as_str),
+        Some("`ConfigBuilder::try_build()` failed: invalid value of `port`: must not be zero")
+    );
+}
+
+#[test]
+fn check_runtime() {
+    #[builder(check = "runtime", derive(Clone, Debug, PartialEq))]
+    #[derive(Debug, PartialEq)]
+    struct Config<T> {
+        name: String,
+        level: T,
+        tags: Option<Vec<String>>,
+
+        #[builder(default = 8080)]
+        port: u16,
+    }
+
+    // The setters can be called repeatedly and conditionally, because
+    // the builder has the same type after each of them
+    let mut builder = Config::builder().name("old").level(1);
+    builder = builder.name("new");
+
+    if builder.clone().build().level == 1 {
+        builder = builder.port(80);
+    }
+
+    assert_eq!(
+        format!("{builder:?}"),
+        r#"ConfigBuilder { name: "new", level: 1, tags: <unset>, port: Some(80) }"#
+    );
+    assert_eq!(builder.clone(), builder);
+    assert_eq!(
+        builder.build(),
+        Config {
+            name: "new".to_owned(),
+            level: 1,
+            tags: None,
+            port: 80,
+        }
+    );
+
+    let panic = std::panic::catch_unwind(|| Config::builder().level(1).build()).unwrap_err();
+
+    assert_eq!(
+        panic.downcast_ref::<String>().map(String::as_str),
+        Some("the required member `name` of `ConfigBuilder` wasn't set")
+    );
+}
+
+#[test]
+fn dyn_builder_attr() {
+    #[builder(
+        check = "runtime",
+        dyn_builder,
+        validate = |port: &u16, _: &Option<String>| if *port == 0 { Err("zero port") } else { Ok(()) },
+    )]
+    struct Server {
+        port: u16,
+        host: Option<String>,
+    }
+
+    fn configure_host(builder: &mut dyn ServerDynBuilder) {
+        builder.set_host("localhost".to_owned());
+    }
+
+    let mut builders: Vec<Box<dyn ServerDynBuilder>> =
+        vec![Box::new(Server::builder()), Box::new(Server::builder())];
+
+    for (builder, port) in builders.iter_mut().zip([80, 0]) {
+        configure_host(&mut **builder);
+        builder.set_port(port);
+    }
+
+    let mut results = builders.into_iter().map(|builder| builder.try_build());
+
+    let server = results.next().unwrap().unwrap();
+    assert_eq!(server.port, 80);
+    assert_eq!(server.host.as_deref(), Some("localhost"));
+
+    assert_eq!(
+        results.next().unwrap().err().unwrap().to_string(),
+        "validation failed: zero port"
+    );
+
+    let missing: Box<dyn ServerDynBuilder> = Box::new(Server::builder());
+
+    assert!(matches!(
+        missing.try_build(),
+        Err(bon::dyn_builder::Error::MissingMember { name: "port" })
+    ));
+}
+
+#[test]
+fn large_members() {
+    #[builder]
+    struct Buffers {
+        head: [u8; 4096],
+        tail: [u8; 4096],
+        #[builder(default, push)]
+        names: Vec<String>,
+    }
+
+    let buffers = Buffers::builder()
+        .head([1; 4096])
+        .push_names("a")
+        .tail([2; 4096])
+        .push_names("b")
+        .build();
+
+    assert_eq!(buffers.head, [1; 4096]);
+    assert_eq!(buffers.tail, [2; 4096]);
+    assert_eq!(buffers.names, ["a", "b"]);
+}
+
+#[test]
+fn preset_attr() {
+    #[builder(
+        preset(name = localhost, url = "http://127.0.0.1", tls = false),
+        preset(name = "secure", tls = true, retries = 3)
+    )]
+    #[derive(Debug, PartialEq)]
+    struct Connection {
+        url: String,
+        tls: bool,
+        retries: Option<u32>,
+    }
+
+    let actual = Connection::localhost().retries(5).build();
+
+    assert_eq!(
+        actual,
+        Connection {
+            url: "http://127.0.0.1".to_owned(),
+            tls: false,
+            retries: Some(5),
+        }
+    );
+
+    let actual = Connection::secure().url("https://example.com").build();
+
+    assert_eq!(
+        actual,
+        Connection {
+            url: "https://example.com".to_owned(),
+            tls: true,
+            retries: Some(3),
+        }
+    );
+
+    #[builder(preset(name = origin, y = 0))]
+    struct Point {
+        #[builder(start_fn)]
+        x: i32,
+        y: i32,
+    }
+
+    let actual = Point::origin(2).build();
+
+    assert_eq!((actual.x, actual.y), (2, 0));
+}
+
+#[test]
+fn cfg_members() {
+    #[builder(derive(Debug))]
+    struct Config {
+        level: u32,
+
+        // The type doesn't exist, so the build would fail if the builder
+        // referenced it anywhere
+        #[cfg(any())]
+        tls: NonExistentType,
+
+        #[cfg(not(any()))]
+        timeout: Option<u32>,
+    }
+
+    let builder = Config::builder().level(1).timeout(2);
+
+    let expected = expect!["ConfigBuilder { level: 1, timeout: Some(2) }"];
+    expected.assert_eq(&format!("{builder:?}"));
+
+    let config = builder.build();
+
+    assert_eq!(config.level, 1);
+    assert_eq!(config.timeout, Some(2));
+
+    // The predicates are evaluated one by one, so there is no limit on their number
+    #[builder]
+    struct Many {
+        #[cfg(any())]
+        a: NonExistentType,
+        #[cfg(not(any()))]
+        b: u32,
+        #[cfg(all(unix, not(unix)))]
+        c: NonExistentType,
+        #[cfg(any(unix, not(unix)))]
+        d: u32,
+        #[cfg(not(any(unix, not(unix))))]
+        e: NonExistentType,
+        #[cfg(not(all(unix, not(unix))))]
+        f: u32,
+    }
+
+    let actual = Many::builder().b(1).d(2).f(3).build();
+
+    assert_eq!((actual.b, actual.d, actual.f), (1, 2, 3));
+}
+
+#[test]
+fn cfg_attr_members() {
+    #[builder]
+    struct Config {
+        #[cfg_attr(not(any()), builder(default = 5))]
+        level: u32,
+
+        #[cfg_attr(any(), builder(start_fn))]
+        timeout: u32,
+    }
+
+    let config = Config::builder().timeout(2).build();
+
+    assert_eq!(config.level, 5);
+    assert_eq!(config.timeout, 2);
+}
+
+#[test]
+fn generic_over_state() {
+    use bon::state::{IsSet, IsUnset};
+
+    #[builder]
+    #[derive(Debug)]
+    struct Request {
+        url: String,
+        retries: Option<u32>,
+        #[builder(default = 30)]
+        timeout: u32,
+    }
+
+    fn with_defaults<S>(builder: RequestBuilder<S>) -> Request
+    where
+        S: request_builder::State,
+        S::Url: IsSet,
+        S::Retries: IsUnset,
+    {
+        builder.retries(3).build()
+    }
+
+    let request = with_defaults(Request::builder().url("https://example.com"));
+
+    assert_eq!(request.url, "https://example.com");
+    assert_eq!(request.retries, Some(3));
+    assert_eq!(request.timeout, 30);
+
+    let request = with_defaults(Request::builder().timeout(10).url("https://example.com"));
+
+    assert_eq!(request.retries, Some(3));
+    assert_eq!(request.timeout, 10);
+}
+
+#[test]
+fn named_state_types() {
+    #[builder]
+    struct Sut {
+        x1: u32,
+        x2: Option<u32>,
+        x3: u32,
+    }
+
+    let builder: SutBuilder = Sut::builder();
+    let builder: SutBuilder<sut_builder::SetX1> = builder.x1(1);
+    let builder: SutBuilder<sut_builder::SetX3<sut_builder::SetX1>> = builder.x3(3);
+
+    let actual = builder.build();
+
+    assert_eq!((actual.x1, actual.x2, actual.x3), (1, None, 3));
+
+    // The states are named after the public names of the members
+    #[builder]
+    struct Request {
+        #[builder(name = url)]
+        raw_url: String,
+        body: Option<String>,
+    }
+
+    struct Client {
+        base: RequestBuilder<request_builder::SetUrl>,
+    }
+
+    let client = Client {
+        base: Request::builder().url("https://example.com"),
+    };
+
+    let request = client.base.body("body").build();
+
+    assert_eq!(request.raw_url, "https://example.com");
+    assert_eq!(request.body.as_deref(), Some("body"));
+}
+
+#[test]
+fn const_generics() {
+    #[builder(derive(Clone, Debug))]
+    #[derive(Debug)]
+    struct Sut<T: Copy, const N: usize> {
+        values: [T; N],
+        #[builder(default = [0; N])]
+        weights: [u8; N],
+        label: Option<[char; N]>,
+    }
+
+    fn with_label<S, const N: usize>(builder: SutBuilder<u32, N, S>) -> Sut<u32, N>
+    where
+        S: sut_builder::State<Label = bon::state::Unset>,
+        S::Values: bon::state::IsSet,
+    {
+        builder.label(['x'; N]).build()
+    }
+
+    let builder: SutBuilder<u32, 2, sut_builder::SetValues> = Sut::builder().values([1, 2]);
+    let sut = with_label(builder.clone());
+
+    assert_eq!(sut.values, [1, 2]);
+    assert_eq!(sut.weights, [0, 0]);
+    assert_eq!(sut.label, Some(['x', 'x']));
+
+    let sut = builder.weights([3, 4]).build();
+
+    assert_eq!(sut.weights, [3, 4]);
+    assert_eq!(sut.label, None);
+}
+
+#[test]
+fn option_option() {
+    #[builder]
+    #[derive(Debug, PartialEq)]
+    struct Patch {
+        name: Option<Option<String>>,
+        #[builder(into)]
+        note: Option<Option<String>>,
+    }
+
+    let patch = Patch::builder().build();
+    assert_eq!(
+        patch,
+        Patch {
+            name: None,
+            note: None
+        }
+    );
+
+    let patch = Patch::builder()
+        .name(None)
+        .note(Some("note".to_owned()))
+        .build();
+    assert_eq!(patch.name, Some(None));
+    assert_eq!(patch.note, Some(Some("note".to_owned())));
+
+    let patch = Patch::builder()
+        .maybe_name(Some(Some("name".to_owned())))
+        .maybe_note(None::<Option<String>>)
+        .build();
+
+    assert_eq!(patch.name, Some(Some("name".to_owned())));
+    assert_eq!(patch.note, None);
+}
+
+#[test]
+fn overwritable_attr() {
+    #[builder]
+    struct Config {
+        #[builder(overwritable)]
+        level: u32,
+
+        #[builder(overwritable)]
+        name: Option<String>,
+
+        port: u16,
+    }
+
+    let config = Config::builder()
+        .level(1)
+        .name("base")
+        .port(80)
+        .level(2)
+        .maybe_name(None::<String>)
+        .build();
+
+    assert_eq!(config.level, 2);
+    assert_eq!(config.name, None);
+    assert_eq!(config.port, 80);
+
+    let mut builder = Config::builder().level(0).port(8080);
+
+    for level in [3, 4, 5] {
+        builder = builder.level(level);
+    }
+
+    let config = builder.build();
+
+    assert_eq!(config.level, 5);
+    assert_eq!(config.port, 8080);
+}
+
+#[test]
+fn maybe_attr() {
+    #[builder(maybe = opt_)]
+    struct Sut {
+        x1: Option<u32>,
+
+        #[builder(maybe = false)]
+        x2: Option<u32>,
+
+        #[builder(default, maybe = maybe_)]
+        x3: u32,
+    }
+
+    let actual = Sut::builder().opt_x1(Some(1)).x2(2).maybe_x3(None).build();
+
+    assert_eq!((actual.x1, actual.x2, actual.x3), (Some(1), Some(2), 0));
+}
+
+#[test]
+fn as_ref_attr() {
+    use std::path::{Path, PathBuf};
+
+    #[builder]
+    struct Sut {
+        #[builder(as_ref)]
+        path: PathBuf,
+
+        #[builder(as_ref)]
+        name: Option<String>,
+
+        #[builder(as_ref)]
+        bytes: Vec<u8>,
+
+        #[builder(as_ref = Path)]
+        dir: PathBuf,
+    }
+
+    let path = PathBuf::from("/tmp");
+    let bytes = [1, 2];
+
+    let actual = Sut::builder()
+        .path(&path)
+        .name("name")
+        .bytes(bytes)
+        .dir(Path::new("/var"))
+        .build();
+
+    assert_eq!(actual.path, path);
+    assert_eq!(actual.name.as_deref(), Some("name"));
+    assert_eq!(actual.bytes, [1, 2]);
+    assert_eq!(actual.dir, Path::new("/var"));
+
+    let actual = Sut::builder()
+        .path("/tmp")
+        .maybe_name(Some(String::from("name")))
+        .bytes(vec![3])
+        .dir("/var")
+        .build();
+
+    assert_eq!(actual.path, path);
+    assert_eq!(actual.name.as_deref(), Some("name"));
+    assert_eq!(actual.bytes, [3]);
+}
+
+#[test]
+fn env_attr() {
+    // Cargo sets these variables when running the tests
+    #[builder]
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct Config {
+        #[builder(env = "CARGO_PKG_NAME")]
+        name: Option<String>,
+
+        #[builder(env = "CARGO_PKG_VERSION_MAJOR", default = 10)]
+        major: u32,
+
+        #[builder(env = "BON_TEST_UNSET_VARIABLE", default = 10)]
+        minor: u32,
+
+        #[builder(env = "BON_TEST_UNSET_VARIABLE")]
+        patch: Option<u32>,
+    }
+
+    let actual = Config::builder().build().unwrap();
+
+    expect![[r#"
+        Config {
+            name: Some(
+                "bon",
+            ),
+            major: 1,
+            minor: 10,
+            patch: None,
+        }
+    "#]]
+    .assert_debug_eq(&actual);
+
+    // The values set explicitly take precedence over the environment
+    let actual = Config::builder().major(5).build().unwrap();
+
+    assert_eq!(actual.major, 5);
+
+    // The values that can't be parsed are reported as errors of the members
+    #[builder]
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct Invalid {
+        #[builder(env = "CARGO_PKG_NAME", default = 10)]
+        level: u32,
+    }
+
+    let err = Invalid::builder().build().unwrap_err();
+
+    assert_eq!(err.errors()[0].member(), "level");
+    assert_eq!(
+        err.to_string(),
+        "invalid value of `level`: invalid value of the environment variable \
+        `CARGO_PKG_NAME`: invalid digit found in string"
+    );
+}
+
+#[cfg(feature = "clap")]
+#[test]
+fn derive_args() {
+    use clap::{Args, FromArgMatches};
+
+    #[builder(derive(Args))]
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct Config {
+        /// URL of the server
+        url: String,
+
+        #[builder(name = retry_count)]
+        retries: Option<u32>,
+
+        #[builder(flag)]
+        verbose: bool,
+
+        timeout: u32,
+    }
+
+    type State = config_builder::SetVerbose<config_builder::SetRetryCount<config_builder::SetUrl>>;
+
+    let command = ConfigBuilder::<State>::augment_args(clap::Command::new("app"));
+
+    let matches = command
+        .clone()
+        .try_get_matches_from(["app", "--url", "https://example.com", "--retry-count", "3"])
+        .unwrap();
+
+    let builder = ConfigBuilder::<State>::from_arg_matches(&matches).unwrap();
+
+    // The members that aren't part of the state can still be set in code
+    let actual = builder.timeout(10).build();
+
+    expect![[r#"
+        Config {
+            url: "https://example.com",
+            retries: Some(
+                3,
+            ),
+            verbose: false,
+            timeout: 10,
+        }
+    "#]]
+    .assert_debug_eq(&actual);
+
+    let err = command.clone().try_get_matches_from(["app"]).unwrap_err();
+    assert_eq!(err.kind(), clap::error::ErrorKind::MissingRequiredArgument);
+
+    let err = command
+        .clone()
+        .try_get_matches_from(["app", "--url", "x", "--timeout", "1"])
+        .unwrap_err();
+    assert_eq!(err.kind(), clap::error::ErrorKind::UnknownArgument);
+
+    let url = command
+        .get_arguments()
+        .find(|arg| arg.get_id() == "url")
+        .unwrap();
+
+    assert_eq!(url.get_help().unwrap().to_string(), "URL of the server");
+}
+
+#[cfg(feature = "schemars")]
+#[test]
+fn derive_json_schema() {
+    use schemars::JsonSchema;
+
+    #[builder(derive(JsonSchema))]
+    #[allow(dead_code)]
+    struct Config {
+        /// URL of the server
+        url: String,
+
+        #[builder(name = retry_count)]
+        retries: Option<u32>,
+
+        #[builder(default = 30)]
+        timeout: u32,
+
+        #[builder(flag)]
+        verbose: bool,
+    }
+
+    let schema = schemars::schema_for!(ConfigBuilder);
+
+    let expected = serde_json::json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "ConfigBuilder",
+        "type": "object",
+        "properties": {
+            "retry_count": {
+                "type": "integer",
+                "format": "uint32",
+                "minimum": 0
+            },
+            "timeout": {
+                "type": "integer",
+                "format": "uint32",
+                "minimum": 0,
+                "default": 30
+            },
+            "url": {
+                "type": "string",
+                "description": "URL of the server"
+            },
+            "verbose": {
+                "type": "boolean",
+                "default": false
+            }
+        },
+        "required": ["url"],
+        "additionalProperties": false
+    });
+
+    assert_eq!(serde_json::to_value(&schema).unwrap(), expected);
+
+    // The id doesn't depend on the type state, so the schema is defined only once
+    assert_eq!(
+        ConfigBuilder::<config_builder::Empty>::schema_id(),
+        ConfigBuilder::<config_builder::SetUrl>::schema_id(),
+    );
+
+    #[builder(derive(JsonSchema))]
+    #[allow(dead_code)]
+    struct Generic<T, const N: usize> {
+        values: [T; N],
+    }
+
+    assert_eq!(
+        GenericBuilder::<u32, 2, generic_builder::Empty>::schema_id(),
+        GenericBuilder::<u32, 2, generic_builder::SetValues>::schema_id(),
+    );
+    assert_ne!(
+        GenericBuilder::<u32, 2>::schema_id(),
+        GenericBuilder::<u64, 2>::schema_id(),
+    );
+    assert_ne!(
+        GenericBuilder::<u32, 2>::schema_id(),
+        GenericBuilder::<u32, 3>::schema_id(),
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn derive_deserialize() {
+    #[builder(derive(Debug, Deserialize))]
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct Config {
+        url: String,
+
+        #[builder(name = retry_count)]
+        retries: Option<u32>,
+
+        verbose: bool,
+    }
+
+    let builder: ConfigBuilder<config_builder::SetRetryCount<config_builder::SetUrl>> =
+        serde_json::from_str(r#"{ "url": "https://example.com", "retry_count": 3 }"#).unwrap();
+
+    let actual = builder.verbose(true).build();
+
+    expect![[r#"
+        Config {
+            url: "https://example.com",
+            retries: Some(
+                3,
+            ),
+            verbose: true,
+        }
+    "#]]
+    .assert_debug_eq(&actual);
+
+    let err = serde_json::from_str::<ConfigBuilder<config_builder::SetUrl>>(r#"{}"#).unwrap_err();
+
+    assert_eq!(err.to_string(), "missing field `url`");
+
+    // The values of the unset members are accepted, and the setters override them
+    let builder: ConfigBuilder<config_builder::SetUrl> = serde_json::from_str(
+        r#"{ "url": "https://example.com", "retry_count": 3, "verbose": false }"#,
+    )
+    .unwrap();
+
+    let actual = builder.retry_count(5).verbose(true).build();
+
+    assert_eq!(actual.retries, Some(5));
+    assert!(actual.verbose);
+
+    let builder: ConfigBuilder<config_builder::SetUrl> =
+        serde_json::from_str(r#"{ "url": "https://example.com", "retry_count": 3 }"#).unwrap();
+
+    assert_eq!(builder.verbose(false).build().retries, Some(3));
+
+    // The optional members may be set to `None`, so their keys may be missing
+    let builder: ConfigBuilder<config_builder::SetRetryCount<config_builder::SetUrl>> =
+        serde_json::from_str(r#"{ "url": "https://example.com" }"#).unwrap();
+
+    assert_eq!(builder.verbose(false).build().retries, None);
+
+    #[builder(derive(Deserialize))]
+    struct Generic<T> {
+        value: T,
+    }
+
+    let builder: GenericBuilder<u32, generic_builder::SetValue> =
+        serde_json::from_str(r#"{ "value": 42 }"#).unwrap();
+
+    assert_eq!(builder.build().value, 42);
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn dynamic_attr() {
+    use bon::dynamic::Error;
+    use serde_json::json;
+
+    #[builder(dynamic)]
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct Config {
+        url: String,
+
+        #[builder(name = retry_count)]
+        retries: Option<u32>,
+
+        #[builder(default = 10)]
+        timeout: u32,
+    }
+
+    let mut builder = Config::builder();
+
+    builder
+        .set_by_name("url", json!("https://example.com"))
+        .unwrap();
+    builder.set_by_name("retry_count", json!(3)).unwrap();
+
+    let actual = builder.try_build().unwrap();
+
+    expect![[r#"
+        Config {
+            url: "https://example.com",
+            retries: Some(
+                3,
+            ),
+            timeout: 10,
+        }
+    "#]]
+    .assert_debug_eq(&actual);
+
+    let mut builder = Config::builder();
+
+    let err = builder.set_by_name("retries", json!(3)).unwrap_err();
+    assert!(matches!(err, Error::UnknownMember { name } if name == "retries"));
+
+    let err = builder.set_by_name("timeout", json!("long")).unwrap_err();
+    assert!(matches!(
+        err,
+        Error::InvalidValue {
+            name: "timeout",
+            ..
+        }
+    ));
+
+    let err = builder.try_build().unwrap_err();
+    assert!(matches!(err, Error::MissingMember { name: "url" }));
+
+    // Members set via the typed setters can't be overwritten dynamically
+    let mut builder = Config::builder().timeout(5);
+
+    let err = builder.set_by_name("timeout", json!(7)).unwrap_err();
+    assert!(matches!(err, Error::AlreadySet { name: "timeout" }));
+
+    builder
+        .set_by_name("url", json!("https://example.com"))
+        .unwrap();
+
+    assert_eq!(builder.try_build().unwrap().timeout, 5);
+}
+
 // This is based on the issue https://github.com/elastio/bon/issues/8
 #[test]
 #[allow(non_camel_case_types)]
@@ -89,3 +2093,766 @@ fn raw_identifiers() {
 
     let _: r#type = Sut::builder();
 }
+
+#[test]
+fn setter_prefix() {
+    #[builder(setter_prefix = "with_")]
+    struct Sut {
+        r#type: u32,
+
+        #[builder(name = label)]
+        _name: Option<String>,
+
+        #[builder(default)]
+        count: u32,
+    }
+
+    let actual = Sut::builder()
+        .with_type(1)
+        .with_label("label")
+        .with_maybe_count(Some(2))
+        .build();
+
+    assert_eq!(actual.r#type, 1);
+    assert_eq!(actual._name.as_deref(), Some("label"));
+    assert_eq!(actual.count, 2);
+}
+
+#[test]
+fn alias_attr() {
+    #[builder]
+    struct Sut {
+        #[builder(alias = "retries", deprecated = "use `retry_count` instead")]
+        retry_count: u32,
+
+        #[builder(alias = label, try_into)]
+        name: Option<String>,
+    }
+
+    #[allow(deprecated)]
+    let actual = Sut::builder().retries(3).maybe_label(Some("old")).build();
+
+    assert_eq!(actual.retry_count, 3);
+    assert_eq!(actual.name.as_deref(), Some("old"));
+
+    let actual = Sut::builder()
+        .retry_count(4)
+        .try_label("old")
+        .unwrap()
+        .build();
+
+    assert_eq!(actual.retry_count, 4);
+    assert_eq!(actual.name.as_deref(), Some("old"));
+}
+
+#[test]
+fn start_fn_on_other_type() {
+    struct Client;
+
+    #[builder(start_fn(on = Client))]
+    #[derive(Debug)]
+    struct Request<'a, T> {
+        path: &'a str,
+        body: Option<T>,
+    }
+
+    let actual = Client::request().path("/users").body(42).build();
+
+    assert_eq!(actual.path, "/users");
+    assert_eq!(actual.body, Some(42));
+
+    #[builder(start_fn(on = Client, name = get, vis = ""))]
+    struct GetRequest {
+        path: String,
+    }
+
+    let actual = Client::get().path("/users").build();
+
+    assert_eq!(actual.path, "/users");
+
+    struct GenericClient<T>(T);
+
+    #[builder(start_fn(on = "GenericClient<u32>"))]
+    struct Response {
+        status: u16,
+    }
+
+    let actual = GenericClient::<u32>::response().status(200).build();
+
+    assert_eq!(actual.status, 200);
+
+    // Keywords that can't be raw identifiers get a trailing underscore
+    #[builder(start_fn(on = Client))]
+    struct Super {
+        level: u32,
+    }
+
+    let actual = Client::super_().level(1).build();
+
+    assert_eq!(actual.level, 1);
+}
+
+#[test]
+fn implements_trait() {
+    trait Build<T> {
+        fn build(self) -> T;
+    }
+
+    #[builder(implements = "Build<Config>")]
+    #[derive(Debug, PartialEq)]
+    struct Config {
+        name: String,
+        level: Option<u32>,
+    }
+
+    fn finish(builder: impl Build<Config>) -> Config {
+        builder.build()
+    }
+
+    let actual = finish(Config::builder().name("config"));
+
+    assert_eq!(
+        actual,
+        Config {
+            name: "config".to_owned(),
+            level: None
+        }
+    );
+
+    // The setters that take `&mut self` are mapped too, and the trait is
+    // dyn-compatible if the finishing function requires `Self: Sized`
+    trait Settings {
+        fn level(&mut self, value: u32);
+        fn maybe_label(&mut self, value: Option<String>);
+        fn build(self) -> Server
+        where
+            Self: Sized;
+    }
+
+    #[builder(mut_setters, implements(Settings, methods(level, maybe_label, build)))]
+    #[derive(Debug, PartialEq)]
+    struct Server {
+        name: String,
+        level: Option<u32>,
+        label: Option<String>,
+    }
+
+    fn configure(settings: &mut dyn Settings) {
+        settings.level(3);
+        settings.maybe_label(Some("label".to_owned()));
+    }
+
+    let mut builder = Server::builder().name("server");
+    configure(&mut builder);
+
+    assert_eq!(
+        Settings::build(builder),
+        Server {
+            name: "server".to_owned(),
+            level: Some(3),
+            label: Some("label".to_owned()),
+        }
+    );
+
+    // Without the finishing function the trait is implemented in any state
+    trait Level {
+        fn level(&mut self, value: u32);
+    }
+
+    #[builder(mut_setters, implements(Level, methods(level)))]
+    struct Client {
+        name: String,
+        level: Option<u32>,
+    }
+
+    let mut builder = Client::builder();
+    Level::level(&mut builder, 5);
+
+    let actual = builder.name("client").build();
+
+    assert_eq!(actual.name, "client");
+    assert_eq!(actual.level, Some(5));
+}
+
+#[test]
+fn closure_fn() {
+    #[builder(closure_fn)]
+    struct Sut<'a, T> {
+        arg1: &'a str,
+        arg2: Option<T>,
+    }
+
+    let actual = Sut::builder_with(|b| b.arg1("a").arg2(42));
+
+    assert_eq!(actual.arg1, "a");
+    assert_eq!(actual.arg2, Some(42));
+
+    struct Client;
+
+    #[builder(start_fn(on = Client), closure_fn)]
+    struct Request<T> {
+        body: T,
+    }
+
+    let actual = Client::request_with(|b| b.body(true));
+
+    assert!(actual.body);
+}
+
+#[test]
+fn member_enum() {
+    #[builder(member_enum)]
+    #[allow(dead_code)]
+    struct Sut {
+        _arg1: u32,
+
+        #[builder(name = renamed)]
+        arg2: Option<String>,
+
+        r#type: bool,
+    }
+
+    assert_eq!(
+        SutBuilderMember::ALL,
+        [
+            SutBuilderMember::Arg1,
+            SutBuilderMember::Renamed,
+            SutBuilderMember::Type
+        ]
+    );
+
+    let names = SutBuilderMember::ALL.iter().map(SutBuilderMember::as_str);
+
+    assert_eq!(names.collect::<Vec<_>>(), ["arg1", "renamed", "type"]);
+    assert_eq!(SutBuilderMember::Renamed.to_string(), "renamed");
+}
+
+#[test]
+fn members_info() {
+    #[builder]
+    #[allow(dead_code)]
+    struct Sut<'a> {
+        /// The first line.
+        /// The second line.
+        #[builder(start_fn)]
+        id: u32,
+
+        #[builder(name = renamed)]
+        arg2: Option<Vec<&'a str>>,
+
+        #[builder(default = 3)]
+        level: u32,
+
+        r#type: ::std::string::String,
+    }
+
+    expect![[r#"
+        [
+            MemberInfo {
+                name: "id",
+                type_name: "u32",
+                required: true,
+                has_default: false,
+                docs: "The first line.\nThe second line.",
+            },
+            MemberInfo {
+                name: "renamed",
+                type_name: "Option<Vec<&'a str>>",
+                required: false,
+                has_default: false,
+                docs: "",
+            },
+            MemberInfo {
+                name: "level",
+                type_name: "u32",
+                required: false,
+                has_default: true,
+                docs: "",
+            },
+            MemberInfo {
+                name: "type",
+                type_name: "::std::string::String",
+                required: true,
+                has_default: false,
+                docs: "",
+            },
+        ]
+    "#]]
+    .assert_debug_eq(&SutBuilder::<sut_builder::Empty>::MEMBERS);
+}
+
+#[test]
+fn default_from() {
+    use std::num::NonZeroU16;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[builder(default_from = Default)]
+    #[derive(Debug, PartialEq)]
+    struct Sut {
+        level: u32,
+        name: String,
+        tag: Option<&'static str>,
+
+        #[builder(default)]
+        explicit_default: u32,
+
+        #[builder(default = 99)]
+        explicit_value: u32,
+    }
+
+    impl Default for Sut {
+        fn default() -> Self {
+            Self {
+                level: 1,
+                name: "default".to_owned(),
+                tag: Some("tag"),
+                explicit_default: 42,
+                explicit_value: 42,
+            }
+        }
+    }
+
+    assert_eq!(
+        Sut::builder().name("custom").build(),
+        Sut {
+            level: 1,
+            name: "custom".to_owned(),
+            tag: Some("tag"),
+            explicit_default: 0,
+            explicit_value: 99,
+        }
+    );
+
+    #[builder(default_from = Config::preset(3))]
+    struct Config {
+        level: u32,
+        name: String,
+    }
+
+    impl Config {
+        fn preset(level: u32) -> Self {
+            Self {
+                level,
+                name: "preset".to_owned(),
+            }
+        }
+    }
+
+    let actual = Config::builder().name("custom").build();
+
+    assert_eq!(actual.level, 3);
+    assert_eq!(actual.name, "custom");
+
+    // The explicit `None` isn't replaced with the value from the instance
+    let actual = Sut::builder().maybe_tag(None).build();
+
+    assert_eq!(actual.tag, None);
+
+    // The instance is created lazily, and its fields are moved out of it,
+    // so their types don't need to implement `Default`
+    #[builder(default_from = Limits::new())]
+    struct Limits {
+        port: NonZeroU16,
+        tag: Option<NonZeroU16>,
+    }
+
+    static CREATED: AtomicUsize = AtomicUsize::new(0);
+
+    impl Limits {
+        fn new() -> Self {
+            CREATED.fetch_add(1, Ordering::Relaxed);
+            Self {
+                port: NonZeroU16::new(8080).unwrap(),
+                tag: NonZeroU16::new(1),
+            }
+        }
+    }
+
+    let port = NonZeroU16::new(80).unwrap();
+    let actual = Limits::builder()
+        .port(port)
+        .maybe_tag(None::<NonZeroU16>)
+        .build();
+
+    assert_eq!(actual.port, port);
+    assert_eq!(actual.tag, None);
+    assert_eq!(CREATED.load(Ordering::Relaxed), 0);
+
+    let actual = Limits::builder().build();
+
+    assert_eq!(actual.port.get(), 8080);
+    assert_eq!(actual.tag, NonZeroU16::new(1));
+    assert_eq!(CREATED.load(Ordering::Relaxed), 1);
+}
+
+#[test]
+fn track_caller_attr() {
+    use std::panic::Location;
+
+    // The default value is evaluated in the finishing function, so the caller
+    // location is the place where the builder is finished
+    #[builder(track_caller, closure_fn)]
+    struct Sut {
+        #[builder(default = Location::caller().line())]
+        line: u32,
+    }
+
+    let actual = Sut::builder().build();
+    assert_eq!(actual.line, line!() - 1);
+
+    let actual = Sut::builder_with(|builder| builder);
+    assert_eq!(actual.line, line!() - 1);
+
+    let actual = Sut::builder().line(99).build();
+    assert_eq!(actual.line, 99);
+
+    // The finishing function may panic because of the missing members, so
+    // it's `#[track_caller]` even without the attribute
+    #[builder(check = "runtime")]
+    struct RuntimeChecks {
+        #[builder(default = Location::caller().line())]
+        line: u32,
+    }
+
+    let actual = RuntimeChecks::builder().build();
+    assert_eq!(actual.line, line!() - 1);
+}
+
+#[test]
+fn state_var_collision() {
+    #[allow(non_camel_case_types)]
+    #[derive(Debug, PartialEq)]
+    struct __State(u32);
+
+    #[builder]
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct Sut<__State2> {
+        state: __State,
+        value: __State2,
+    }
+
+    let actual = Sut::builder().state(__State(1)).value("value").build();
+
+    assert_eq!(actual.state, __State(1));
+    assert_eq!(actual.value, "value");
+
+    #[builder]
+    fn pair<__State3: Copy>(state: __State, value: __State3) -> (__State, __State3) {
+        (state, value)
+    }
+
+    assert_eq!(pair().state(__State(2)).value(3).call(), (__State(2), 3));
+}
+
+mod facade {
+    pub(crate) use bon as bon_reexport;
+}
+
+#[test]
+fn crate_attr() {
+    #[builder(crate = crate::builder_on_struct::facade::bon_reexport, derive(Clone, Debug))]
+    #[derive(Debug, PartialEq)]
+    struct Sut {
+        #[cfg_attr(all(), builder(default = 1))]
+        required: u32,
+        optional: Option<&'static str>,
+    }
+
+    let builder = Sut::builder().optional("value");
+
+    assert_eq!(
+        builder.clone().build(),
+        Sut {
+            required: 1,
+            optional: Some("value"),
+        }
+    );
+
+    assert_eq!(
+        format!("{builder:?}"),
+        r#"SutBuilder { required: <unset>, optional: Some("value") }"#
+    );
+
+    #[builder(crate = ::bon)]
+    fn double(arg: u32) -> u32 {
+        arg * 2
+    }
+
+    assert_eq!(double().arg(2).call(), 4);
+}
+
+#[test]
+fn keep_leading_underscore() {
+    #[builder(keep_leading_underscore)]
+    #[derive(Debug, PartialEq)]
+    struct Sut {
+        internal: u32,
+        _internal: Option<u32>,
+    }
+
+    let actual = Sut::builder().internal(1)._maybe_internal(Some(2)).build();
+
+    assert_eq!(
+        actual,
+        Sut {
+            internal: 1,
+            _internal: Some(2),
+        }
+    );
+
+    #[builder(keep_leading_underscore)]
+    fn identity(_arg: u32) -> u32 {
+        _arg
+    }
+
+    assert_eq!(identity()._arg(3).call(), 3);
+}
+
+#[test]
+fn merge_attr() {
+    #[builder(merge)]
+    #[derive(Debug, PartialEq)]
+    struct Config {
+        #[builder(start_fn)]
+        name: &'static str,
+        url: String,
+        port: Option<u16>,
+        verbose: Option<bool>,
+    }
+
+    let defaults = Config::builder("defaults").port(80).verbose(false);
+    let file = Config::builder("file")
+        .url("https://example.com")
+        .verbose(true);
+    let cli = Config::builder("cli").port(8080);
+
+    type Defaults = config_builder::SetVerbose<config_builder::SetPort>;
+    type File = config_builder::SetVerbose<config_builder::SetUrl>;
+    type Cli = config_builder::SetPort;
+
+    let builder: ConfigBuilder<
+        config_builder::Merged<config_builder::Merged<Defaults, File>, Cli>,
+    > = defaults.merge(file).merge(cli);
+
+    assert_eq!(
+        builder.build(),
+        Config {
+            name: "defaults",
+            url: "https://example.com".to_owned(),
+            port: Some(8080),
+            verbose: Some(true),
+        }
+    );
+
+    let config = Config::builder("empty")
+        .merge(Config::builder("url").url("https://example.com"))
+        .build();
+
+    assert_eq!(config.name, "empty");
+    assert_eq!(config.port, None);
+}
+
+#[test]
+fn finish_fn_clone_attr() {
+    #[builder(finish_fn_clone)]
+    #[derive(Debug, PartialEq)]
+    struct User {
+        name: String,
+        level: Option<u32>,
+        #[builder(default, push)]
+        tags: Vec<&'static str>,
+    }
+
+    let template = User::builder().name("bon").push_tags("admin").level(3);
+
+    let first = template.build_clone();
+    let second = template.build_clone();
+
+    assert_eq!(first, second);
+    assert_eq!(
+        template.build(),
+        User {
+            name: "bon".to_owned(),
+            level: Some(3),
+            tags: vec!["admin"],
+        }
+    );
+}
+
+#[test]
+fn into_builder_attr() {
+    #[builder(into_builder, to_builder)]
+    #[derive(Debug, PartialEq)]
+    struct Config<T: Clone> {
+        #[builder(start_fn)]
+        name: &'static str,
+        url: String,
+        port: Option<u16>,
+        #[builder(default = 3)]
+        retries: u32,
+        #[builder(required)]
+        payload: Option<T>,
+        #[builder(skip = 30)]
+        timeout: u32,
+        #[builder(finish_fn)]
+        verbose: bool,
+    }
+
+    let config = Config::builder("config")
+        .url("https://example.com")
+        .payload(Some(1))
+        .build(false);
+
+    let changed = config
+        .to_builder()
+        .port(8080)
+        .url("https://other.com")
+        .build(true);
+
+    assert_eq!(
+        changed,
+        Config {
+            name: "config",
+            url: "https://other.com".to_owned(),
+            port: Some(8080),
+            retries: 3,
+            payload: Some(1),
+            timeout: 30,
+            verbose: true,
+        }
+    );
+
+    let changed = changed
+        .into_builder()
+        .retries(5)
+        .maybe_port(None)
+        .build(false);
+
+    assert_eq!(changed.retries, 5);
+    assert_eq!(changed.port, None);
+    assert_eq!(changed.url, "https://other.com");
+
+    // Only `to_builder` requires the members to implement `Clone`
+    #[derive(Debug, PartialEq)]
+    struct NotClone(u32);
+
+    #[builder(into_builder)]
+    struct Wrapper {
+        value: NotClone,
+        label: Option<&'static str>,
+    }
+
+    let wrapper = Wrapper::builder()
+        .value(NotClone(1))
+        .build()
+        .into_builder()
+        .label("label")
+        .build();
+
+    assert_eq!(wrapper.value, NotClone(1));
+    assert_eq!(wrapper.label, Some("label"));
+}
+
+#[test]
+fn apply_to_attr() {
+    #[builder(apply_to)]
+    #[derive(Debug, PartialEq)]
+    struct User {
+        name: String,
+        email: Option<String>,
+        #[builder(default = 18)]
+        age: u32,
+        #[builder(required)]
+        nickname: Option<&'static str>,
+    }
+
+    let mut user = User {
+        name: "Bon".to_owned(),
+        email: Some("bon@example.com".to_owned()),
+        age: 20,
+        nickname: Some("bonny"),
+    };
+
+    User::builder().age(21).apply_to(&mut user);
+
+    assert_eq!(
+        user,
+        User {
+            name: "Bon".to_owned(),
+            email: Some("bon@example.com".to_owned()),
+            age: 21,
+            nickname: Some("bonny"),
+        }
+    );
+
+    User::builder()
+        .name("Builder")
+        .email("builder@example.com")
+        .nickname(None)
+        .apply_to(&mut user);
+
+    assert_eq!(
+        user,
+        User {
+            name: "Builder".to_owned(),
+            email: Some("builder@example.com".to_owned()),
+            age: 21,
+            nickname: None,
+        }
+    );
+
+    User::builder()
+        .maybe_email(None::<String>)
+        .apply_to(&mut user);
+
+    assert_eq!(user.email, None);
+    assert_eq!(user.name, "Builder");
+}
+
+#[test]
+fn mut_setters_attr() {
+    #[builder(mut_setters, derive(Debug))]
+    #[derive(Debug)]
+    struct Request {
+        url: String,
+        timeout: Option<u32>,
+        #[builder(default, push)]
+        headers: Vec<String>,
+        #[builder(flag)]
+        verbose: bool,
+        #[builder(try_into)]
+        retries: Option<u8>,
+    }
+
+    let mut builder = Request::builder().url("https://example.com");
+
+    for (i, name) in ["accept", "origin"].into_iter().enumerate() {
+        builder.push_headers(name);
+
+        if i > 0 {
+            builder.timeout(10).verbose();
+        }
+    }
+
+    builder.timeout(30).try_retries(3_u32).unwrap();
+
+    assert_eq!(
+        format!("{builder:?}"),
+        "RequestBuilder { \
+            url: \"https://example.com\", \
+            timeout: Some(30), \
+            headers: Some([\"accept\", \"origin\"]), \
+            verbose: Some(true), \
+            retries: Some(3) \
+        }"
+    );
+
+    let request = builder.build();
+
+    assert_eq!(request.url, "https://example.com");
+    assert_eq!(request.timeout, Some(30));
+    assert_eq!(request.headers, ["accept", "origin"]);
+    assert!(request.verbose);
+    assert_eq!(request.retries, Some(3));
+}
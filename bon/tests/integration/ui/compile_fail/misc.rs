@@ -21,4 +21,435 @@ fn unnecessary_into_override_false(#[builder(into = false)] _x: u32) {}
 #[builder(start_fn())]
 struct EmptyStartFn {}
 
+
+struct BorrowedReceiver;
+
+#[bon::bon]
+impl BorrowedReceiver {
+    #[builder(owned)]
+    fn method(&self, _x: &str) {}
+}
+
+#[builder(owned)]
+fn elided_output_lifetime(items: &[u32]) -> Option<&u32> {
+    items.first()
+}
+
+#[builder(owned)]
+fn anonymous_output_lifetime(input: &str) -> std::str::Chars<'_> {
+    input.chars()
+}
+
+#[builder]
+fn with_untyped_closure_param(#[builder(with = |x| x)] _x: u32) {}
+
+#[builder]
+fn with_and_into(#[builder(with = |x: u32| x, into)] _x: u32) {}
+
+#[builder(on(u32, into))]
+fn on_into_redundant_override(#[builder(into)] _x: u32) {}
+
+#[builder(on(u32))]
+fn on_without_settings(_x: u32) {}
+
+#[derive(bon::Builder)]
+enum DeriveOnEnum {}
+
+#[builder]
+fn start_fn_with_default(#[builder(start_fn, default)] _x: u32) {}
+
+#[builder]
+fn finish_fn_with_start_fn(#[builder(start_fn, finish_fn)] _x: u32) {}
+
+#[builder]
+fn finish_fn_with_try_into(#[builder(finish_fn, try_into)] _x: u32) {}
+
+#[builder]
+fn skip_with_into(#[builder(skip, into)] _x: String) {}
+
+#[builder]
+struct SkipWithDefault {
+    #[builder(skip = 1, default = 2)]
+    x: u32,
+}
+
+#[builder]
+struct SkippedMemberHasNoSetter {
+    #[builder(skip)]
+    x: u32,
+}
+
+fn skipped_member_has_no_setter() {
+    let _ = SkippedMemberHasNoSetter::builder().x(1).build();
+}
+
+#[builder]
+struct FieldWithStartFn {
+    #[builder(field, start_fn)]
+    x: u32,
+}
+
+#[builder]
+struct PushForNonVec {
+    #[builder(push)]
+    x: u32,
+}
+
+#[builder]
+struct InsertForNonMap {
+    #[builder(insert)]
+    x: Vec<u32>,
+}
+
+#[builder]
+struct RequiredForNonOption {
+    #[builder(required)]
+    x: u32,
+}
+
+#[builder]
+struct RequiredWithDefault {
+    #[builder(required, default = Some(1))]
+    x: Option<u32>,
+}
+
+#[builder]
+struct FlagForNonBool {
+    #[builder(flag)]
+    x: u32,
+}
+
+#[builder]
+struct FlagWithDefault {
+    #[builder(flag, default = true)]
+    x: bool,
+}
+
+#[builder]
+struct MaybeForRequiredMember {
+    #[builder(maybe = false)]
+    x: u32,
+}
+
+#[builder(maybe = true)]
+struct MaybeTrue {
+    x: Option<u32>,
+}
+
+#[builder(on(String, into = false))]
+struct RedundantIntoFalse {
+    #[builder(into = false)]
+    x: String,
+}
+
+#[builder]
+struct AsRefForUnknownType {
+    #[builder(as_ref)]
+    x: u32,
+}
+
+#[builder]
+struct EnvWithoutDefault {
+    #[builder(env = "X")]
+    x: u32,
+}
+
+#[bon::bon]
+trait RequiredTraitMethod {
+    #[builder]
+    fn method(&self, x: u32);
+}
+
+#[builder(validate(async_with = || async { Ok::<_, String>(()) }))]
+fn async_validate_on_sync_fn() {}
+
+trait Level {
+    fn level(&mut self, value: u32);
+    fn name(&mut self, value: String);
+}
+
+#[builder(implements(Level, methods(level, name)))]
+struct ImplementsTypeStateSetter {
+    name: String,
+    level: Option<u32>,
+}
+
+#[builder(mut_setters, implements(Level, methods(level, missing)))]
+struct ImplementsMissingSetter {
+    level: Option<u32>,
+}
+
+#[builder]
+struct DeprecatedWithoutAlias {
+    #[builder(deprecated = "use something else")]
+    x: u32,
+}
+
+#[builder]
+struct AliasSameAsName {
+    #[builder(alias = x)]
+    x: u32,
+}
+
+#[builder(track_caller)]
+async fn track_caller_async() {}
+
+mod private_finish_fn {
+    #[bon::builder(finish_fn(vis = ""))]
+    pub fn private_finish_fn() {}
+}
+
+fn call_private_finish_fn() {
+    private_finish_fn::private_finish_fn().call();
+}
+
+#[builder(crate = bon)]
+struct RelativeCratePath {}
+
+#[builder]
+struct MissingMember {
+    url: String,
+    retries: Option<u32>,
+}
+
+fn build_without_required_member() {
+    let _ = MissingMember::builder().retries(3).build();
+}
+
+#[builder]
+struct UnexpectedEndOfMemberParams {
+    #[builder(default = )]
+    x: u32,
+}
+
+#[builder(validate = 1)]
+struct ValidateWithNonFunction {}
+
+#[builder]
+struct SetterNameCollision {
+    x: u32,
+    _x: u32,
+}
+
+#[builder]
+struct MaybeSetterNameCollision {
+    x: Option<u32>,
+    maybe_x: u32,
+}
+
+#[builder]
+struct SetterCollidesWithFinishFn {
+    build: u32,
+}
+
+#[builder]
+struct RequiresUnknownMember {
+    #[builder(requires = y)]
+    x: Option<u32>,
+}
+
+#[builder]
+struct RequiresItself {
+    #[builder(requires = x)]
+    x: Option<u32>,
+}
+
+#[builder]
+struct RequiresUnsetMember {
+    host: Option<String>,
+    #[builder(requires = host)]
+    port: Option<u16>,
+}
+
+fn set_member_without_its_requirement() {
+    let _ = RequiresUnsetMember::builder().port(80).build();
+}
+
+#[builder]
+struct ConflictsWithRequiredMember {
+    x: u32,
+    #[builder(conflicts_with = x)]
+    y: Option<u32>,
+}
+
+#[builder]
+struct Source {
+    path: Option<String>,
+    #[builder(conflicts_with = path)]
+    url: Option<String>,
+}
+
+fn set_conflicting_members() {
+    let _ = Source::builder().path("a").url("b").build();
+    let _ = Source::builder().url("b").path("a").build();
+}
+
+#[builder]
+struct GroupWithSingleMember {
+    #[builder(group = source)]
+    path: Option<String>,
+}
+
+#[builder]
+struct GroupWithRequiredMember {
+    #[builder(group = source)]
+    path: String,
+    #[builder(group = source)]
+    url: Option<String>,
+}
+
+#[builder]
+struct Input {
+    #[builder(group = source)]
+    path: Option<String>,
+    #[builder(group = source)]
+    url: Option<String>,
+}
+
+fn build_without_group_member() {
+    let _ = Input::builder().build();
+}
+
+#[builder]
+struct SectionWithRequiredMember {
+    #[builder(section = http)]
+    port: u16,
+}
+
+#[builder]
+struct Server {
+    #[builder(section = http)]
+    port: Option<u16>,
+}
+
+fn set_section_member_directly() {
+    let _ = Server::builder().port(80).build();
+}
+
+#[builder]
+struct NestedMemberWithInto {
+    #[builder(nested, into)]
+    http: Http,
+}
+
+#[builder(preset(name = localhost, host = "127.0.0.1"))]
+struct PresetWithUnknownMember {
+    url: String,
+}
+
+#[builder(finish_fn(map = wrap))]
+struct MappedWithUnknownOutput {
+    name: String,
+}
+
+#[builder(finish_fn(map = std::sync::Arc::new))]
+struct MappedWithWrapperNew {
+    name: String,
+}
+
+#[builder(finish_fn(unwrap = build_or_panic))]
+struct UnwrapWithoutValidation {
+    name: String,
+}
+
+#[builder(check = "runtime", merge)]
+struct RuntimeChecksWithMerge {
+    name: String,
+}
+
+#[builder(check = "runtime")]
+struct RuntimeChecksWithRequires {
+    name: Option<String>,
+
+    #[builder(requires = name)]
+    alias: Option<String>,
+}
+
+#[builder(check = "never")]
+struct UnknownCheck {
+    name: String,
+}
+
+#[builder(dyn_builder)]
+struct DynBuilderWithTypeState {
+    name: String,
+}
+
+#[builder(apply_to, pre_build = |_| {})]
+struct ApplyToWithPreBuild {
+    name: String,
+}
+
+#[builder(apply_to)]
+struct ApplyToWithMemberValidator {
+    #[builder(validate = |name: &String| if name.is_empty() { Err("empty") } else { Ok(()) })]
+    name: String,
+}
+
+#[builder]
+struct FlattenedRetry {
+    attempts: u32,
+}
+
+#[builder]
+struct FlattenWithInto {
+    #[builder(flatten, into)]
+    retry: FlattenedRetry,
+}
+
+#[builder(default_from = Default)]
+struct FlattenWithDefaultFrom {
+    #[builder(flatten)]
+    retry: FlattenedRetry,
+}
+
+#[builder]
+struct FlattenReference {
+    #[builder(flatten)]
+    retry: &'static FlattenedRetry,
+}
+
+#[builder]
+struct FlattenGenericType {
+    #[builder(flatten)]
+    retries: Vec<FlattenedRetry>,
+}
+
+#[builder]
+struct FlattenWithoutBuilder {
+    #[builder(flatten)]
+    name: String,
+}
+
+#[derive(bon::Builder)]
+struct FlattenInDerive {
+    #[builder(flatten)]
+    retry: FlattenedRetry,
+}
+
+struct FlattenInMethod;
+
+#[bon::bon]
+impl FlattenInMethod {
+    #[builder]
+    fn method(#[builder(flatten)] _retry: FlattenedRetry) {}
+}
+
+#[builder(extends = FlattenedRetry)]
+struct ExtendsWithoutField {
+    name: String,
+}
+
+#[builder(extends = FlattenedRetry)]
+struct ExtendsAmbiguous {
+    retry: FlattenedRetry,
+    fallback_retry: FlattenedRetry,
+}
+
+#[builder(extends = FlattenedRetry)]
+struct ExtendsRedundantFlatten {
+    #[builder(flatten)]
+    retry: FlattenedRetry,
+}
+
 fn main() {}
@@ -0,0 +1,15 @@
+#![deny(unused_must_use)]
+
+use bon::builder;
+
+#[builder]
+struct Example {
+    x: u32,
+    tags: Vec<String>,
+}
+
+fn main() {
+    Example::builder();
+    Example::builder().x(1);
+    Example::builder().tags(vec![]).x(1);
+}
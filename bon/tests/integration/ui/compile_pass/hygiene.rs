@@ -0,0 +1,89 @@
+//! Generated code must compile in environments where the standard prelude
+//! is unavailable or where its items are shadowed by local items.
+
+#[no_implicit_prelude]
+mod no_implicit_prelude {
+    #[::bon::builder(closure_fn, implements = Finish)]
+    pub(crate) struct Struct {
+        required: ::std::string::String,
+        optional: ::core::option::Option<::std::string::String>,
+
+        #[builder(default = "default")]
+        with_default: ::std::string::String,
+    }
+
+    pub(crate) trait Finish {
+        fn build(self) -> Struct;
+    }
+
+    #[::bon::builder(owned)]
+    pub(crate) async fn function(
+        required: &::core::primitive::str,
+        optional: ::core::option::Option<u32>,
+        #[builder(default)] with_default: u32,
+    ) -> u32 {
+        let _ = required;
+        optional.unwrap_or(with_default)
+    }
+
+    pub(crate) struct Counter;
+
+    #[::bon::bon]
+    impl Counter {
+        #[builder]
+        pub(crate) fn new(_initial: ::core::option::Option<u32>) -> Self {
+            Self
+        }
+
+        #[builder]
+        pub(crate) fn method(&self, _value: ::std::string::String) {}
+    }
+}
+
+mod shadowed_prelude {
+    #![allow(dead_code, non_camel_case_types)]
+
+    struct Option;
+    struct Some;
+    struct None;
+    struct Into;
+    struct From;
+    struct Default;
+    struct Clone;
+    struct Copy;
+    struct Debug;
+    struct Vec;
+    struct String;
+    struct Box;
+    struct Result;
+    struct str;
+
+    mod std {}
+    mod core {}
+
+    #[bon::builder(closure_fn, default_from = Sut::preset())]
+    struct Sut {
+        required: ::std::string::String,
+        optional: ::core::option::Option<u32>,
+    }
+
+    impl Sut {
+        fn preset() -> Self {
+            Self {
+                required: ::std::string::String::new(),
+                optional: ::core::option::Option::None,
+            }
+        }
+    }
+
+    #[bon::builder(owned)]
+    fn function(
+        required: &::core::primitive::str,
+        optional: ::core::option::Option<&::core::primitive::str>,
+        #[builder(default = 42)] with_default: u32,
+    ) {
+        let _ = (required, optional, with_default);
+    }
+}
+
+fn main() {}
@@ -0,0 +1,43 @@
+//! The precise capturing syntax `use<..>` in the return types is carried over
+//! to the finishing functions and the closure functions.
+
+use bon::{bon, builder};
+
+#[builder(closure_fn)]
+fn free<'a, T: Clone>(items: &'a [T], skip: usize) -> impl Iterator<Item = T> + use<'a, T> {
+    items.iter().skip(skip).cloned()
+}
+
+// The generic parameter has the same name as the one that the closure
+// function would use for the closure by default
+#[builder(closure_fn)]
+fn colliding<__Configure: Copy>(value: __Configure) -> impl Copy + use<__Configure> {
+    value
+}
+
+struct Container {
+    items: Vec<u32>,
+}
+
+#[bon]
+impl Container {
+    #[builder(closure_fn)]
+    fn offset<'a>(&'a self, offset: u32) -> impl Iterator<Item = u32> + use<'a> {
+        self.items.iter().map(move |item| item + offset)
+    }
+}
+
+fn main() {
+    let items = [1, 2, 3];
+
+    let _ = free().items(&items).skip(1).call().count();
+    let _ = free_with(|b| b.items(&items).skip(1)).count();
+
+    let _ = colliding().value(1).call();
+    let _ = colliding_with(|b| b.value(1));
+
+    let container = Container { items: vec![1, 2] };
+
+    let _ = container.offset().offset(1).call().count();
+    let _ = container.offset_with(|b| b.offset(1)).count();
+}
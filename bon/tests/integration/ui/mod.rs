@@ -2,4 +2,13 @@
 fn ui() {
     let t = trybuild::TestCases::new();
     t.compile_fail("tests/integration/ui/compile_fail/*.rs");
+    t.pass("tests/integration/ui/compile_pass/*.rs");
+}
+
+/// The precise capturing syntax `use<..>` is stable only since Rust 1.82
+#[rustversion::since(1.82)]
+#[test]
+fn ui_since_1_82() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/integration/ui/compile_pass_since_1_82/*.rs");
 }
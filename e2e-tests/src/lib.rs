@@ -85,3 +85,26 @@ pub fn greet(
 ) -> String {
     format!("Hello {name} with age {age}!")
 }
+
+/// Unsafe function whose `# Safety` section is carried over to the finishing
+/// function of the builder.
+///
+/// # Safety
+///
+/// The `value` must be a valid number. The hidden lines of the doc test must
+/// not end the section:
+///
+/// ```
+/// let value: u32 =
+/// # 42;
+/// assert_eq!(unsafe { e2e_tests::parse_unchecked().value(value).call() }, 42);
+/// ```
+///
+/// # Examples
+///
+/// This section is not carried over.
+#[builder]
+#[allow(unsafe_code)]
+pub unsafe fn parse_unchecked(value: u32) -> u32 {
+    value
+}
@@ -89,6 +89,10 @@ export default defineConfig({
                             text: "Documenting",
                             link: "/docs/guide/documenting",
                         },
+                        {
+                            text: "Reusable members",
+                            link: "/docs/guide/reusable-members",
+                        },
                         {
                             text: "Limitations",
                             link: "/docs/guide/limitations",